@@ -5,8 +5,16 @@
 #include <stdlib.h>
 
 
+#define DOTLOTTIE_ANIMATION_NOT_FOUND 8
+
 #define DOTLOTTIE_ERROR 1
 
+#define DOTLOTTIE_FILE_READ_ERROR 7
+
+#define DOTLOTTIE_FRAME_OUT_OF_RANGE 11
+
+#define DOTLOTTIE_INVALID_FILE 6
+
 #define DOTLOTTIE_INVALID_PARAMETER 2
 
 #define DOTLOTTIE_MANIFEST_NOT_AVAILABLE 3
@@ -15,8 +23,32 @@
 
 #define DOTLOTTIE_MAX_STR_LENGTH 512
 
+/**
+ * The failures of loading animations, themes and state machines, restoring snapshots, setting
+ * frames, seeking and resizing are reported with the codes from 5 on instead of
+ * DOTLOTTIE_ERROR. Any code other than DOTLOTTIE_SUCCESS is a failure, dotlottie_last_error
+ * describes it.
+ */
+#define DOTLOTTIE_RENDERER_ERROR 5
+
+#define DOTLOTTIE_STATE_MACHINE_ERROR 10
+
 #define DOTLOTTIE_SUCCESS 0
 
+#define DOTLOTTIE_THEME_NOT_FOUND 9
+
+#define LISTENER_TYPE_BLUR (1 << 8)
+
+#define LISTENER_TYPE_DOUBLE_CLICK (1 << 11)
+
+#define LISTENER_TYPE_FOCUS (1 << 7)
+
+#define LISTENER_TYPE_KEY_DOWN (1 << 5)
+
+#define LISTENER_TYPE_KEY_UP (1 << 6)
+
+#define LISTENER_TYPE_LONG_PRESS (1 << 10)
+
 #define LISTENER_TYPE_POINTER_DOWN (1 << 1)
 
 #define LISTENER_TYPE_POINTER_ENTER (1 << 2)
@@ -27,8 +59,26 @@
 
 #define LISTENER_TYPE_POINTER_UP (1 << 0)
 
+#define LISTENER_TYPE_SCROLL (1 << 9)
+
+#define LISTENER_TYPE_SWIPE (1 << 12)
+
 #define LISTENER_TYPE_UNSET 0
 
+typedef enum DotLottieDiagnosticSeverity {
+  Error,
+  Warning,
+} DotLottieDiagnosticSeverity;
+
+typedef enum DotLottieEventResult {
+  Success,
+  Failure,
+  Play,
+  Pause,
+  DrawFrame,
+  Queued,
+} DotLottieEventResult;
+
 typedef enum DotLottieFit {
   Contain,
   Fill,
@@ -38,6 +88,13 @@ typedef enum DotLottieFit {
   Void,
 } DotLottieFit;
 
+typedef enum DotLottieSwipeDirection {
+  Left,
+  Right,
+  Up,
+  Down,
+} DotLottieSwipeDirection;
+
 typedef enum Mode {
   Forward,
   Reverse,
@@ -155,6 +212,17 @@ typedef enum DotLottieEvent_Tag {
   OnPointerExit,
   OnComplete,
   SetNumericContext,
+  OnMarkerReached,
+  OnFrameReached,
+  OnLoopComplete,
+  OnKeyDown,
+  OnKeyUp,
+  OnFocus,
+  OnBlur,
+  OnScroll,
+  OnLongPress,
+  OnDoubleClick,
+  OnSwipe,
 } DotLottieEvent_Tag;
 
 typedef struct Boolean_Body {
@@ -199,6 +267,41 @@ typedef struct SetNumericContext_Body {
   float value;
 } SetNumericContext_Body;
 
+typedef struct OnMarkerReached_Body {
+  struct DotLottieString name;
+} OnMarkerReached_Body;
+
+typedef struct OnFrameReached_Body {
+  float frame;
+} OnFrameReached_Body;
+
+typedef struct OnKeyDown_Body {
+  struct DotLottieString key;
+} OnKeyDown_Body;
+
+typedef struct OnKeyUp_Body {
+  struct DotLottieString key;
+} OnKeyUp_Body;
+
+typedef struct OnScroll_Body {
+  float delta_x;
+  float delta_y;
+} OnScroll_Body;
+
+typedef struct OnLongPress_Body {
+  float x;
+  float y;
+} OnLongPress_Body;
+
+typedef struct OnDoubleClick_Body {
+  float x;
+  float y;
+} OnDoubleClick_Body;
+
+typedef struct OnSwipe_Body {
+  enum DotLottieSwipeDirection direction;
+} OnSwipe_Body;
+
 typedef struct DotLottieEvent {
   DotLottieEvent_Tag tag;
   union {
@@ -211,21 +314,90 @@ typedef struct DotLottieEvent {
     OnPointerEnter_Body on_pointer_enter;
     OnPointerExit_Body on_pointer_exit;
     SetNumericContext_Body set_numeric_context;
+    OnMarkerReached_Body on_marker_reached;
+    OnFrameReached_Body on_frame_reached;
+    OnKeyDown_Body on_key_down;
+    OnKeyUp_Body on_key_up;
+    OnScroll_Body on_scroll;
+    OnLongPress_Body on_long_press;
+    OnDoubleClick_Body on_double_click;
+    OnSwipe_Body on_swipe;
   };
 } DotLottieEvent;
 
+typedef struct DotLottieEventInfo {
+  struct DotLottieString event_type;
+  struct DotLottieString value;
+} DotLottieEventInfo;
+
+typedef struct DotLottieContextInfo {
+  struct DotLottieString key;
+  struct DotLottieString context_type;
+  struct DotLottieString value;
+} DotLottieContextInfo;
+
+typedef struct DotLottieListenerInfo {
+  struct DotLottieString listener_type;
+  struct DotLottieString target;
+  struct DotLottieString actions;
+} DotLottieListenerInfo;
+
+typedef struct DotLottieStateInfo {
+  struct DotLottieString name;
+  struct DotLottieString path;
+  struct DotLottieString state_type;
+  bool active;
+  struct DotLottieString config;
+} DotLottieStateInfo;
+
 typedef void (*OnTransitionOp)(const char*, const char*);
 
 typedef void (*OnStateEnteredOp)(const char*);
 
 typedef void (*OnStateExitOp)(const char*);
 
+typedef void (*OnActionOp)(const char*, const char*, const char*);
+
+typedef void (*OnContextResetOp)(const char*);
+
+typedef void (*OnContextChangedOp)(const char*, const char*, const char*);
+
+typedef void (*OnEventRejectedOp)(const char*, const char*);
+
+typedef void (*OnListenerTriggeredOp)(const char*, const char*);
+
+typedef void (*OnCustomEventOp)(const char*);
+
+typedef void (*OnErrorOp)(const char*);
+
+/**
+ * The callbacks of a state machine observer. The ones after on_state_exit_op are optional and
+ * skipped when null, zero the struct before setting the callbacks in use.
+ *
+ * on_context_changed_op to on_error_op were appended to the struct, changing its size. Hosts
+ * built against a header without them have to be rebuilt.
+ */
 typedef struct StateMachineObserver {
   OnTransitionOp on_transition_op;
   OnStateEnteredOp on_state_entered_op;
   OnStateExitOp on_state_exit_op;
+  OnActionOp on_action_op;
+  OnContextResetOp on_context_reset_op;
+  OnContextChangedOp on_context_changed_op;
+  OnEventRejectedOp on_event_rejected_op;
+  OnListenerTriggeredOp on_listener_triggered_op;
+  OnCustomEventOp on_custom_event_op;
+  OnErrorOp on_error_op;
 } StateMachineObserver;
 
+typedef struct DotLottieTransitionInfo {
+  struct DotLottieString from_state;
+  struct DotLottieString to_state;
+  struct DotLottieEventInfo event;
+  struct DotLottieString guards;
+  struct DotLottieString actions;
+} DotLottieTransitionInfo;
+
 typedef void (*OnOp)(void);
 
 typedef void (*OnFrameOp)(float);
@@ -246,6 +418,12 @@ typedef struct Observer {
   OnOp on_complete_op;
 } Observer;
 
+typedef struct DotLottieDiagnostic {
+  enum DotLottieDiagnosticSeverity severity;
+  struct DotLottieString path;
+  struct DotLottieString message;
+} DotLottieDiagnostic;
+
 int32_t dotlottie_active_animation_id(struct DotLottiePlayer *ptr, char *result);
 
 int32_t dotlottie_active_theme_id(struct DotLottiePlayer *ptr, char *result);
@@ -276,6 +454,8 @@ int32_t dotlottie_is_playing(struct DotLottiePlayer *ptr);
 
 int32_t dotlottie_is_stopped(struct DotLottiePlayer *ptr);
 
+int32_t dotlottie_last_error(struct DotLottiePlayer *ptr, char *result);
+
 int32_t dotlottie_layer_bounds(struct DotLottiePlayer *ptr,
                                const char *layer_name,
                                struct LayerBoundingBox *bounding_box);
@@ -341,14 +521,36 @@ int32_t dotlottie_pause(struct DotLottiePlayer *ptr);
 
 int32_t dotlottie_play(struct DotLottiePlayer *ptr);
 
+/**
+ * Handles an event, returning 0 when it was handled, 1 when it failed, 2 when the animation
+ * was played, 3 when it was paused and 4 when a new frame needs to be drawn.
+ *
+ * Kept for existing hosts, dotlottie_post_event_with_result reports every EventResult.
+ */
 int32_t dotlottie_post_event(struct DotLottiePlayer *ptr, const struct DotLottieEvent *event);
 
+/**
+ * Handles an event, the returned value is a status code and the EventResult is written to
+ * result.
+ */
+int32_t dotlottie_post_event_with_result(struct DotLottiePlayer *ptr,
+                                         const struct DotLottieEvent *event,
+                                         enum DotLottieEventResult *result);
+
+int32_t dotlottie_process_events(struct DotLottiePlayer *ptr, enum DotLottieEventResult *result);
+
+int32_t dotlottie_queue_event(struct DotLottiePlayer *ptr, const struct DotLottieEvent *event);
+
 int32_t dotlottie_render(struct DotLottiePlayer *ptr);
 
 int32_t dotlottie_request_frame(struct DotLottiePlayer *ptr, float *result);
 
+int32_t dotlottie_request_frame_at(struct DotLottiePlayer *ptr, double time_ms, float *result);
+
 int32_t dotlottie_resize(struct DotLottiePlayer *ptr, uint32_t width, uint32_t height);
 
+int32_t dotlottie_restore_state_machine_snapshot(struct DotLottiePlayer *ptr, const char *snapshot);
+
 int32_t dotlottie_seek(struct DotLottiePlayer *ptr, float no);
 
 int32_t dotlottie_segment_duration(struct DotLottiePlayer *ptr, float *result);
@@ -375,11 +577,35 @@ int32_t dotlottie_set_viewport(struct DotLottiePlayer *ptr,
 
 int32_t dotlottie_start_state_machine(struct DotLottiePlayer *ptr);
 
+int32_t dotlottie_state_machine_available_events(struct DotLottiePlayer *ptr,
+                                                 struct DotLottieEventInfo *result,
+                                                 size_t *size);
+
+int32_t dotlottie_state_machine_context(struct DotLottiePlayer *ptr,
+                                        struct DotLottieContextInfo *result,
+                                        size_t *size);
+
 int32_t dotlottie_state_machine_framework_setup(struct DotLottiePlayer *ptr, uint16_t *result);
 
+int32_t dotlottie_state_machine_listeners(struct DotLottiePlayer *ptr,
+                                          struct DotLottieListenerInfo *result,
+                                          size_t *size);
+
+int32_t dotlottie_state_machine_snapshot(struct DotLottiePlayer *ptr, char *result, size_t *size);
+
+int32_t dotlottie_state_machine_states(struct DotLottiePlayer *ptr,
+                                       struct DotLottieStateInfo *result,
+                                       size_t *size);
+
 int32_t dotlottie_state_machine_subscribe(struct DotLottiePlayer *ptr,
                                           struct StateMachineObserver *observer);
 
+int32_t dotlottie_state_machine_to_json(struct DotLottiePlayer *ptr, char *result, size_t *size);
+
+int32_t dotlottie_state_machine_transitions(struct DotLottiePlayer *ptr,
+                                            struct DotLottieTransitionInfo *result,
+                                            size_t *size);
+
 int32_t dotlottie_state_machine_unsubscribe(struct DotLottiePlayer *ptr,
                                             struct StateMachineObserver *observer);
 
@@ -389,6 +615,13 @@ int32_t dotlottie_stop_state_machine(struct DotLottiePlayer *ptr);
 
 int32_t dotlottie_subscribe(struct DotLottiePlayer *ptr, struct Observer *observer);
 
+int32_t dotlottie_tick(struct DotLottiePlayer *ptr, double delta_ms, float *result);
+
 int32_t dotlottie_total_frames(struct DotLottiePlayer *ptr, float *result);
 
 int32_t dotlottie_unsubscribe(struct DotLottiePlayer *ptr, struct Observer *observer);
+
+int32_t dotlottie_validate_state_machine(struct DotLottiePlayer *ptr,
+                                         const char *state_machine_definition,
+                                         struct DotLottieDiagnostic *result,
+                                         size_t *size);
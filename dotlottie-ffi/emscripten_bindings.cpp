@@ -24,6 +24,12 @@ EMSCRIPTEN_BINDINGS(DotLottiePlayer)
     // Register std::vector<float> as VectorFloat for the Config::segment field
     register_vector<float>("VectorFloat");
     register_vector<Marker>("VectorMarker");
+    register_vector<Diagnostic>("VectorDiagnostic");
+    register_vector<StateInfo>("VectorStateInfo");
+    register_vector<TransitionInfo>("VectorTransitionInfo");
+    register_vector<ListenerInfo>("VectorListenerInfo");
+    register_vector<ContextInfo>("VectorContextInfo");
+    register_vector<EventInfo>("VectorEventInfo");
     register_vector<std::string>("VectorString");
     // register_vector<ManifestTheme>("VectorManifestTheme");
     // register_vector<ManifestAnimation>("VectorManifestAnimation");
@@ -53,6 +59,51 @@ EMSCRIPTEN_BINDINGS(DotLottiePlayer)
         .field("time", &Marker::time)
         .field("duration", &Marker::duration);
 
+    enum_<EventResult>("EventResult")
+        .value("Success", EventResult::kSuccess)
+        .value("Failure", EventResult::kFailure)
+        .value("Play", EventResult::kPlay)
+        .value("Pause", EventResult::kPause)
+        .value("DrawFrame", EventResult::kDrawFrame)
+        .value("Queued", EventResult::kQueued);
+
+    enum_<DiagnosticSeverity>("DiagnosticSeverity")
+        .value("Error", DiagnosticSeverity::kError)
+        .value("Warning", DiagnosticSeverity::kWarning);
+
+    value_object<Diagnostic>("Diagnostic")
+        .field("severity", &Diagnostic::severity)
+        .field("path", &Diagnostic::path)
+        .field("message", &Diagnostic::message);
+
+    value_object<StateInfo>("StateInfo")
+        .field("name", &StateInfo::name)
+        .field("path", &StateInfo::path)
+        .field("stateType", &StateInfo::state_type)
+        .field("active", &StateInfo::active)
+        .field("config", &StateInfo::config);
+
+    value_object<EventInfo>("EventInfo")
+        .field("eventType", &EventInfo::event_type)
+        .field("value", &EventInfo::value);
+
+    value_object<TransitionInfo>("TransitionInfo")
+        .field("fromState", &TransitionInfo::from_state)
+        .field("toState", &TransitionInfo::to_state)
+        .field("event", &TransitionInfo::event)
+        .field("guards", &TransitionInfo::guards)
+        .field("actions", &TransitionInfo::actions);
+
+    value_object<ListenerInfo>("ListenerInfo")
+        .field("listenerType", &ListenerInfo::listener_type)
+        .field("target", &ListenerInfo::target)
+        .field("actions", &ListenerInfo::actions);
+
+    value_object<ContextInfo>("ContextInfo")
+        .field("key", &ContextInfo::key)
+        .field("contextType", &ContextInfo::context_type)
+        .field("value", &ContextInfo::value);
+
     value_object<Config>("Config")
         .field("autoplay", &Config::autoplay)
         .field("loopAnimation", &Config::loop_animation)
@@ -128,6 +179,7 @@ EMSCRIPTEN_BINDINGS(DotLottiePlayer)
         .function("loadAnimationPath", &DotLottiePlayer::load_animation_path, allow_raw_pointers())
         .function("loadDotLottieData", &load_dotlottie_data, allow_raw_pointers())
         .function("loadAnimation", &DotLottiePlayer::load_animation, allow_raw_pointers())
+        .function("lastErrorMessage", &DotLottiePlayer::last_error_message)
         // .function("manifest", &DotLottiePlayer::manifest)
         .function("manifestString", &DotLottiePlayer::manifest_string)
         .function("loopCount", &DotLottiePlayer::loop_count)
@@ -135,11 +187,13 @@ EMSCRIPTEN_BINDINGS(DotLottiePlayer)
         .function("play", &DotLottiePlayer::play)
         .function("render", &DotLottiePlayer::render)
         .function("requestFrame", &DotLottiePlayer::request_frame)
+        .function("requestFrameAt", &DotLottiePlayer::request_frame_at)
         .function("resize", &DotLottiePlayer::resize)
         .function("setConfig", &DotLottiePlayer::set_config)
         .function("setFrame", &DotLottiePlayer::set_frame)
         .function("seek", &DotLottiePlayer::seek)
         .function("stop", &DotLottiePlayer::stop)
+        .function("tick", &DotLottiePlayer::tick)
         .function("totalFrames", &DotLottiePlayer::total_frames)
         // .function("subscribe", &DotLottiePlayer::subscribe)
         // .function("unsubscribe", &DotLottiePlayer::unsubscribe)
@@ -161,6 +215,15 @@ EMSCRIPTEN_BINDINGS(DotLottiePlayer)
         .function("setStateMachineStringContext", &DotLottiePlayer::set_state_machine_string_context)
         .function("setStateMachineBooleanContext", &DotLottiePlayer::set_state_machine_boolean_context)
         .function("loadStateMachineData", &DotLottiePlayer::load_state_machine_data)
+        .function("validateStateMachine", &DotLottiePlayer::validate_state_machine)
+        .function("stateMachineToJson", &DotLottiePlayer::state_machine_to_json)
+        .function("stateMachineSnapshot", &DotLottiePlayer::state_machine_snapshot)
+        .function("restoreStateMachineSnapshot", &DotLottiePlayer::restore_state_machine_snapshot)
+        .function("stateMachineStates", &DotLottiePlayer::state_machine_states)
+        .function("stateMachineTransitions", &DotLottiePlayer::state_machine_transitions)
+        .function("stateMachineListeners", &DotLottiePlayer::state_machine_listeners)
+        .function("stateMachineContext", &DotLottiePlayer::state_machine_context)
+        .function("stateMachineAvailableEvents", &DotLottiePlayer::state_machine_available_events)
         .function("getLayerBounds", &DotLottiePlayer::get_layer_bounds)
         .function("postBoolEvent", &DotLottiePlayer::post_bool_event)
         .function("postStringEvent", &DotLottiePlayer::post_string_event)
@@ -170,7 +233,13 @@ EMSCRIPTEN_BINDINGS(DotLottiePlayer)
         .function("postPointerMoveEvent", &DotLottiePlayer::post_pointer_move_event)
         .function("postPointerEnterEvent", &DotLottiePlayer::post_pointer_enter_event)
         .function("postPointerExitEvent", &DotLottiePlayer::post_pointer_exit_event)
-        .function("postSetNumericContext", &DotLottiePlayer::post_set_numeric_context);
+        .function("postKeyDownEvent", &DotLottiePlayer::post_key_down_event)
+        .function("postKeyUpEvent", &DotLottiePlayer::post_key_up_event)
+        .function("postFocusEvent", &DotLottiePlayer::post_focus_event)
+        .function("postBlurEvent", &DotLottiePlayer::post_blur_event)
+        .function("postScrollEvent", &DotLottiePlayer::post_scroll_event)
+        .function("postSetNumericContext", &DotLottiePlayer::post_set_numeric_context)
+        .function("processEvents", &DotLottiePlayer::process_events);
     // .function("state_machine_subscribe", &DotLottiePlayer::state_machine_subscribe)
     // .function("state_machine_unsubscribe", &DotLottiePlayer::state_machine_unsubscribe)
 }
@@ -1,6 +1,8 @@
 use std::{ffi::c_char, slice};
 
-use dotlottie_rs::{Config, DotLottiePlayer, LayerBoundingBox};
+use dotlottie_rs::{
+    Config, DotLottieError, DotLottiePlayer, DotLottiePlayerError, EventResult, LayerBoundingBox,
+};
 use types::*;
 
 pub mod types;
@@ -26,6 +28,53 @@ fn to_exit_status(result: bool) -> i32 {
     }
 }
 
+// Translates rust results into C return codes, keeping the cause of the failure
+fn to_result_status<T>(result: Result<T, DotLottiePlayerError>) -> i32 {
+    match result {
+        Ok(_) => DOTLOTTIE_SUCCESS,
+        Err(error) => to_error_code(&error),
+    }
+}
+
+// Copies a string of any length into a C buffer of the given size. Without a buffer, the size
+// needed for the string and its nul terminator is returned instead
+unsafe fn transfer_string(value: &str, result: *mut c_char, size: *mut usize) -> i32 {
+    if size.is_null() {
+        return DOTLOTTIE_INVALID_PARAMETER;
+    }
+
+    let required_size = value.len() + 1;
+
+    if result.is_null() {
+        *size = required_size;
+        DOTLOTTIE_SUCCESS
+    } else if *size < required_size {
+        DOTLOTTIE_INVALID_PARAMETER
+    } else {
+        *size = required_size;
+        to_exit_status(DotLottieString::copy(value, result, required_size).is_ok())
+    }
+}
+
+fn to_error_code(error: &DotLottiePlayerError) -> i32 {
+    match error {
+        DotLottiePlayerError::RendererError(_) => DOTLOTTIE_RENDERER_ERROR,
+        DotLottiePlayerError::DotLottieError(error) => match error {
+            DotLottieError::AnimationNotFound { .. } | DotLottieError::AnimationsNotFound => {
+                DOTLOTTIE_ANIMATION_NOT_FOUND
+            }
+            DotLottieError::StateMachineError { .. } => DOTLOTTIE_STATE_MACHINE_ERROR,
+            _ => DOTLOTTIE_INVALID_FILE,
+        },
+        DotLottiePlayerError::StateMachineError(_) => DOTLOTTIE_STATE_MACHINE_ERROR,
+        DotLottiePlayerError::FileReadError { .. } => DOTLOTTIE_FILE_READ_ERROR,
+        DotLottiePlayerError::FrameOutOfRange { .. } => DOTLOTTIE_FRAME_OUT_OF_RANGE,
+        DotLottiePlayerError::ThemeNotFound { .. }
+        | DotLottiePlayerError::ThemeNotAvailable { .. } => DOTLOTTIE_THEME_NOT_FOUND,
+        DotLottiePlayerError::LockError => DOTLOTTIE_ERROR,
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn dotlottie_new_player(ptr: *const DotLottieConfig) -> *mut DotLottiePlayer {
     if let Some(dotlottie_config) = ptr.as_ref() {
@@ -59,7 +108,11 @@ pub unsafe extern "C" fn dotlottie_load_animation_data(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         if let Ok(animation_data) = DotLottieString::read(animation_data) {
-            to_exit_status(dotlottie_player.load_animation_data(&animation_data, width, height))
+            to_result_status(dotlottie_player.try_load_animation_data(
+                &animation_data,
+                width,
+                height,
+            ))
         } else {
             DOTLOTTIE_INVALID_PARAMETER
         }
@@ -75,7 +128,11 @@ pub unsafe extern "C" fn dotlottie_load_animation_path(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         if let Ok(animation_path) = DotLottieString::read(animation_path) {
-            to_exit_status(dotlottie_player.load_animation_path(&animation_path, width, height))
+            to_result_status(dotlottie_player.try_load_animation_path(
+                &animation_path,
+                width,
+                height,
+            ))
         } else {
             DOTLOTTIE_INVALID_PARAMETER
         }
@@ -91,7 +148,7 @@ pub unsafe extern "C" fn dotlottie_load_animation(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         if let Ok(animation_id) = DotLottieString::read(animation_id) {
-            to_exit_status(dotlottie_player.load_animation(&animation_id, width, height))
+            to_result_status(dotlottie_player.try_load_animation(&animation_id, width, height))
         } else {
             DOTLOTTIE_INVALID_PARAMETER
         }
@@ -108,7 +165,7 @@ pub unsafe extern "C" fn dotlottie_load_dotlottie_data(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         let file_slice = slice::from_raw_parts(file_data as *const u8, file_size);
-        to_exit_status(dotlottie_player.load_dotlottie_data(file_slice, width, height))
+        to_result_status(dotlottie_player.try_load_dotlottie_data(file_slice, width, height))
     })
 }
 
@@ -213,6 +270,19 @@ pub unsafe extern "C" fn dotlottie_manifest_states(
     })
 }
 
+// The result of handling the events is only written when a result pointer is given
+unsafe fn transfer_event_result(value: EventResult, result: *mut DotLottieEventResult) -> i32 {
+    if let Some(result) = result.as_mut() {
+        *result = value.into();
+    }
+
+    DOTLOTTIE_SUCCESS
+}
+
+/// Handles an event, returning 0 when it was handled, 1 when it failed, 2 when the animation
+/// was played, 3 when it was paused and 4 when a new frame needs to be drawn.
+///
+/// Kept for existing hosts, dotlottie_post_event_with_result reports every EventResult.
 #[no_mangle]
 pub unsafe extern "C" fn dotlottie_post_event(
     ptr: *mut DotLottiePlayer,
@@ -220,13 +290,61 @@ pub unsafe extern "C" fn dotlottie_post_event(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         if let Some(event) = event.as_ref() {
-            dotlottie_player.post_event(&event.to_event())
+            match dotlottie_player.post_event(&event.to_event()) {
+                EventResult::Success | EventResult::Queued => DOTLOTTIE_SUCCESS,
+                EventResult::Failure => DOTLOTTIE_ERROR,
+                EventResult::Play => 2,
+                EventResult::Pause => 3,
+                EventResult::DrawFrame => 4,
+            }
         } else {
             DOTLOTTIE_ERROR
         }
     })
 }
 
+/// Handles an event, the returned value is a status code and the EventResult is written to
+/// result.
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_post_event_with_result(
+    ptr: *mut DotLottiePlayer,
+    event: *const DotLottieEvent,
+    result: *mut DotLottieEventResult,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        if let Some(event) = event.as_ref() {
+            transfer_event_result(dotlottie_player.post_event(&event.to_event()), result)
+        } else {
+            DOTLOTTIE_INVALID_PARAMETER
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_queue_event(
+    ptr: *mut DotLottiePlayer,
+    event: *const DotLottieEvent,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        if let Some(event) = event.as_ref() {
+            dotlottie_player.queue_event(&event.to_event());
+            DOTLOTTIE_SUCCESS
+        } else {
+            DOTLOTTIE_INVALID_PARAMETER
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_process_events(
+    ptr: *mut DotLottiePlayer,
+    result: *mut DotLottieEventResult,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        transfer_event_result(dotlottie_player.process_events(), result)
+    })
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn dotlottie_buffer_ptr(
     ptr: *mut DotLottiePlayer,
@@ -382,17 +500,49 @@ pub unsafe extern "C" fn dotlottie_request_frame(
     })
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_request_frame_at(
+    ptr: *mut DotLottiePlayer,
+    time_ms: f64,
+    result: *mut f32,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        if !result.is_null() {
+            *result = dotlottie_player.request_frame_at(time_ms);
+            DOTLOTTIE_SUCCESS
+        } else {
+            DOTLOTTIE_INVALID_PARAMETER
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_tick(
+    ptr: *mut DotLottiePlayer,
+    delta_ms: f64,
+    result: *mut f32,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        if !result.is_null() {
+            *result = dotlottie_player.tick(delta_ms);
+            DOTLOTTIE_SUCCESS
+        } else {
+            DOTLOTTIE_INVALID_PARAMETER
+        }
+    })
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn dotlottie_set_frame(ptr: *mut DotLottiePlayer, no: f32) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
-        to_exit_status(dotlottie_player.set_frame(no))
+        to_result_status(dotlottie_player.try_set_frame(no))
     })
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn dotlottie_seek(ptr: *mut DotLottiePlayer, no: f32) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
-        to_exit_status(dotlottie_player.seek(no))
+        to_result_status(dotlottie_player.try_seek(no))
     })
 }
 
@@ -410,7 +560,7 @@ pub unsafe extern "C" fn dotlottie_resize(
     height: u32,
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
-        to_exit_status(dotlottie_player.resize(width, height))
+        to_result_status(dotlottie_player.try_resize(width, height))
     })
 }
 
@@ -444,7 +594,7 @@ pub unsafe extern "C" fn dotlottie_load_theme(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         if let Ok(theme_id) = DotLottieString::read(theme_id) {
-            to_exit_status(dotlottie_player.load_theme(&theme_id))
+            to_result_status(dotlottie_player.try_load_theme(&theme_id))
         } else {
             DOTLOTTIE_INVALID_PARAMETER
         }
@@ -458,7 +608,7 @@ pub unsafe extern "C" fn dotlottie_load_theme_data(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         if let Ok(theme_data) = DotLottieString::read(theme_data) {
-            to_exit_status(dotlottie_player.load_theme_data(&theme_data))
+            to_result_status(dotlottie_player.try_load_theme_data(&theme_data))
         } else {
             DOTLOTTIE_INVALID_PARAMETER
         }
@@ -502,6 +652,30 @@ pub unsafe extern "C" fn dotlottie_active_theme_id(
     })
 }
 
+// Copies the message of the last error, or an empty string if the last fallible call succeeded
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_last_error(
+    ptr: *mut DotLottiePlayer,
+    result: *mut c_char,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        let mut message = dotlottie_player.last_error_message();
+
+        // Leave room for the nul terminator, cutting on a character boundary
+        if message.len() >= DOTLOTTIE_MAX_STR_LENGTH {
+            let mut end = DOTLOTTIE_MAX_STR_LENGTH - 1;
+
+            while !message.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            message.truncate(end);
+        }
+
+        to_exit_status(DotLottieString::copy(&message, result, DOTLOTTIE_MAX_STR_LENGTH).is_ok())
+    })
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn dotlottie_set_viewport(
     ptr: *mut DotLottiePlayer,
@@ -588,7 +762,7 @@ pub unsafe extern "C" fn dotlottie_load_state_machine(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         if let Ok(state_machine_id) = DotLottieString::read(state_machine_id) {
-            to_exit_status(dotlottie_player.load_state_machine(&state_machine_id))
+            to_result_status(dotlottie_player.try_load_state_machine(&state_machine_id))
         } else {
             DOTLOTTIE_INVALID_PARAMETER
         }
@@ -681,13 +855,138 @@ pub unsafe extern "C" fn dotlottie_load_state_machine_data(
 ) -> i32 {
     exec_dotlottie_player_op(ptr, |dotlottie_player| {
         if let Ok(state_machine_definition) = DotLottieString::read(state_machine_definition) {
-            to_exit_status(dotlottie_player.load_state_machine_data(&state_machine_definition))
+            to_result_status(
+                dotlottie_player.try_load_state_machine_data(&state_machine_definition),
+            )
         } else {
             DOTLOTTIE_ERROR
         }
     })
 }
 
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_validate_state_machine(
+    ptr: *mut DotLottiePlayer,
+    state_machine_definition: *const c_char,
+    result: *mut DotLottieDiagnostic,
+    size: *mut usize,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        if let Ok(state_machine_definition) = DotLottieString::read(state_machine_definition) {
+            DotLottieDiagnostic::transfer_all(
+                &dotlottie_player.validate_state_machine(&state_machine_definition),
+                result,
+                size,
+            )
+        } else {
+            DOTLOTTIE_INVALID_PARAMETER
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_state_machine_to_json(
+    ptr: *mut DotLottiePlayer,
+    result: *mut c_char,
+    size: *mut usize,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        transfer_string(&dotlottie_player.state_machine_to_json(), result, size)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_state_machine_snapshot(
+    ptr: *mut DotLottiePlayer,
+    result: *mut c_char,
+    size: *mut usize,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        transfer_string(&dotlottie_player.state_machine_snapshot(), result, size)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_restore_state_machine_snapshot(
+    ptr: *mut DotLottiePlayer,
+    snapshot: *const c_char,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        if let Ok(snapshot) = DotLottieString::read(snapshot) {
+            to_result_status(dotlottie_player.try_restore_state_machine_snapshot(&snapshot))
+        } else {
+            DOTLOTTIE_INVALID_PARAMETER
+        }
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_state_machine_states(
+    ptr: *mut DotLottiePlayer,
+    result: *mut DotLottieStateInfo,
+    size: *mut usize,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        DotLottieStateInfo::transfer_all(&dotlottie_player.state_machine_states(), result, size)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_state_machine_transitions(
+    ptr: *mut DotLottiePlayer,
+    result: *mut DotLottieTransitionInfo,
+    size: *mut usize,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        DotLottieTransitionInfo::transfer_all(
+            &dotlottie_player.state_machine_transitions(),
+            result,
+            size,
+        )
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_state_machine_listeners(
+    ptr: *mut DotLottiePlayer,
+    result: *mut DotLottieListenerInfo,
+    size: *mut usize,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        DotLottieListenerInfo::transfer_all(
+            &dotlottie_player.state_machine_listeners(),
+            result,
+            size,
+        )
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_state_machine_context(
+    ptr: *mut DotLottiePlayer,
+    result: *mut DotLottieContextInfo,
+    size: *mut usize,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        DotLottieContextInfo::transfer_all(&dotlottie_player.state_machine_context(), result, size)
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn dotlottie_state_machine_available_events(
+    ptr: *mut DotLottiePlayer,
+    result: *mut DotLottieEventInfo,
+    size: *mut usize,
+) -> i32 {
+    exec_dotlottie_player_op(ptr, |dotlottie_player| {
+        DotLottieEventInfo::transfer_all(
+            &dotlottie_player.state_machine_available_events(),
+            result,
+            size,
+        )
+    })
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn dotlottie_subscribe(
     ptr: *mut DotLottiePlayer,
@@ -6,7 +6,9 @@ use std::io;
 use std::sync::Arc;
 
 use dotlottie_rs::{
-    Config, Event, Fit, Layout, Manifest, ManifestAnimation, ManifestTheme, Marker, Mode,
+    Config, ContextInfo, Diagnostic, DiagnosticSeverity, Event, EventInfo, EventResult, Fit,
+    Layout, ListenerInfo, Manifest, ManifestAnimation, ManifestTheme, Marker, Mode, StateInfo,
+    SwipeDirection, TransitionInfo,
 };
 
 // Function return codes
@@ -15,6 +17,17 @@ pub const DOTLOTTIE_ERROR: i32 = 1;
 pub const DOTLOTTIE_INVALID_PARAMETER: i32 = 2;
 pub const DOTLOTTIE_MANIFEST_NOT_AVAILABLE: i32 = 3;
 pub const DOTLOTTIE_MANIFEST_THEMES_NOT_AVAILABLE: i32 = 4;
+/// The failures of loading animations, themes and state machines, restoring snapshots, setting
+/// frames, seeking and resizing are reported with the codes from 5 on instead of
+/// DOTLOTTIE_ERROR. Any code other than DOTLOTTIE_SUCCESS is a failure, dotlottie_last_error
+/// describes it.
+pub const DOTLOTTIE_RENDERER_ERROR: i32 = 5;
+pub const DOTLOTTIE_INVALID_FILE: i32 = 6;
+pub const DOTLOTTIE_FILE_READ_ERROR: i32 = 7;
+pub const DOTLOTTIE_ANIMATION_NOT_FOUND: i32 = 8;
+pub const DOTLOTTIE_THEME_NOT_FOUND: i32 = 9;
+pub const DOTLOTTIE_STATE_MACHINE_ERROR: i32 = 10;
+pub const DOTLOTTIE_FRAME_OUT_OF_RANGE: i32 = 11;
 
 // Other constant(s)
 pub const DOTLOTTIE_MAX_STR_LENGTH: usize = 512;
@@ -25,6 +38,14 @@ pub const LISTENER_TYPE_POINTER_DOWN: u16 = 1 << 1;
 pub const LISTENER_TYPE_POINTER_ENTER: u16 = 1 << 2;
 pub const LISTENER_TYPE_POINTER_EXIT: u16 = 1 << 3;
 pub const LISTENER_TYPE_POINTER_MOVE: u16 = 1 << 4;
+pub const LISTENER_TYPE_KEY_DOWN: u16 = 1 << 5;
+pub const LISTENER_TYPE_KEY_UP: u16 = 1 << 6;
+pub const LISTENER_TYPE_FOCUS: u16 = 1 << 7;
+pub const LISTENER_TYPE_BLUR: u16 = 1 << 8;
+pub const LISTENER_TYPE_SCROLL: u16 = 1 << 9;
+pub const LISTENER_TYPE_LONG_PRESS: u16 = 1 << 10;
+pub const LISTENER_TYPE_DOUBLE_CLICK: u16 = 1 << 11;
+pub const LISTENER_TYPE_SWIPE: u16 = 1 << 12;
 
 // This type allows us to work with Listener Types as bit flags and easily communicate this
 // information to the C side
@@ -39,6 +60,14 @@ bitflags! {
         const POINTER_ENTER = LISTENER_TYPE_POINTER_ENTER;
         const POINTER_EXIT  = LISTENER_TYPE_POINTER_EXIT;
         const POINTER_MOVE  = LISTENER_TYPE_POINTER_MOVE;
+        const KEY_DOWN      = LISTENER_TYPE_KEY_DOWN;
+        const KEY_UP        = LISTENER_TYPE_KEY_UP;
+        const FOCUS         = LISTENER_TYPE_FOCUS;
+        const BLUR          = LISTENER_TYPE_BLUR;
+        const SCROLL        = LISTENER_TYPE_SCROLL;
+        const LONG_PRESS    = LISTENER_TYPE_LONG_PRESS;
+        const DOUBLE_CLICK  = LISTENER_TYPE_DOUBLE_CLICK;
+        const SWIPE         = LISTENER_TYPE_SWIPE;
     }
 }
 
@@ -65,6 +94,14 @@ impl FromStr for ListenerType {
             "PointerEnter" => Ok(ListenerType::POINTER_ENTER),
             "PointerExit" => Ok(ListenerType::POINTER_EXIT),
             "PointerMove" => Ok(ListenerType::POINTER_MOVE),
+            "KeyDown" => Ok(ListenerType::KEY_DOWN),
+            "KeyUp" => Ok(ListenerType::KEY_UP),
+            "Focus" => Ok(ListenerType::FOCUS),
+            "Blur" => Ok(ListenerType::BLUR),
+            "Scroll" => Ok(ListenerType::SCROLL),
+            "LongPress" => Ok(ListenerType::LONG_PRESS),
+            "DoubleClick" => Ok(ListenerType::DOUBLE_CLICK),
+            "Swipe" => Ok(ListenerType::SWIPE),
             _ => Err(ListenerTypeParseError),
         }
     }
@@ -316,6 +353,138 @@ impl Transferable<Marker> for DotLottieMarker {
     }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum DotLottieDiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+impl From<DiagnosticSeverity> for DotLottieDiagnosticSeverity {
+    fn from(severity: DiagnosticSeverity) -> Self {
+        match severity {
+            DiagnosticSeverity::Error => DotLottieDiagnosticSeverity::Error,
+            DiagnosticSeverity::Warning => DotLottieDiagnosticSeverity::Warning,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+#[repr(C)]
+pub struct DotLottieDiagnostic {
+    pub severity: DotLottieDiagnosticSeverity,
+    pub path: DotLottieString,
+    pub message: DotLottieString,
+}
+
+impl Transferable<Diagnostic> for DotLottieDiagnostic {
+    unsafe fn new(diagnostic: &Diagnostic) -> Result<DotLottieDiagnostic, io::Error> {
+        Ok(DotLottieDiagnostic {
+            severity: diagnostic.severity.into(),
+            path: DotLottieString::new(&diagnostic.path)?,
+            message: DotLottieString::new(&diagnostic.message)?,
+        })
+    }
+}
+
+// The JSON of configs, guards and actions has to fit in a DotLottieString, the whole
+// definition is available from dotlottie_state_machine_to_json
+#[derive(Clone, PartialEq)]
+#[repr(C)]
+pub struct DotLottieStateInfo {
+    pub name: DotLottieString,
+    pub path: DotLottieString,
+    pub state_type: DotLottieString,
+    pub active: bool,
+    pub config: DotLottieString,
+}
+
+impl Transferable<StateInfo> for DotLottieStateInfo {
+    unsafe fn new(state: &StateInfo) -> Result<DotLottieStateInfo, io::Error> {
+        Ok(DotLottieStateInfo {
+            name: DotLottieString::new(&state.name)?,
+            path: DotLottieString::new(&state.path)?,
+            state_type: DotLottieString::new(&state.state_type)?,
+            active: state.active,
+            config: DotLottieString::new(&state.config)?,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+#[repr(C)]
+pub struct DotLottieEventInfo {
+    pub event_type: DotLottieString,
+    pub value: DotLottieString,
+}
+
+impl Transferable<EventInfo> for DotLottieEventInfo {
+    unsafe fn new(event: &EventInfo) -> Result<DotLottieEventInfo, io::Error> {
+        Ok(DotLottieEventInfo {
+            event_type: DotLottieString::new(&event.event_type)?,
+            value: DotLottieString::new(&event.value)?,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+#[repr(C)]
+pub struct DotLottieTransitionInfo {
+    pub from_state: DotLottieString,
+    pub to_state: DotLottieString,
+    pub event: DotLottieEventInfo,
+    pub guards: DotLottieString,
+    pub actions: DotLottieString,
+}
+
+impl Transferable<TransitionInfo> for DotLottieTransitionInfo {
+    unsafe fn new(transition: &TransitionInfo) -> Result<DotLottieTransitionInfo, io::Error> {
+        Ok(DotLottieTransitionInfo {
+            from_state: DotLottieString::new(&transition.from_state)?,
+            to_state: DotLottieString::new(&transition.to_state)?,
+            event: DotLottieEventInfo::new(&transition.event)?,
+            guards: DotLottieString::new(&transition.guards)?,
+            actions: DotLottieString::new(&transition.actions)?,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+#[repr(C)]
+pub struct DotLottieListenerInfo {
+    pub listener_type: DotLottieString,
+    pub target: DotLottieString,
+    pub actions: DotLottieString,
+}
+
+impl Transferable<ListenerInfo> for DotLottieListenerInfo {
+    unsafe fn new(listener: &ListenerInfo) -> Result<DotLottieListenerInfo, io::Error> {
+        Ok(DotLottieListenerInfo {
+            listener_type: DotLottieString::new(&listener.listener_type)?,
+            target: DotLottieString::new(&listener.target)?,
+            actions: DotLottieString::new(&listener.actions)?,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+#[repr(C)]
+pub struct DotLottieContextInfo {
+    pub key: DotLottieString,
+    pub context_type: DotLottieString,
+    pub value: DotLottieString,
+}
+
+impl Transferable<ContextInfo> for DotLottieContextInfo {
+    unsafe fn new(context: &ContextInfo) -> Result<DotLottieContextInfo, io::Error> {
+        Ok(DotLottieContextInfo {
+            key: DotLottieString::new(&context.key)?,
+            context_type: DotLottieString::new(&context.context_type)?,
+            value: DotLottieString::new(&context.value)?,
+        })
+    }
+}
+
 #[derive(Clone, PartialEq)]
 #[repr(C)]
 pub struct DotLottieManifestAnimation {
@@ -486,6 +655,50 @@ impl DotLottieLayout {
     }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+#[repr(C)]
+pub enum DotLottieEventResult {
+    Success,
+    Failure,
+    Play,
+    Pause,
+    DrawFrame,
+    Queued,
+}
+
+impl From<EventResult> for DotLottieEventResult {
+    fn from(result: EventResult) -> Self {
+        match result {
+            EventResult::Success => DotLottieEventResult::Success,
+            EventResult::Failure => DotLottieEventResult::Failure,
+            EventResult::Play => DotLottieEventResult::Play,
+            EventResult::Pause => DotLottieEventResult::Pause,
+            EventResult::DrawFrame => DotLottieEventResult::DrawFrame,
+            EventResult::Queued => DotLottieEventResult::Queued,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub enum DotLottieSwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl From<DotLottieSwipeDirection> for SwipeDirection {
+    fn from(direction: DotLottieSwipeDirection) -> Self {
+        match direction {
+            DotLottieSwipeDirection::Left => SwipeDirection::Left,
+            DotLottieSwipeDirection::Right => SwipeDirection::Right,
+            DotLottieSwipeDirection::Up => SwipeDirection::Up,
+            DotLottieSwipeDirection::Down => SwipeDirection::Down,
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[repr(C)]
 pub enum DotLottieEvent {
@@ -499,6 +712,17 @@ pub enum DotLottieEvent {
     OnPointerExit { x: f32, y: f32 },
     OnComplete,
     SetNumericContext { key: DotLottieString, value: f32 },
+    OnMarkerReached { name: DotLottieString },
+    OnFrameReached { frame: f32 },
+    OnLoopComplete,
+    OnKeyDown { key: DotLottieString },
+    OnKeyUp { key: DotLottieString },
+    OnFocus,
+    OnBlur,
+    OnScroll { delta_x: f32, delta_y: f32 },
+    OnLongPress { x: f32, y: f32 },
+    OnDoubleClick { x: f32, y: f32 },
+    OnSwipe { direction: DotLottieSwipeDirection },
 }
 
 impl DotLottieEvent {
@@ -519,6 +743,28 @@ impl DotLottieEvent {
                 key: key.to_string(),
                 value: *value,
             },
+            DotLottieEvent::OnMarkerReached { name } => Event::OnMarkerReached {
+                name: name.to_string(),
+            },
+            DotLottieEvent::OnFrameReached { frame } => Event::OnFrameReached { frame: *frame },
+            DotLottieEvent::OnLoopComplete => Event::OnLoopComplete,
+            DotLottieEvent::OnKeyDown { key } => Event::OnKeyDown {
+                key: key.to_string(),
+            },
+            DotLottieEvent::OnKeyUp { key } => Event::OnKeyUp {
+                key: key.to_string(),
+            },
+            DotLottieEvent::OnFocus => Event::OnFocus,
+            DotLottieEvent::OnBlur => Event::OnBlur,
+            DotLottieEvent::OnScroll { delta_x, delta_y } => Event::OnScroll {
+                delta_x: *delta_x,
+                delta_y: *delta_y,
+            },
+            DotLottieEvent::OnLongPress { x, y } => Event::OnLongPress { x: *x, y: *y },
+            DotLottieEvent::OnDoubleClick { x, y } => Event::OnDoubleClick { x: *x, y: *y },
+            DotLottieEvent::OnSwipe { direction } => Event::OnSwipe {
+                direction: (*direction).into(),
+            },
         }
     }
 }
@@ -583,12 +829,36 @@ impl Observer {
 pub type OnTransitionOp = unsafe extern "C" fn(*const c_char, *const c_char);
 pub type OnStateEnteredOp = unsafe extern "C" fn(*const c_char);
 pub type OnStateExitOp = unsafe extern "C" fn(*const c_char);
-
+// action type ("URLAction", "SoundAction" or "LogAction"), url, sound id or message, target
+pub type OnActionOp = Option<unsafe extern "C" fn(*const c_char, *const c_char, *const c_char)>;
+pub type OnContextResetOp = Option<unsafe extern "C" fn(*const c_char)>;
+// key, old value (null for a new variable), new value
+pub type OnContextChangedOp =
+    Option<unsafe extern "C" fn(*const c_char, *const c_char, *const c_char)>;
+// event type, value
+pub type OnEventRejectedOp = Option<unsafe extern "C" fn(*const c_char, *const c_char)>;
+// listener type, target
+pub type OnListenerTriggeredOp = Option<unsafe extern "C" fn(*const c_char, *const c_char)>;
+pub type OnCustomEventOp = Option<unsafe extern "C" fn(*const c_char)>;
+pub type OnErrorOp = Option<unsafe extern "C" fn(*const c_char)>;
+
+/// The callbacks of a state machine observer. The ones after on_state_exit_op are optional and
+/// skipped when null, zero the struct before setting the callbacks in use.
+///
+/// on_context_changed_op to on_error_op were appended to the struct, changing its size. Hosts
+/// built against a header without them have to be rebuilt.
 #[repr(C)]
 pub struct StateMachineObserver {
     pub on_transition_op: OnTransitionOp,
     pub on_state_entered_op: OnStateEnteredOp,
     pub on_state_exit_op: OnStateExitOp,
+    pub on_action_op: OnActionOp,
+    pub on_context_reset_op: OnContextResetOp,
+    pub on_context_changed_op: OnContextChangedOp,
+    pub on_event_rejected_op: OnEventRejectedOp,
+    pub on_listener_triggered_op: OnListenerTriggeredOp,
+    pub on_custom_event_op: OnCustomEventOp,
+    pub on_error_op: OnErrorOp,
 }
 
 impl dotlottie_rs::StateMachineObserver for StateMachineObserver {
@@ -622,6 +892,115 @@ impl dotlottie_rs::StateMachineObserver for StateMachineObserver {
             }
         }
     }
+
+    fn on_action(&self, action: dotlottie_rs::StateAction) {
+        use dotlottie_rs::StateAction;
+
+        let Some(on_action_op) = self.on_action_op else {
+            return;
+        };
+
+        let (action_type, value, target) = match action {
+            StateAction::Url { url, target } => ("URLAction", url, target),
+            StateAction::Theme { theme_id, target } => ("ThemeAction", theme_id, target),
+            StateAction::Sound { sound_id, target } => ("SoundAction", sound_id, target),
+            StateAction::Log { message } => ("LogAction", message, None),
+            StateAction::FireCustomEvent { event } => ("FireCustomEvent", event, None),
+        };
+
+        if let (Ok(action_type), Ok(value), Ok(target)) = (
+            CString::new(action_type),
+            CString::new(value),
+            CString::new(target.unwrap_or_default()),
+        ) {
+            unsafe {
+                on_action_op(
+                    action_type.as_bytes_with_nul().as_ptr() as *const c_char,
+                    value.as_bytes_with_nul().as_ptr() as *const c_char,
+                    target.as_bytes_with_nul().as_ptr() as *const c_char,
+                )
+            }
+        }
+    }
+
+    fn on_context_reset(&self, key: String) {
+        let Some(on_context_reset_op) = self.on_context_reset_op else {
+            return;
+        };
+
+        if let Ok(key) = CString::new(key) {
+            unsafe { on_context_reset_op(key.as_bytes_with_nul().as_ptr() as *const c_char) }
+        }
+    }
+
+    fn on_context_changed(&self, key: String, old_value: Option<String>, new_value: String) {
+        let Some(on_context_changed_op) = self.on_context_changed_op else {
+            return;
+        };
+
+        if let (Ok(key), Ok(old_value), Ok(new_value)) = (
+            CString::new(key),
+            old_value.map(CString::new).transpose(),
+            CString::new(new_value),
+        ) {
+            unsafe {
+                on_context_changed_op(
+                    key.as_bytes_with_nul().as_ptr() as *const c_char,
+                    old_value
+                        .as_ref()
+                        .map_or(std::ptr::null(), |old_value| old_value.as_ptr()),
+                    new_value.as_bytes_with_nul().as_ptr() as *const c_char,
+                )
+            }
+        }
+    }
+
+    fn on_event_rejected(&self, event: EventInfo) {
+        let Some(on_event_rejected_op) = self.on_event_rejected_op else {
+            return;
+        };
+
+        if let (Ok(event_type), Ok(value)) =
+            (CString::new(event.event_type), CString::new(event.value))
+        {
+            unsafe {
+                on_event_rejected_op(
+                    event_type.as_bytes_with_nul().as_ptr() as *const c_char,
+                    value.as_bytes_with_nul().as_ptr() as *const c_char,
+                )
+            }
+        }
+    }
+
+    fn on_listener_triggered(&self, listener_type: String, target: String) {
+        let Some(on_listener_triggered_op) = self.on_listener_triggered_op else {
+            return;
+        };
+
+        if let (Ok(listener_type), Ok(target)) = (CString::new(listener_type), CString::new(target))
+        {
+            unsafe {
+                on_listener_triggered_op(
+                    listener_type.as_bytes_with_nul().as_ptr() as *const c_char,
+                    target.as_bytes_with_nul().as_ptr() as *const c_char,
+                )
+            }
+        }
+    }
+
+    fn on_custom_event(&self, event: String) {
+        if let (Some(on_custom_event_op), Ok(event)) =
+            (self.on_custom_event_op, CString::new(event))
+        {
+            unsafe { on_custom_event_op(event.as_bytes_with_nul().as_ptr() as *const c_char) }
+        }
+    }
+
+    fn on_error(&self, message: String) {
+        if let (Some(on_error_op), Ok(message)) = (self.on_error_op, CString::new(message)) {
+            unsafe { on_error_op(message.as_bytes_with_nul().as_ptr() as *const c_char) }
+        }
+    }
 }
 
 impl StateMachineObserver {
@@ -0,0 +1,37 @@
+#![no_main]
+
+// Feeds arbitrary archives to the dotLottie loading paths, none of them should panic.
+//
+// Runs offline, seeded with the archives of the repository:
+// cargo +nightly fuzz run dotlottie_manager corpus/dotlottie_manager ../src/fms/tests/resources ../tests/fixtures
+
+use dotlottie_rs::{get_animation, get_width_height, DotLottieManager};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let bytes = data.to_vec();
+
+    let Ok(mut manager) = DotLottieManager::new(Some(bytes.clone())) else {
+        return;
+    };
+
+    let Some(manifest) = manager.manifest() else {
+        return;
+    };
+
+    for animation in manifest.animations {
+        if let Ok(animation_data) = manager.get_animation(&animation.id) {
+            let _ = get_width_height(&animation_data);
+        }
+
+        let _ = get_animation(&bytes, &animation.id);
+    }
+
+    if let Some(themes) = manifest.themes {
+        for theme in themes {
+            let _ = manager.get_theme(&theme.id);
+        }
+    }
+
+    let _ = manager.get_animations();
+});
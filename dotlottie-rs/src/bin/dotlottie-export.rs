@@ -0,0 +1,268 @@
+//! Renders frames of a Lottie or dotLottie animation to PNG or raw RGBA files,
+//! or one playback of it to an animated GIF, APNG or WebP.
+//!
+//! cargo run --features export --bin dotlottie-export -- animation.lottie -o frames --fps 10
+//! cargo run --features export --bin dotlottie-export -- animation.lottie --format gif --loop
+
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use dotlottie_rs::{
+    AnimatedFormat, AnimationSource, Config, ExportConfig, Fit, FrameExporter, FrameSelection,
+    Layout, Mode,
+};
+
+const USAGE: &str = "Usage: dotlottie-export <input.json|input.lottie> [options]
+
+Options:
+  -o, --output <path>         Output directory, or file for animated formats
+                              (default: frames, or animation.<gif|png|webp>)
+  --animation-id <id>         Animation to export from a dotLottie file
+  --theme <id>                Theme to apply from a dotLottie file
+  --theme-data <file>         Theme file to apply
+  --width <px>                Output width (default: animation width)
+  --height <px>               Output height (default: animation height)
+  --fit <fit>                 contain, fill, cover, fit-width, fit-height or none
+  --align <x,y>               Alignment between 0 and 1 (default: 0.5,0.5)
+  --background <RRGGBBAA>     Background color (default: transparent)
+  --frame <n>                 Export a single frame (default: 0)
+  --range <start:end>         Export every frame from start to end
+  --fps <n>                   Export the whole animation at a fixed frame rate
+  --format <format>           png, rgba, gif, apng or webp (default: png)
+
+Animated formats play the animation once, following the options below:
+  --fps <n>                   Output frame rate (default: animation frame rate)
+  --mode <mode>               forward, reverse, bounce or reverse-bounce
+  --speed <n>                 Playback speed (default: 1)
+  --segment <start:end>       Only play the frames from start to end
+  --marker <name>             Only play the frames of a marker
+  --loop                      Repeat the animation forever
+  --no-interpolation          Only show whole frames
+  -h, --help                  Print this help";
+
+enum Format {
+    Png,
+    Rgba,
+    Animated(AnimatedFormat),
+}
+
+struct Args {
+    input: PathBuf,
+    output: Option<PathBuf>,
+    format: Format,
+    fps: Option<f32>,
+    config: ExportConfig,
+    playback: Config,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut input = None;
+    let mut output = None;
+    let mut format = Format::Png;
+    let mut fps = None;
+    let mut config = ExportConfig::default();
+    let mut playback = Config::default();
+    let mut fit = config.layout.fit;
+    let mut align = config.layout.align.clone();
+
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" {
+            return Ok(None);
+        }
+
+        match arg.as_str() {
+            "--loop" => {
+                playback.loop_animation = true;
+                continue;
+            }
+            "--no-interpolation" => {
+                playback.use_frame_interpolation = false;
+                continue;
+            }
+            _ => {}
+        }
+
+        if !arg.starts_with('-') {
+            if input.replace(PathBuf::from(&arg)).is_some() {
+                return Err(format!("Unexpected argument: {}", arg));
+            }
+            continue;
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", arg))?;
+
+        match arg.as_str() {
+            "-o" | "--output" => output = Some(PathBuf::from(value)),
+            "--animation-id" => config.animation_id = value,
+            "--theme" => config.theme_id = value,
+            "--theme-data" => {
+                config.theme_data = std::fs::read_to_string(&value)
+                    .map_err(|error| format!("Failed to read {}: {}", value, error))?
+            }
+            "--width" => config.width = Some(parse_number(&arg, &value)?),
+            "--height" => config.height = Some(parse_number(&arg, &value)?),
+            "--fit" => {
+                fit = match value.as_str() {
+                    "contain" => Fit::Contain,
+                    "fill" => Fit::Fill,
+                    "cover" => Fit::Cover,
+                    "fit-width" => Fit::FitWidth,
+                    "fit-height" => Fit::FitHeight,
+                    "none" => Fit::None,
+                    _ => return Err(format!("Invalid fit: {}", value)),
+                }
+            }
+            "--align" => {
+                align = value
+                    .split(',')
+                    .map(|part| parse_number(&arg, part))
+                    .collect::<Result<_, _>>()?
+            }
+            "--background" => {
+                config.background_color = u32::from_str_radix(value.trim_start_matches('#'), 16)
+                    .map_err(|_| format!("Invalid background color: {}", value))?
+            }
+            "--frame" => config.frames = FrameSelection::Frame(parse_number(&arg, &value)?),
+            "--range" => {
+                let (start, end) = value
+                    .split_once(':')
+                    .ok_or_else(|| format!("Invalid range: {}", value))?;
+
+                config.frames = FrameSelection::Range {
+                    start: parse_number(&arg, start)?,
+                    end: parse_number(&arg, end)?,
+                };
+            }
+            "--fps" => fps = Some(parse_number(&arg, &value)?),
+            "--format" => {
+                format = match value.as_str() {
+                    "png" => Format::Png,
+                    "rgba" => Format::Rgba,
+                    "gif" => Format::Animated(AnimatedFormat::Gif),
+                    "apng" => Format::Animated(AnimatedFormat::Apng),
+                    "webp" => Format::Animated(AnimatedFormat::WebP),
+                    _ => return Err(format!("Invalid format: {}", value)),
+                }
+            }
+            "--mode" => {
+                playback.mode = match value.as_str() {
+                    "forward" => Mode::Forward,
+                    "reverse" => Mode::Reverse,
+                    "bounce" => Mode::Bounce,
+                    "reverse-bounce" => Mode::ReverseBounce,
+                    _ => return Err(format!("Invalid mode: {}", value)),
+                }
+            }
+            "--speed" => playback.speed = parse_number(&arg, &value)?,
+            "--segment" => {
+                let (start, end) = value
+                    .split_once(':')
+                    .ok_or_else(|| format!("Invalid segment: {}", value))?;
+
+                playback.segment = vec![parse_number(&arg, start)?, parse_number(&arg, end)?];
+            }
+            "--marker" => playback.marker = value,
+            _ => return Err(format!("Unknown option: {}", arg)),
+        }
+    }
+
+    config.layout = Layout::new(fit, align);
+
+    // --fps selects the exported frames of image sequences, and the frame rate of animations
+    if let (Format::Png | Format::Rgba, Some(fps)) = (&format, fps) {
+        config.frames = FrameSelection::Fps(fps);
+    }
+
+    let input = input.ok_or_else(|| "Missing input file".to_string())?;
+
+    Ok(Some(Args {
+        input,
+        output,
+        format,
+        fps,
+        config,
+        playback,
+    }))
+}
+
+fn parse_number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", option, value))
+}
+
+fn run(args: Args) -> Result<Vec<PathBuf>, String> {
+    let data = std::fs::read(&args.input)
+        .map_err(|error| format!("Failed to read {}: {}", args.input.display(), error))?;
+
+    let is_dotlottie = args
+        .input
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("lottie"));
+
+    let animation_data;
+    let source = if is_dotlottie {
+        AnimationSource::DotLottie(&data)
+    } else {
+        animation_data = String::from_utf8(data)
+            .map_err(|_| format!("{} is not valid UTF-8", args.input.display()))?;
+        AnimationSource::Json(&animation_data)
+    };
+
+    let mut exporter =
+        FrameExporter::new(source, args.config).map_err(|error| error.to_string())?;
+
+    let frames_dir = || PathBuf::from("frames");
+
+    let result = match args.format {
+        Format::Png => exporter.export_png_sequence(&args.output.unwrap_or_else(frames_dir)),
+        Format::Rgba => exporter.export_rgba_sequence(&args.output.unwrap_or_else(frames_dir)),
+        Format::Animated(format) => {
+            let path = args
+                .output
+                .unwrap_or_else(|| PathBuf::from(format!("animation.{}", format.extension())));
+
+            exporter
+                .write_animation(&path, format, &args.playback, args.fps)
+                .map(|_| vec![path])
+        }
+    };
+
+    result.map_err(|error| error.to_string())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            eprintln!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("{}\n\n{}", error, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(args) {
+        Ok(paths) => {
+            let mut stdout = io::stdout().lock();
+
+            for path in paths {
+                let _ = writeln!(stdout, "{}", path.display());
+            }
+
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use instant::Instant;
+
+/// The time source driving playback.
+///
+/// Only the differences between two readings matter, the origin of the time is arbitrary.
+pub trait Clock: Send + Sync {
+    /// The time elapsed since the origin of the clock.
+    fn now(&self) -> Duration;
+}
+
+/// Reads the time from the system monotonic clock, the default time source of the player.
+pub struct SystemClock {
+    origin: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// A clock that only moves when it is told to, for hosts driving playback from their own
+/// vsync or display link, deterministic tests and offline rendering.
+///
+/// Clones share the same time, keep one to advance the clock given to the player.
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    time: Arc<RwLock<Duration>>,
+}
+
+impl ManualClock {
+    pub fn new(time: Duration) -> Self {
+        ManualClock {
+            time: Arc::new(RwLock::new(time)),
+        }
+    }
+
+    pub fn set_time(&self, time: Duration) {
+        *self.time.write().unwrap() = time;
+    }
+
+    pub fn advance(&self, delta: Duration) {
+        *self.time.write().unwrap() += delta;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        *self.time.read().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manual_clock() {
+        let clock = ManualClock::new(Duration::from_millis(100));
+        let shared_clock: Arc<dyn Clock> = Arc::new(clock.clone());
+
+        assert_eq!(shared_clock.now(), Duration::from_millis(100));
+
+        clock.advance(Duration::from_millis(16));
+        assert_eq!(shared_clock.now(), Duration::from_millis(116));
+
+        clock.set_time(Duration::from_secs(2));
+        assert_eq!(shared_clock.now(), Duration::from_secs(2));
+    }
+}
@@ -1,8 +1,10 @@
-use instant::{Duration, Instant};
+use std::collections::VecDeque;
 use std::sync::RwLock;
+use std::time::Duration;
 use std::{fs, rc::Rc, sync::Arc};
+use thiserror::Error;
 
-use crate::errors::StateMachineError::ParsingError;
+use crate::errors::StateMachineError;
 use crate::listeners::ListenerTrait;
 use crate::state_machine::events::Event;
 use crate::{
@@ -11,8 +13,49 @@ use crate::{
     lottie_renderer::{LottieRenderer, LottieRendererError},
     Marker, MarkersMap, StateMachine,
 };
-use crate::{DotLottieError, DotLottieManager, Manifest, ManifestAnimation};
-use crate::{StateMachineObserver, StateMachineStatus};
+use crate::{
+    validate_state_machine, ContextInfo, Diagnostic, EventInfo, EventResult, ListenerInfo,
+    StateInfo, StateMachineBuilder, StateMachineObserver, StateMachineSnapshot, StateMachineStatus,
+    TransitionInfo, ValidationTarget,
+};
+use crate::{
+    AnimationTransition, Clock, DotLottieError, DotLottieManager, Manifest, ManifestAnimation,
+    ManualClock, SystemClock,
+};
+
+#[derive(Error, Debug, Clone)]
+pub enum DotLottiePlayerError {
+    #[error("Renderer error: {0}")]
+    RendererError(#[from] LottieRendererError),
+
+    #[error("dotLottie error: {0}")]
+    DotLottieError(#[from] DotLottieError),
+
+    #[error("State machine error: {0}")]
+    StateMachineError(#[from] StateMachineError),
+
+    #[error("Unable to read {path}: {reason}")]
+    FileReadError { path: String, reason: String },
+
+    #[error("Frame {frame} is outside of the playback range {start_frame} - {end_frame}")]
+    FrameOutOfRange {
+        frame: f32,
+        start_frame: f32,
+        end_frame: f32,
+    },
+
+    #[error("Theme not found: {theme_id}")]
+    ThemeNotFound { theme_id: String },
+
+    #[error("Theme {theme_id} is not available for the animation {animation_id}")]
+    ThemeNotAvailable {
+        theme_id: String,
+        animation_id: String,
+    },
+
+    #[error("Unable to lock the player")]
+    LockError,
+}
 
 pub trait Observer: Send + Sync {
     fn on_load(&self);
@@ -26,6 +69,7 @@ pub trait Observer: Send + Sync {
     fn on_complete(&self);
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PlaybackState {
     Playing,
     Paused,
@@ -131,7 +175,11 @@ struct DotLottieRuntime {
     renderer: LottieRenderer,
     playback_state: PlaybackState,
     is_loaded: bool,
-    start_time: Instant,
+    clock: Arc<dyn Clock>,
+    // installed by tick and request_frame_at, None when the clock is provided by the host
+    manual_clock: Option<ManualClock>,
+    // clock time in seconds at which the current playback cycle started
+    start_time: f64,
     loop_count: u32,
     config: Config,
     dotlottie_manager: DotLottieManager,
@@ -139,6 +187,12 @@ struct DotLottieRuntime {
     markers: MarkersMap,
     active_animation_id: String,
     active_theme_id: String,
+    // the running transition and the clock time in seconds at which it started
+    transition: Option<(AnimationTransition, f64)>,
+    // the frame, loop count and direction of the last render
+    last_render: Option<(f32, u32, Direction)>,
+    // the frames, markers and loops reached by the renders since they were last taken
+    playback_events: Vec<Event>,
 }
 
 impl DotLottieRuntime {
@@ -154,7 +208,9 @@ impl DotLottieRuntime {
             renderer: LottieRenderer::new(),
             playback_state: PlaybackState::Stopped,
             is_loaded: false,
-            start_time: Instant::now(),
+            clock: Arc::new(SystemClock::new()),
+            manual_clock: None,
+            start_time: 0.0,
             loop_count: 0,
             config,
             dotlottie_manager: DotLottieManager::new(None).unwrap(),
@@ -162,6 +218,9 @@ impl DotLottieRuntime {
             markers: MarkersMap::new(),
             active_animation_id: String::new(),
             active_theme_id: String::new(),
+            transition: None,
+            last_render: None,
+            playback_events: Vec::new(),
         }
     }
 
@@ -208,6 +267,10 @@ impl DotLottieRuntime {
         self.renderer.hit_check(layer_name, x, y).unwrap_or(false)
     }
 
+    pub fn has_layer(&self, layer_name: &str) -> bool {
+        self.renderer.get_layer_bounds(layer_name).is_ok()
+    }
+
     pub fn get_layer_bounds(&self, layer_name: &str) -> Vec<f32> {
         let bbox = self.renderer.get_layer_bounds(layer_name);
 
@@ -245,14 +308,14 @@ impl DotLottieRuntime {
         }
 
         if self.is_complete() && self.is_stopped() {
-            self.start_time = Instant::now();
+            self.start_time = self.now();
             match self.config.mode {
                 Mode::Forward | Mode::Bounce => {
-                    self.set_frame(self.start_frame());
+                    let _ = self.set_frame(self.start_frame());
                     self.direction = Direction::Forward;
                 }
                 Mode::Reverse | Mode::ReverseBounce => {
-                    self.set_frame(self.end_frame());
+                    let _ = self.set_frame(self.end_frame());
                     self.direction = Direction::Reverse;
                 }
             }
@@ -284,10 +347,10 @@ impl DotLottieRuntime {
 
             match self.config.mode {
                 Mode::Forward | Mode::Bounce => {
-                    self.set_frame(start_frame);
+                    let _ = self.set_frame(start_frame);
                 }
                 Mode::Reverse | Mode::ReverseBounce => {
-                    self.set_frame(end_frame);
+                    let _ = self.set_frame(end_frame);
                 }
             }
 
@@ -305,10 +368,8 @@ impl DotLottieRuntime {
         (self.renderer.width, self.renderer.height)
     }
 
-    pub fn get_state_machine(&self, state_machine_id: &str) -> Option<String> {
-        self.dotlottie_manager
-            .get_state_machine(state_machine_id)
-            .ok()
+    pub fn get_state_machine(&self, state_machine_id: &str) -> Result<String, DotLottieError> {
+        self.dotlottie_manager.get_state_machine(state_machine_id)
     }
 
     pub fn request_frame(&mut self) -> f32 {
@@ -316,7 +377,7 @@ impl DotLottieRuntime {
             return self.current_frame();
         }
 
-        let elapsed_time = self.start_time.elapsed().as_secs_f32();
+        let elapsed_time = (self.now() - self.start_time) as f32;
 
         // the animation total frames
         let total_frames = self.total_frames();
@@ -370,7 +431,7 @@ impl DotLottieRuntime {
         if next_frame >= end_frame {
             if self.config.loop_animation {
                 self.loop_count += 1;
-                self.start_time = Instant::now();
+                self.start_time = self.now();
             }
 
             end_frame
@@ -383,7 +444,7 @@ impl DotLottieRuntime {
         if next_frame <= start_frame {
             if self.config.loop_animation {
                 self.loop_count += 1;
-                self.start_time = Instant::now();
+                self.start_time = self.now();
             }
 
             start_frame
@@ -397,7 +458,7 @@ impl DotLottieRuntime {
             Direction::Forward => {
                 if next_frame >= end_frame {
                     self.direction = Direction::Reverse;
-                    self.start_time = Instant::now();
+                    self.start_time = self.now();
 
                     end_frame
                 } else {
@@ -409,7 +470,7 @@ impl DotLottieRuntime {
                     if self.config.loop_animation {
                         self.loop_count += 1;
                         self.direction = Direction::Forward;
-                        self.start_time = Instant::now();
+                        self.start_time = self.now();
                     }
 
                     start_frame
@@ -430,7 +491,7 @@ impl DotLottieRuntime {
             Direction::Reverse => {
                 if next_frame <= start_frame {
                     self.direction = Direction::Forward;
-                    self.start_time = Instant::now();
+                    self.start_time = self.now();
                     start_frame
                 } else {
                     next_frame
@@ -441,7 +502,7 @@ impl DotLottieRuntime {
                     if self.config.loop_animation {
                         self.loop_count += 1;
                         self.direction = Direction::Reverse;
-                        self.start_time = Instant::now();
+                        self.start_time = self.now();
                     }
 
                     end_frame
@@ -452,6 +513,55 @@ impl DotLottieRuntime {
         }
     }
 
+    fn now(&self) -> f64 {
+        self.clock.now().as_secs_f64()
+    }
+
+    /// Replace the time source of the playback, the current frame is kept.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        let transition_elapsed = self
+            .transition
+            .map(|(_, start_time)| self.now() - start_time);
+
+        self.clock = clock;
+        self.manual_clock = None;
+
+        self.update_start_time_for_frame(self.current_frame());
+
+        if let (Some((_, start_time)), Some(elapsed)) = (&mut self.transition, transition_elapsed) {
+            *start_time = self.clock.now().as_secs_f64() - elapsed;
+        }
+    }
+
+    // The clock driven by tick and request_frame_at, installed on first use
+    fn manual_clock(&mut self, initial_time: Duration) -> ManualClock {
+        match &self.manual_clock {
+            Some(clock) => clock.clone(),
+            None => {
+                let clock = ManualClock::new(initial_time);
+
+                self.set_clock(Arc::new(clock.clone()));
+                self.manual_clock = Some(clock.clone());
+
+                clock
+            }
+        }
+    }
+
+    /// Advance the playback time by delta and return the next frame to render.
+    pub fn tick(&mut self, delta: Duration) -> f32 {
+        self.manual_clock(Duration::ZERO).advance(delta);
+
+        self.request_frame()
+    }
+
+    /// Return the frame to render at the given time of the host clock.
+    pub fn request_frame_at(&mut self, time: Duration) -> f32 {
+        self.manual_clock(time).set_time(time);
+
+        self.request_frame()
+    }
+
     fn update_start_time_for_frame(&mut self, frame_no: f32) {
         let start_frame = self.start_frame();
         let end_frame = self.end_frame();
@@ -476,15 +586,9 @@ impl DotLottieRuntime {
                 elapsed_time_for_frame = 0.0;
             }
             // update start_time to account for the already elapsed time
-            if let Some(start_time) =
-                Instant::now().checked_sub(Duration::from_secs_f32(elapsed_time_for_frame))
-            {
-                self.start_time = start_time;
-            } else {
-                self.start_time = Instant::now();
-            }
+            self.start_time = self.now() - elapsed_time_for_frame as f64;
         } else {
-            self.start_time = Instant::now();
+            self.start_time = self.now();
         }
     }
 
@@ -496,19 +600,28 @@ impl DotLottieRuntime {
     ///
     /// # Returns
     ///
-    /// Returns `true` if the frame number is valid and updated and `false` otherwise.
+    /// Returns `Ok` if the frame number is valid and updated and the cause of the failure otherwise.
     ///
     /// The frame number is considered valid if it's within the range of the start and end frames.
     ///
     /// This function does not update the start time for the new frame assuming it's already managed by the `request_frame` method in the animation loop.
     /// It's the responsibility of the caller to update the start time if needed.
     ///
-    pub fn set_frame(&mut self, no: f32) -> bool {
-        if no < self.start_frame() || no > self.end_frame() {
-            return false;
+    pub fn set_frame(&mut self, no: f32) -> Result<(), DotLottiePlayerError> {
+        let start_frame = self.start_frame();
+        let end_frame = self.end_frame();
+
+        if no < start_frame || no > end_frame {
+            return Err(DotLottiePlayerError::FrameOutOfRange {
+                frame: no,
+                start_frame,
+                end_frame,
+            });
         }
 
-        self.renderer.set_frame(no).is_ok()
+        self.renderer.set_frame(no)?;
+
+        Ok(())
     }
 
     /// Seek to a specific frame number.
@@ -519,20 +632,18 @@ impl DotLottieRuntime {
     ///
     /// # Returns
     ///
-    /// Returns `true` if the frame number is valid and updated and `false` otherwise.
+    /// Returns `Ok` if the frame number is valid and updated and the cause of the failure otherwise.
     ///
     /// The frame number is considered valid if it's within the range of the start and end frames.
     ///
     /// The start time is updated based on the new frame number.
     ///
-    pub fn seek(&mut self, no: f32) -> bool {
-        let is_ok = self.set_frame(no);
+    pub fn seek(&mut self, no: f32) -> Result<(), DotLottiePlayerError> {
+        self.set_frame(no)?;
 
-        if is_ok {
-            self.update_start_time_for_frame(no);
-        }
+        self.update_start_time_for_frame(no);
 
-        is_ok
+        Ok(())
     }
 
     pub fn set_viewport(&mut self, x: i32, y: i32, w: i32, h: i32) -> bool {
@@ -540,8 +651,14 @@ impl DotLottieRuntime {
     }
 
     pub fn render(&mut self) -> bool {
+        self.update_transition();
+
         let is_ok = self.renderer.render().is_ok();
 
+        if is_ok {
+            self.record_playback_events();
+        }
+
         // rendered the last frame successfully
         if is_ok && self.is_complete() && !self.config.loop_animation {
             self.playback_state = PlaybackState::Stopped;
@@ -550,6 +667,108 @@ impl DotLottieRuntime {
         is_ok
     }
 
+    // Record the frames and markers crossed since the last render, and the completed loops
+    fn record_playback_events(&mut self) {
+        let frame = self.current_frame();
+        let loop_count = self.loop_count;
+        let direction = self.direction;
+        let last_render = self.last_render.replace((frame, loop_count, direction));
+
+        let start_frame = self.start_frame();
+        let end_frame = self.end_frame();
+
+        // The crossed frames in the order they were played, as spans from low to high with their
+        // inclusiveness and whether they were played ascending. Looping or turning around plays
+        // the end of the previous pass, then the start of the next one
+        let spans = match last_render {
+            None => vec![(frame, true, frame, true, true)],
+            Some((previous, _, previous_direction)) => match (previous_direction, direction) {
+                (Direction::Forward, Direction::Forward) if frame < previous => {
+                    if self.config.mode == Mode::Forward {
+                        vec![
+                            (previous, false, end_frame, true, true),
+                            (start_frame, true, frame, true, true),
+                        ]
+                    } else {
+                        vec![(frame, true, previous, false, false)]
+                    }
+                }
+                (Direction::Reverse, Direction::Reverse) if frame > previous => {
+                    if self.config.mode == Mode::Reverse {
+                        vec![
+                            (start_frame, true, previous, false, false),
+                            (frame, true, end_frame, true, false),
+                        ]
+                    } else {
+                        vec![(previous, false, frame, true, true)]
+                    }
+                }
+                (Direction::Forward, Direction::Reverse) => vec![
+                    (previous, false, end_frame, true, true),
+                    (frame, true, end_frame, false, false),
+                ],
+                (Direction::Reverse, Direction::Forward) => vec![
+                    (start_frame, true, previous, false, false),
+                    (start_frame, false, frame, true, true),
+                ],
+                _ if frame > previous => vec![(previous, false, frame, true, true)],
+                _ if frame < previous => vec![(frame, true, previous, false, false)],
+                _ => Vec::new(),
+            },
+        };
+
+        let completed_loop = matches!(
+            last_render,
+            Some((_, previous_loop_count, _)) if loop_count > previous_loop_count
+        );
+
+        for (index, span) in spans.into_iter().enumerate() {
+            self.record_crossed_frames(span);
+
+            // Between the passes of a loop
+            if index == 0 && completed_loop {
+                self.playback_events.push(Event::OnLoopComplete);
+            }
+        }
+    }
+
+    fn record_crossed_frames(&mut self, span: (f32, bool, f32, bool, bool)) {
+        let (low, low_inclusive, high, high_inclusive, ascending) = span;
+
+        let is_crossed = |frame: f32| {
+            (frame > low || (low_inclusive && frame == low))
+                && (frame < high || (high_inclusive && frame == high))
+        };
+
+        let mut reached: Vec<(f32, Event)> = (low.ceil() as i64..=high.floor() as i64)
+            .map(|frame| frame as f32)
+            .filter(|frame| is_crossed(*frame))
+            .map(|frame| (frame, Event::OnFrameReached { frame }))
+            .collect();
+
+        reached.extend(
+            self.markers
+                .iter()
+                .filter(|(_, (time, _))| is_crossed(*time))
+                .map(|(name, (time, _))| (*time, Event::OnMarkerReached { name: name.clone() })),
+        );
+
+        reached.sort_by(|(a, _), (b, _)| {
+            if ascending {
+                a.total_cmp(b)
+            } else {
+                b.total_cmp(a)
+            }
+        });
+
+        self.playback_events
+            .extend(reached.into_iter().map(|(_, event)| event));
+    }
+
+    fn take_playback_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.playback_events)
+    }
+
     pub fn total_frames(&self) -> f32 {
         match self.renderer.total_frames() {
             Ok(total_frames) => total_frames - 1.0,
@@ -631,6 +850,9 @@ impl DotLottieRuntime {
         if should_flip {
             self.direction = self.direction.flip();
             self.update_start_time_for_frame(self.current_frame());
+
+            // Changing the mode isn't a turnaround of the playback
+            self.last_render = None;
         }
     }
 
@@ -660,43 +882,60 @@ impl DotLottieRuntime {
         }
     }
 
-    fn load_animation_common<F>(&mut self, loader: F, width: u32, height: u32) -> bool
+    fn load_animation_common<F>(
+        &mut self,
+        loader: F,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError>
     where
-        F: FnOnce(&mut LottieRenderer, u32, u32) -> Result<(), LottieRendererError>,
+        F: FnOnce(
+            &mut LottieRenderer,
+            &DotLottieManager,
+            u32,
+            u32,
+        ) -> Result<(), LottieRendererError>,
     {
         self.clear();
+        self.transition = None;
+        self.last_render = None;
+        self.playback_events.clear();
         self.playback_state = PlaybackState::Stopped;
-        self.start_time = Instant::now();
+        self.start_time = self.now();
         self.loop_count = 0;
 
-        let loaded = loader(&mut self.renderer, width, height).is_ok()
-            && self
-                .renderer
-                .set_background_color(self.config.background_color)
-                .is_ok();
-
-        self.renderer.set_layout(&self.config.layout).unwrap();
+        let loaded = loader(&mut self.renderer, &self.dotlottie_manager, width, height)
+            .and_then(|_| {
+                self.renderer
+                    .set_background_color(self.config.background_color)
+            })
+            .and_then(|_| self.renderer.set_layout(&self.config.layout));
 
-        self.is_loaded = loaded;
+        self.is_loaded = loaded.is_ok();
 
         let start_frame = self.start_frame();
         let end_frame = self.end_frame();
 
         match self.config.mode {
             Mode::Forward | Mode::Bounce => {
-                self.set_frame(start_frame);
+                let _ = self.set_frame(start_frame);
                 self.direction = Direction::Forward;
             }
             Mode::Reverse | Mode::ReverseBounce => {
-                self.set_frame(end_frame);
+                let _ = self.set_frame(end_frame);
                 self.direction = Direction::Reverse;
             }
         }
 
-        loaded
+        loaded.map_err(DotLottiePlayerError::from)
     }
 
-    pub fn load_animation_data(&mut self, animation_data: &str, width: u32, height: u32) -> bool {
+    pub fn load_animation_data(
+        &mut self,
+        animation_data: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
         self.active_animation_id.clear();
         self.active_theme_id.clear();
 
@@ -705,75 +944,139 @@ impl DotLottieRuntime {
         self.markers = extract_markers(animation_data);
 
         self.load_animation_common(
-            |renderer, w, h| renderer.load_data(animation_data, w, h, false),
+            |renderer, _, w, h| renderer.load_data(animation_data, w, h, false),
             width,
             height,
         )
     }
 
-    pub fn load_animation_path(&mut self, file_path: &str, width: u32, height: u32) -> bool {
+    pub fn load_animation_path(
+        &mut self,
+        file_path: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
         self.active_animation_id.clear();
         self.active_theme_id.clear();
 
-        match fs::read_to_string(file_path) {
-            Ok(data) => self.load_animation_data(&data, width, height),
-            Err(_) => false,
-        }
+        let data =
+            fs::read_to_string(file_path).map_err(|error| DotLottiePlayerError::FileReadError {
+                path: file_path.to_string(),
+                reason: error.to_string(),
+            })?;
+
+        self.load_animation_data(&data, width, height)
     }
 
-    pub fn load_dotlottie_data(&mut self, file_data: &[u8], width: u32, height: u32) -> bool {
+    pub fn load_dotlottie_data(
+        &mut self,
+        file_data: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
         self.active_animation_id.clear();
         self.active_theme_id.clear();
 
-        if self.dotlottie_manager.init(file_data).is_err() {
-            return false;
-        }
+        self.dotlottie_manager.init(file_data)?;
 
-        let first_animation: Result<String, DotLottieError> =
-            self.dotlottie_manager.get_active_animation();
+        let active_animation_id = self.dotlottie_manager.active_animation_id();
+        let animation_data = self
+            .dotlottie_manager
+            .get_animation_data(&active_animation_id)?;
 
-        let ok = match first_animation {
-            Ok(animation_data) => {
-                self.markers = extract_markers(animation_data.as_str());
+        self.markers = extract_markers(animation_data.as_str());
 
-                // For the moment we're ignoring manifest values
+        // For the moment we're ignoring manifest values
 
-                // self.load_playback_settings();
-                self.load_animation_common(
-                    |renderer, w, h| renderer.load_data(&animation_data, w, h, false),
-                    width,
-                    height,
-                )
-            }
-            Err(_error) => false,
-        };
+        // self.load_playback_settings();
+        self.load_animation_common(
+            |renderer, images, w, h| {
+                renderer.load_data_with_images(&animation_data, images, w, h, false)
+            },
+            width,
+            height,
+        )?;
 
-        if ok {
-            self.active_animation_id = self.dotlottie_manager.active_animation_id();
-        }
+        self.active_animation_id = self.dotlottie_manager.active_animation_id();
 
-        ok
+        Ok(())
     }
 
-    pub fn load_animation(&mut self, animation_id: &str, width: u32, height: u32) -> bool {
+    pub fn load_animation(
+        &mut self,
+        animation_id: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
         self.active_animation_id.clear();
 
-        let animation_data = self.dotlottie_manager.get_animation(animation_id);
+        let animation_data = self.dotlottie_manager.get_animation_data(animation_id)?;
 
-        let ok = match animation_data {
-            Ok(animation_data) => self.load_animation_common(
-                |renderer, w, h| renderer.load_data(&animation_data, w, h, false),
-                width,
-                height,
-            ),
-            Err(_error) => false,
-        };
+        self.load_animation_common(
+            |renderer, images, w, h| {
+                renderer.load_data_with_images(&animation_data, images, w, h, false)
+            },
+            width,
+            height,
+        )?;
 
-        if ok {
-            self.active_animation_id = animation_id.to_string();
+        self.active_animation_id = animation_id.to_string();
+
+        Ok(())
+    }
+
+    /// Load an animation of the dotLottie file and blend it with the one on screen.
+    ///
+    /// The transition moves forward each time a frame is rendered, until `is_transitioning`
+    /// returns false. Without an animation on screen, the new one is shown right away.
+    pub fn load_animation_with_transition(
+        &mut self,
+        animation_id: &str,
+        width: u32,
+        height: u32,
+        transition: AnimationTransition,
+    ) -> Result<(), DotLottiePlayerError> {
+        self.end_transition();
+
+        if self.is_loaded {
+            self.renderer.begin_transition();
         }
 
-        ok
+        let loaded = self.load_animation(animation_id, width, height);
+
+        if loaded.is_ok() && self.renderer.is_transitioning() {
+            self.transition = Some((transition, self.now()));
+            self.update_transition();
+        } else {
+            self.end_transition();
+        }
+
+        loaded
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    fn update_transition(&mut self) {
+        if let Some((transition, start_time)) = self.transition {
+            let progress = transition.progress((self.now() - start_time) as f32);
+
+            if progress >= 1.0
+                || self
+                    .renderer
+                    .update_transition(&transition.effect, progress)
+                    .is_err()
+            {
+                self.end_transition();
+            }
+        }
+    }
+
+    fn end_transition(&mut self) {
+        self.transition = None;
+
+        let _ = self.renderer.end_transition();
     }
 
     #[allow(dead_code)]
@@ -816,8 +1119,10 @@ impl DotLottieRuntime {
         true
     }
 
-    pub fn resize(&mut self, width: u32, height: u32) -> bool {
-        self.renderer.resize(width, height).is_ok()
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), DotLottiePlayerError> {
+        self.renderer.resize(width, height)?;
+
+        Ok(())
     }
 
     pub fn config(&self) -> Config {
@@ -840,49 +1145,50 @@ impl DotLottieRuntime {
         }
     }
 
-    pub fn load_theme(&mut self, theme_id: &str) -> bool {
+    pub fn load_theme(&mut self, theme_id: &str) -> Result<(), DotLottiePlayerError> {
         self.active_theme_id.clear();
 
         if theme_id.is_empty() {
-            return self.renderer.load_theme_data("").is_ok();
+            self.renderer.load_theme_data("")?;
+
+            return Ok(());
         }
 
-        let ok = self
+        let theme = self
             .manifest()
             .and_then(|manifest| manifest.themes)
-            .map_or(false, |themes| {
-                themes
-                    .iter()
-                    .find(|t| t.id == theme_id)
-                    .map_or(false, |theme| {
-                        // check if the theme is either global or scoped to the currently active animation
-                        let is_global_or_active_animation = theme.animations.is_empty()
-                            || theme
-                                .animations
-                                .iter()
-                                .any(|animation| animation == &self.active_animation_id);
-
-                        is_global_or_active_animation
-                            && self
-                                .dotlottie_manager
-                                .get_theme(theme_id)
-                                .ok()
-                                .and_then(|theme_data| {
-                                    self.renderer.load_theme_data(&theme_data).ok()
-                                })
-                                .is_some()
-                    })
+            .and_then(|themes| themes.into_iter().find(|theme| theme.id == theme_id))
+            .ok_or_else(|| DotLottiePlayerError::ThemeNotFound {
+                theme_id: theme_id.to_string(),
+            })?;
+
+        // check if the theme is either global or scoped to the currently active animation
+        let is_global_or_active_animation = theme.animations.is_empty()
+            || theme
+                .animations
+                .iter()
+                .any(|animation| animation == &self.active_animation_id);
+
+        if !is_global_or_active_animation {
+            return Err(DotLottiePlayerError::ThemeNotAvailable {
+                theme_id: theme_id.to_string(),
+                animation_id: self.active_animation_id.clone(),
             });
-
-        if ok {
-            self.active_theme_id = theme_id.to_string();
         }
 
-        ok
+        let theme_data = self.dotlottie_manager.get_theme(theme_id)?;
+
+        self.renderer.load_theme_data(&theme_data)?;
+
+        self.active_theme_id = theme_id.to_string();
+
+        Ok(())
     }
 
-    pub fn load_theme_data(&mut self, theme_data: &str) -> bool {
-        self.renderer.load_theme_data(theme_data).is_ok()
+    pub fn load_theme_data(&mut self, theme_data: &str) -> Result<(), DotLottiePlayerError> {
+        self.renderer.load_theme_data(theme_data)?;
+
+        Ok(())
     }
 
     pub fn active_animation_id(&self) -> &str {
@@ -898,6 +1204,9 @@ pub struct DotLottiePlayerContainer {
     runtime: RwLock<DotLottieRuntime>,
     observers: RwLock<Vec<Arc<dyn Observer>>>,
     state_machine: Rc<RwLock<Option<StateMachine>>>,
+    // the events posted and not yet handed to the state machine, which may be busy handling
+    // another event when they're posted by its observers
+    pending_events: RwLock<VecDeque<Event>>,
 }
 
 impl DotLottiePlayerContainer {
@@ -906,91 +1215,127 @@ impl DotLottiePlayerContainer {
             runtime: RwLock::new(DotLottieRuntime::new(config)),
             observers: RwLock::new(Vec::new()),
             state_machine: Rc::new(RwLock::new(None)),
+            pending_events: RwLock::new(VecDeque::new()),
         }
     }
 
     pub fn load_animation_data(&self, animation_data: &str, width: u32, height: u32) -> bool {
-        let is_ok = self
+        self.try_load_animation_data(animation_data, width, height)
+            .is_ok()
+    }
+
+    pub fn try_load_animation_data(
+        &self,
+        animation_data: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
+        let result = self
             .runtime
             .write()
-            .is_ok_and(|mut runtime| runtime.load_animation_data(animation_data, width, height));
-
-        if is_ok {
-            self.observers.read().unwrap().iter().for_each(|observer| {
-                observer.on_load();
-            });
-
-            if self.config().autoplay {
-                self.play();
-            }
-        } else {
-            self.observers.read().unwrap().iter().for_each(|observer| {
-                observer.on_load_error();
-            });
+            .map_err(|_| DotLottiePlayerError::LockError)
+            .and_then(|mut runtime| runtime.load_animation_data(animation_data, width, height));
 
-            return false;
-        }
-
-        is_ok
+        self.notify_load(result)
     }
 
     pub fn load_animation_path(&self, animation_path: &str, width: u32, height: u32) -> bool {
-        let is_ok = self
+        self.try_load_animation_path(animation_path, width, height)
+            .is_ok()
+    }
+
+    pub fn try_load_animation_path(
+        &self,
+        animation_path: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
+        let result = self
             .runtime
             .write()
-            .is_ok_and(|mut runtime| runtime.load_animation_path(animation_path, width, height));
-
-        if is_ok {
-            self.observers.read().unwrap().iter().for_each(|observer| {
-                observer.on_load();
-            });
+            .map_err(|_| DotLottiePlayerError::LockError)
+            .and_then(|mut runtime| runtime.load_animation_path(animation_path, width, height));
 
-            if self.config().autoplay {
-                self.play();
-            }
-        } else {
-            self.observers.read().unwrap().iter().for_each(|observer| {
-                observer.on_load_error();
-            });
-
-            return false;
-        }
-
-        is_ok
+        self.notify_load(result)
     }
 
     pub fn load_dotlottie_data(&self, file_data: &[u8], width: u32, height: u32) -> bool {
-        let is_ok = self
+        self.try_load_dotlottie_data(file_data, width, height)
+            .is_ok()
+    }
+
+    pub fn try_load_dotlottie_data(
+        &self,
+        file_data: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
+        let result = self
             .runtime
             .write()
-            .is_ok_and(|mut runtime| runtime.load_dotlottie_data(file_data, width, height));
-
-        if is_ok {
-            self.observers.read().unwrap().iter().for_each(|observer| {
-                observer.on_load();
-            });
-
-            if self.config().autoplay {
-                self.play();
-            }
-        } else {
-            self.observers.read().unwrap().iter().for_each(|observer| {
-                observer.on_load_error();
-            });
-
-            return false;
-        }
+            .map_err(|_| DotLottiePlayerError::LockError)
+            .and_then(|mut runtime| runtime.load_dotlottie_data(file_data, width, height));
 
-        is_ok
+        self.notify_load(result)
     }
 
     pub fn load_animation(&self, animation_id: &str, width: u32, height: u32) -> bool {
-        let is_ok = self
+        self.try_load_animation(animation_id, width, height).is_ok()
+    }
+
+    pub fn try_load_animation(
+        &self,
+        animation_id: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
+        let result = self
             .runtime
             .write()
-            .is_ok_and(|mut runtime| runtime.load_animation(animation_id, width, height));
+            .map_err(|_| DotLottiePlayerError::LockError)
+            .and_then(|mut runtime| runtime.load_animation(animation_id, width, height));
+
+        self.notify_load(result)
+    }
+
+    pub fn load_animation_with_transition(
+        &self,
+        animation_id: &str,
+        width: u32,
+        height: u32,
+        transition: AnimationTransition,
+    ) -> bool {
+        self.try_load_animation_with_transition(animation_id, width, height, transition)
+            .is_ok()
+    }
+
+    pub fn try_load_animation_with_transition(
+        &self,
+        animation_id: &str,
+        width: u32,
+        height: u32,
+        transition: AnimationTransition,
+    ) -> Result<(), DotLottiePlayerError> {
+        let result = self
+            .runtime
+            .write()
+            .map_err(|_| DotLottiePlayerError::LockError)
+            .and_then(|mut runtime| {
+                runtime.load_animation_with_transition(animation_id, width, height, transition)
+            });
 
-        if is_ok {
+        self.notify_load(result)
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.runtime.read().unwrap().is_transitioning()
+    }
+
+    fn notify_load(
+        &self,
+        result: Result<(), DotLottiePlayerError>,
+    ) -> Result<(), DotLottiePlayerError> {
+        if result.is_ok() {
             self.observers.read().unwrap().iter().for_each(|observer| {
                 observer.on_load();
             });
@@ -1002,11 +1347,9 @@ impl DotLottiePlayerContainer {
             self.observers.read().unwrap().iter().for_each(|observer| {
                 observer.on_load_error();
             });
-
-            return false;
         }
 
-        is_ok
+        result
     }
 
     pub fn manifest(&self) -> Option<Manifest> {
@@ -1117,32 +1460,118 @@ impl DotLottiePlayerContainer {
         self.runtime.write().unwrap().request_frame()
     }
 
+    pub fn tick(&self, delta: Duration) -> f32 {
+        self.runtime.write().unwrap().tick(delta)
+    }
+
+    pub fn request_frame_at(&self, time: Duration) -> f32 {
+        self.runtime.write().unwrap().request_frame_at(time)
+    }
+
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.runtime.write().unwrap().set_clock(clock);
+    }
+
+    // The time of the clock driving the playback, None while the runtime is locked
+    pub(crate) fn clock_time(&self) -> Option<Duration> {
+        self.runtime
+            .try_read()
+            .ok()
+            .map(|runtime| runtime.clock.now())
+    }
+
     pub fn set_frame(&self, no: f32) -> bool {
-        let ok = self.runtime.write().unwrap().set_frame(no);
+        self.try_set_frame(no).is_ok()
+    }
 
-        if ok {
-            self.observers.read().unwrap().iter().for_each(|observer| {
-                observer.on_frame(no);
-            });
-        }
+    pub fn try_set_frame(&self, no: f32) -> Result<(), DotLottiePlayerError> {
+        self.runtime.write().unwrap().set_frame(no)?;
 
-        ok
+        self.observers.read().unwrap().iter().for_each(|observer| {
+            observer.on_frame(no);
+        });
+
+        Ok(())
     }
 
     pub fn seek(&self, no: f32) -> bool {
-        let ok = self.runtime.write().unwrap().seek(no);
+        self.try_seek(no).is_ok()
+    }
 
-        if ok {
-            self.observers.read().unwrap().iter().for_each(|observer| {
-                observer.on_frame(no);
-            });
+    pub fn try_seek(&self, no: f32) -> Result<(), DotLottiePlayerError> {
+        self.runtime.write().unwrap().seek(no)?;
+
+        self.observers.read().unwrap().iter().for_each(|observer| {
+            observer.on_frame(no);
+        });
+
+        Ok(())
+    }
+
+    pub fn post_event(&self, event: &Event) -> EventResult {
+        self.queue_event(event);
+        self.process_events()
+    }
+
+    pub fn queue_event(&self, event: &Event) {
+        if let Ok(mut pending_events) = self.pending_events.write() {
+            pending_events.push_back(event.clone());
         }
+    }
 
-        ok
+    // Events posted by the observers while handling are handed over until none is left
+    pub fn process_events(&self) -> EventResult {
+        let mut result = EventResult::Failure;
+
+        loop {
+            let Ok(mut state_machine) = self.state_machine.try_write() else {
+                return EventResult::Queued;
+            };
+
+            let events: Vec<Event> = match self.pending_events.write() {
+                Ok(mut pending_events) => pending_events.drain(..).collect(),
+                Err(_) => return result,
+            };
+
+            // Without a state machine the events are dropped
+            let Some(sm) = state_machine.as_mut() else {
+                return result;
+            };
+
+            if events.is_empty() {
+                return result;
+            }
+
+            events.into_iter().for_each(|event| sm.queue_event(event));
+            result = result.then(sm.process_events());
+        }
     }
 
     pub fn render(&self) -> bool {
+        // The events queued since the last frame are handled before drawing it
+        self.process_events();
+
         let ok = self.runtime.write().unwrap().render();
+        let playback_events = self.runtime.write().unwrap().take_playback_events();
+
+        if let Ok(mut state_machine) = self.state_machine.try_write() {
+            if let Some(sm) = state_machine.as_mut() {
+                for event in playback_events {
+                    let state = sm.get_current_state();
+
+                    sm.post_event(&event);
+
+                    // The remaining events were reached by the animation of the state that was left
+                    let entered_state = sm.get_current_state();
+                    if !matches!((&state, &entered_state), (Some(a), Some(b)) if Arc::ptr_eq(a, b))
+                    {
+                        break;
+                    }
+                }
+
+                sm.update();
+            }
+        }
 
         if ok {
             let frame_no = self.current_frame();
@@ -1161,15 +1590,14 @@ impl DotLottiePlayerContainer {
                         observer.on_complete();
                     });
 
-                    if let Ok(mut state_machine) = self.state_machine.try_write() {
-                        if let Some(sm) = state_machine.as_mut() {
-                            sm.post_event(&Event::OnComplete);
-                        }
-                    }
+                    self.queue_event(&Event::OnComplete);
                 }
             }
         }
 
+        // OnComplete, and the events posted by the state machine observers while rendering
+        self.process_events();
+
         ok
     }
 
@@ -1181,6 +1609,10 @@ impl DotLottiePlayerContainer {
     }
 
     pub fn resize(&self, width: u32, height: u32) -> bool {
+        self.try_resize(width, height).is_ok()
+    }
+
+    pub fn try_resize(&self, width: u32, height: u32) -> Result<(), DotLottiePlayerError> {
         self.runtime.write().unwrap().resize(width, height)
     }
 
@@ -1212,10 +1644,18 @@ impl DotLottiePlayerContainer {
     }
 
     pub fn load_theme(&self, theme_id: &str) -> bool {
+        self.try_load_theme(theme_id).is_ok()
+    }
+
+    pub fn try_load_theme(&self, theme_id: &str) -> Result<(), DotLottiePlayerError> {
         self.runtime.write().unwrap().load_theme(theme_id)
     }
 
     pub fn load_theme_data(&self, theme_data: &str) -> bool {
+        self.try_load_theme_data(theme_data).is_ok()
+    }
+
+    pub fn try_load_theme_data(&self, theme_data: &str) -> Result<(), DotLottiePlayerError> {
         self.runtime.write().unwrap().load_theme_data(theme_data)
     }
 
@@ -1237,6 +1677,27 @@ impl DotLottiePlayerContainer {
         self.runtime.read().unwrap().get_layer_bounds(layer_name)
     }
 
+    // Validates against the manifest and the layers of the loaded animation, when there are any
+    pub fn validate_state_machine(&self, state_machine: &str) -> Vec<Diagnostic> {
+        let runtime = self.runtime.read().unwrap();
+        let has_layer = |layer_name: &str| runtime.has_layer(layer_name);
+
+        let target = ValidationTarget {
+            animation_ids: runtime.manifest().map(|manifest| {
+                manifest
+                    .animations
+                    .into_iter()
+                    .map(|animation| animation.id)
+                    .collect()
+            }),
+            has_layer: runtime
+                .is_loaded()
+                .then_some(&has_layer as &dyn Fn(&str) -> bool),
+        };
+
+        validate_state_machine(state_machine, &target)
+    }
+
     pub fn markers(&self) -> Vec<Marker> {
         self.runtime.read().unwrap().markers()
     }
@@ -1254,9 +1715,16 @@ impl DotLottiePlayerContainer {
     }
 
     pub fn get_state_machine(&self, state_machine_id: &str) -> Option<String> {
+        self.try_get_state_machine(state_machine_id).ok()
+    }
+
+    pub fn try_get_state_machine(
+        &self,
+        state_machine_id: &str,
+    ) -> Result<String, DotLottiePlayerError> {
         match self.runtime.try_read() {
-            Ok(runtime) => runtime.get_state_machine(state_machine_id),
-            Err(_) => None,
+            Ok(runtime) => Ok(runtime.get_state_machine(state_machine_id)?),
+            Err(_) => Err(DotLottiePlayerError::LockError),
         }
     }
 }
@@ -1264,6 +1732,7 @@ impl DotLottiePlayerContainer {
 pub struct DotLottiePlayer {
     player: Rc<RwLock<DotLottiePlayerContainer>>,
     state_machine: Rc<RwLock<Option<StateMachine>>>,
+    last_error: RwLock<Option<DotLottiePlayerError>>,
 }
 
 impl DotLottiePlayer {
@@ -1271,13 +1740,54 @@ impl DotLottiePlayer {
         DotLottiePlayer {
             player: Rc::new(RwLock::new(DotLottiePlayerContainer::new(config))),
             state_machine: Rc::new(RwLock::new(None)),
+            last_error: RwLock::new(None),
         }
     }
 
+    /// Returns the error of the last fallible call (loading animations, themes and state machines,
+    /// set_frame, seek, resize), or None if it succeeded.
+    /// Meant for bindings that only receive the boolean results.
+    pub fn last_error(&self) -> Option<DotLottiePlayerError> {
+        self.last_error
+            .read()
+            .ok()
+            .and_then(|last_error| last_error.clone())
+    }
+
+    /// The message of the last error, empty if the last fallible call succeeded.
+    pub fn last_error_message(&self) -> String {
+        self.last_error()
+            .map_or_else(String::new, |error| error.to_string())
+    }
+
+    fn record_result<T>(
+        &self,
+        result: Result<T, DotLottiePlayerError>,
+    ) -> Result<T, DotLottiePlayerError> {
+        if let Ok(mut last_error) = self.last_error.write() {
+            *last_error = result.as_ref().err().cloned();
+        }
+
+        result
+    }
+
     pub fn load_animation_data(&self, animation_data: &str, width: u32, height: u32) -> bool {
-        self.player
-            .write()
-            .is_ok_and(|runtime| runtime.load_animation_data(animation_data, width, height))
+        self.try_load_animation_data(animation_data, width, height)
+            .is_ok()
+    }
+
+    pub fn try_load_animation_data(
+        &self,
+        animation_data: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
+        self.record_result(
+            self.player
+                .write()
+                .map_err(|_| DotLottiePlayerError::LockError)
+                .and_then(|runtime| runtime.try_load_animation_data(animation_data, width, height)),
+        )
     }
 
     pub fn get_state_machine(&self) -> Rc<RwLock<Option<StateMachine>>> {
@@ -1317,6 +1827,9 @@ impl DotLottiePlayer {
             }
         }
 
+        // The events posted by the observers while entering the initial state
+        self.process_events();
+
         true
     }
 
@@ -1400,6 +1913,8 @@ impl DotLottiePlayer {
             Err(_) => return false,
         }
 
+        self.process_events();
+
         true
     }
 
@@ -1424,6 +1939,8 @@ impl DotLottiePlayer {
             Err(_) => return false,
         }
 
+        self.process_events();
+
         true
     }
 
@@ -1448,80 +1965,104 @@ impl DotLottiePlayer {
             Err(_) => return false,
         }
 
+        self.process_events();
+
         true
     }
 
-    // Return codes
-    // 0: Success
-    // 1: Failure
-    // 2: Play animation
-    // 3: Pause animation
-    // 4: Request and draw a new single frame of the animation (needed for sync state)
-    pub fn post_event(&self, event: &Event) -> i32 {
-        match self.state_machine.try_read() {
-            Ok(state_machine) => {
-                if state_machine.is_none() {
-                    return 1;
-                }
-            }
-            Err(_) => return 1,
-        }
+    /// Post an event to the state machine and handle it after the events queued before it.
+    ///
+    /// Returns Queued when the state machine is busy, e.g. when called by one of its observers,
+    /// the event is then handled as soon as the current one is done.
+    pub fn post_event(&self, event: &Event) -> EventResult {
+        self.player.read().unwrap().post_event(event)
+    }
 
-        match self.state_machine.try_write() {
-            Ok(mut state_machine) => {
-                if let Some(sm) = state_machine.as_mut() {
-                    return sm.post_event(event);
-                }
-            }
-            Err(_) => return 1,
-        }
+    /// Queue an event without handling it, e.g. to handle the pointer input of a frame at once.
+    /// The queued events are handled by process_events, post_event and render.
+    pub fn queue_event(&self, event: &Event) {
+        self.player.read().unwrap().queue_event(event)
+    }
 
-        1
+    /// Handle the queued events in the order they were posted, each running to completion before
+    /// the next one. The events posted by the observers meanwhile are handled after them.
+    ///
+    /// Returns the result of the last event that did something, Failure if none did, or Queued
+    /// when the state machine is busy.
+    pub fn process_events(&self) -> EventResult {
+        self.player.read().unwrap().process_events()
     }
 
-    pub fn post_bool_event(&self, value: bool) -> i32 {
+    pub fn post_bool_event(&self, value: bool) -> EventResult {
         let event = Event::Bool { value };
         self.post_event(&event)
     }
 
-    pub fn post_string_event(&self, value: &str) -> i32 {
+    pub fn post_string_event(&self, value: &str) -> EventResult {
         let event = Event::String {
             value: value.to_string(),
         };
         self.post_event(&event)
     }
 
-    pub fn post_numeric_event(&self, value: f32) -> i32 {
+    pub fn post_numeric_event(&self, value: f32) -> EventResult {
         let event = Event::Numeric { value };
         self.post_event(&event)
     }
 
-    pub fn post_pointer_down_event(&self, x: f32, y: f32) -> i32 {
+    pub fn post_pointer_down_event(&self, x: f32, y: f32) -> EventResult {
         let event = Event::OnPointerDown { x, y };
         self.post_event(&event)
     }
 
-    pub fn post_pointer_up_event(&self, x: f32, y: f32) -> i32 {
+    pub fn post_pointer_up_event(&self, x: f32, y: f32) -> EventResult {
         let event = Event::OnPointerUp { x, y };
         self.post_event(&event)
     }
 
-    pub fn post_pointer_move_event(&self, x: f32, y: f32) -> i32 {
+    pub fn post_pointer_move_event(&self, x: f32, y: f32) -> EventResult {
         let event = Event::OnPointerMove { x, y };
         self.post_event(&event)
     }
 
-    pub fn post_pointer_enter_event(&self, x: f32, y: f32) -> i32 {
+    pub fn post_pointer_enter_event(&self, x: f32, y: f32) -> EventResult {
         let event = Event::OnPointerEnter { x, y };
         self.post_event(&event)
     }
 
-    pub fn post_pointer_exit_event(&self, x: f32, y: f32) -> i32 {
+    pub fn post_pointer_exit_event(&self, x: f32, y: f32) -> EventResult {
         let event = Event::OnPointerExit { x, y };
         self.post_event(&event)
     }
 
-    pub fn post_set_numeric_context(&self, key: &str, value: f32) -> i32 {
+    pub fn post_key_down_event(&self, key: &str) -> EventResult {
+        let event = Event::OnKeyDown {
+            key: key.to_string(),
+        };
+        self.post_event(&event)
+    }
+
+    pub fn post_key_up_event(&self, key: &str) -> EventResult {
+        let event = Event::OnKeyUp {
+            key: key.to_string(),
+        };
+        self.post_event(&event)
+    }
+
+    pub fn post_focus_event(&self) -> EventResult {
+        self.post_event(&Event::OnFocus)
+    }
+
+    pub fn post_blur_event(&self) -> EventResult {
+        self.post_event(&Event::OnBlur)
+    }
+
+    pub fn post_scroll_event(&self, delta_x: f32, delta_y: f32) -> EventResult {
+        let event = Event::OnScroll { delta_x, delta_y };
+        self.post_event(&event)
+    }
+
+    pub fn post_set_numeric_context(&self, key: &str, value: f32) -> EventResult {
         let event = Event::SetNumericContext {
             key: key.to_string(),
             value,
@@ -1531,21 +2072,98 @@ impl DotLottiePlayer {
     }
 
     pub fn load_animation_path(&self, animation_path: &str, width: u32, height: u32) -> bool {
-        self.player
-            .write()
-            .is_ok_and(|runtime| runtime.load_animation_path(animation_path, width, height))
+        self.try_load_animation_path(animation_path, width, height)
+            .is_ok()
+    }
+
+    pub fn try_load_animation_path(
+        &self,
+        animation_path: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
+        self.record_result(
+            self.player
+                .write()
+                .map_err(|_| DotLottiePlayerError::LockError)
+                .and_then(|runtime| runtime.try_load_animation_path(animation_path, width, height)),
+        )
     }
 
     pub fn load_dotlottie_data(&self, file_data: &[u8], width: u32, height: u32) -> bool {
-        self.player
-            .write()
-            .is_ok_and(|runtime| runtime.load_dotlottie_data(file_data, width, height))
+        self.try_load_dotlottie_data(file_data, width, height)
+            .is_ok()
+    }
+
+    pub fn try_load_dotlottie_data(
+        &self,
+        file_data: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
+        self.record_result(
+            self.player
+                .write()
+                .map_err(|_| DotLottiePlayerError::LockError)
+                .and_then(|runtime| runtime.try_load_dotlottie_data(file_data, width, height)),
+        )
     }
 
     pub fn load_animation(&self, animation_id: &str, width: u32, height: u32) -> bool {
-        self.player
-            .write()
-            .is_ok_and(|runtime| runtime.load_animation(animation_id, width, height))
+        self.try_load_animation(animation_id, width, height).is_ok()
+    }
+
+    pub fn try_load_animation(
+        &self,
+        animation_id: &str,
+        width: u32,
+        height: u32,
+    ) -> Result<(), DotLottiePlayerError> {
+        self.record_result(
+            self.player
+                .write()
+                .map_err(|_| DotLottiePlayerError::LockError)
+                .and_then(|runtime| runtime.try_load_animation(animation_id, width, height)),
+        )
+    }
+
+    /// Load an animation of the dotLottie file with a crossfade, slide or wipe from the
+    /// animation on screen. Keep rendering frames while `is_transitioning` returns true.
+    pub fn load_animation_with_transition(
+        &self,
+        animation_id: &str,
+        width: u32,
+        height: u32,
+        transition: AnimationTransition,
+    ) -> bool {
+        self.try_load_animation_with_transition(animation_id, width, height, transition)
+            .is_ok()
+    }
+
+    pub fn try_load_animation_with_transition(
+        &self,
+        animation_id: &str,
+        width: u32,
+        height: u32,
+        transition: AnimationTransition,
+    ) -> Result<(), DotLottiePlayerError> {
+        self.record_result(
+            self.player
+                .write()
+                .map_err(|_| DotLottiePlayerError::LockError)
+                .and_then(|runtime| {
+                    runtime.try_load_animation_with_transition(
+                        animation_id,
+                        width,
+                        height,
+                        transition,
+                    )
+                }),
+        )
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.player.read().unwrap().is_transitioning()
     }
 
     pub fn manifest(&self) -> Option<Manifest> {
@@ -1632,12 +2250,42 @@ impl DotLottiePlayer {
         self.player.write().unwrap().request_frame()
     }
 
+    /// Advance the playback time by delta_ms milliseconds and return the next frame to render.
+    ///
+    /// Playback follows the time given to tick and request_frame_at from the first call on,
+    /// instead of the system clock, e.g. to drive it from a display link or step it in tests.
+    pub fn tick(&self, delta_ms: f64) -> f32 {
+        self.player.write().unwrap().tick(to_duration(delta_ms))
+    }
+
+    /// Return the frame to render at time_ms, a timestamp in milliseconds of the host clock
+    /// such as the one given by requestAnimationFrame.
+    pub fn request_frame_at(&self, time_ms: f64) -> f32 {
+        self.player
+            .write()
+            .unwrap()
+            .request_frame_at(to_duration(time_ms))
+    }
+
+    /// Replace the clock driving the playback, the system clock by default.
+    pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+        self.player.write().unwrap().set_clock(clock);
+    }
+
     pub fn set_frame(&self, no: f32) -> bool {
-        self.player.write().unwrap().set_frame(no)
+        self.try_set_frame(no).is_ok()
+    }
+
+    pub fn try_set_frame(&self, no: f32) -> Result<(), DotLottiePlayerError> {
+        self.record_result(self.player.write().unwrap().try_set_frame(no))
     }
 
     pub fn seek(&self, no: f32) -> bool {
-        self.player.write().unwrap().seek(no)
+        self.try_seek(no).is_ok()
+    }
+
+    pub fn try_seek(&self, no: f32) -> Result<(), DotLottiePlayerError> {
+        self.record_result(self.player.write().unwrap().try_seek(no))
     }
 
     pub fn render(&self) -> bool {
@@ -1645,7 +2293,11 @@ impl DotLottiePlayer {
     }
 
     pub fn resize(&self, width: u32, height: u32) -> bool {
-        self.player.write().unwrap().resize(width, height)
+        self.try_resize(width, height).is_ok()
+    }
+
+    pub fn try_resize(&self, width: u32, height: u32) -> Result<(), DotLottiePlayerError> {
+        self.record_result(self.player.write().unwrap().try_resize(width, height))
     }
 
     pub fn config(&self) -> Config {
@@ -1694,81 +2346,182 @@ impl DotLottiePlayer {
     }
 
     pub fn load_theme(&self, theme_id: &str) -> bool {
-        self.player.write().unwrap().load_theme(theme_id)
+        self.try_load_theme(theme_id).is_ok()
+    }
+
+    pub fn try_load_theme(&self, theme_id: &str) -> Result<(), DotLottiePlayerError> {
+        self.record_result(self.player.write().unwrap().try_load_theme(theme_id))
+    }
+
+    /// Lints a state machine definition against the loaded animation without loading it.
+    pub fn validate_state_machine(&self, state_machine: &str) -> Vec<Diagnostic> {
+        self.player
+            .read()
+            .unwrap()
+            .validate_state_machine(state_machine)
     }
 
     pub fn load_state_machine_data(&self, state_machine: &str) -> bool {
-        let state_machine = StateMachine::new(state_machine, self.player.clone());
+        self.try_load_state_machine_data(state_machine).is_ok()
+    }
 
-        if state_machine.is_ok() {
-            match self.state_machine.try_write() {
-                Ok(mut sm) => {
-                    sm.replace(state_machine.unwrap());
-                }
-                Err(_) => {
-                    return false;
-                }
-            }
+    pub fn try_load_state_machine_data(
+        &self,
+        state_machine: &str,
+    ) -> Result<(), DotLottiePlayerError> {
+        self.record_result(self.set_state_machine(state_machine))
+    }
 
-            let player = self.player.try_write();
+    pub fn load_state_machine_builder(&self, builder: StateMachineBuilder) -> bool {
+        self.try_load_state_machine_builder(builder).is_ok()
+    }
 
-            match player {
-                Ok(mut player) => {
-                    player.state_machine = self.state_machine.clone();
-                }
-                Err(_) => {
-                    return false;
-                }
-            }
-        }
+    pub fn try_load_state_machine_builder(
+        &self,
+        builder: StateMachineBuilder,
+    ) -> Result<(), DotLottiePlayerError> {
+        let result = builder
+            .build(self.player.clone())
+            .map_err(DotLottiePlayerError::from)
+            .and_then(|state_machine| self.install_state_machine(state_machine));
 
-        true
+        self.record_result(result)
     }
 
     pub fn load_state_machine(&self, state_machine_id: &str) -> bool {
-        let state_machine_string = self
+        self.try_load_state_machine(state_machine_id).is_ok()
+    }
+
+    pub fn try_load_state_machine(
+        &self,
+        state_machine_id: &str,
+    ) -> Result<(), DotLottiePlayerError> {
+        let result = self
             .player
             .read()
-            .unwrap()
-            .get_state_machine(state_machine_id);
+            .map_err(|_| DotLottiePlayerError::LockError)
+            .and_then(|player| player.try_get_state_machine(state_machine_id))
+            .and_then(|state_machine| self.set_state_machine(&state_machine));
 
-        match state_machine_string {
-            Some(machine) => {
-                let state_machine = StateMachine::new(&machine, self.player.clone());
+        self.record_result(result)
+    }
 
-                if state_machine.is_ok() {
-                    match self.state_machine.try_write() {
-                        Ok(mut sm) => {
-                            sm.replace(state_machine.unwrap());
-                        }
-                        Err(_) => {
-                            return false;
-                        }
-                    }
+    /// The definition of the loaded state machine as JSON, empty when none is loaded.
+    pub fn state_machine_to_json(&self) -> String {
+        match self.state_machine.try_read().as_deref() {
+            Ok(Some(state_machine)) => state_machine.to_json().unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
 
-                    let player = self.player.try_write();
+    /// The current state and context of the loaded state machine as JSON, to be restored with
+    /// `restore_state_machine_snapshot` after loading the same state machine again. Empty when
+    /// no state machine is loaded.
+    pub fn state_machine_snapshot(&self) -> String {
+        match self.state_machine.try_read().as_deref() {
+            Ok(Some(state_machine)) => {
+                serde_json::to_string(&state_machine.snapshot()).unwrap_or_default()
+            }
+            _ => String::new(),
+        }
+    }
 
-                    match player {
-                        Ok(mut player) => {
-                            player.state_machine = self.state_machine.clone();
-                        }
-                        Err(_) => {
-                            return false;
-                        }
+    pub fn state_machine_states(&self) -> Vec<StateInfo> {
+        self.describe_state_machine(StateMachine::describe_states)
+    }
+
+    pub fn state_machine_transitions(&self) -> Vec<TransitionInfo> {
+        self.describe_state_machine(StateMachine::describe_transitions)
+    }
+
+    pub fn state_machine_listeners(&self) -> Vec<ListenerInfo> {
+        self.describe_state_machine(StateMachine::describe_listeners)
+    }
+
+    pub fn state_machine_context(&self) -> Vec<ContextInfo> {
+        self.describe_state_machine(StateMachine::describe_context)
+    }
+
+    /// The events that would currently take a transition, see `StateMachine::available_events`.
+    pub fn state_machine_available_events(&self) -> Vec<EventInfo> {
+        self.describe_state_machine(StateMachine::available_events)
+    }
+
+    // Empty when no state machine is loaded
+    fn describe_state_machine<T>(&self, describe: fn(&StateMachine) -> Vec<T>) -> Vec<T> {
+        match self.state_machine.try_read().as_deref() {
+            Ok(Some(state_machine)) => describe(state_machine),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn restore_state_machine_snapshot(&self, snapshot: &str) -> bool {
+        self.try_restore_state_machine_snapshot(snapshot).is_ok()
+    }
+
+    pub fn try_restore_state_machine_snapshot(
+        &self,
+        snapshot: &str,
+    ) -> Result<(), DotLottiePlayerError> {
+        let result = serde_json::from_str::<StateMachineSnapshot>(snapshot)
+            .map_err(|err| StateMachineError::SnapshotError {
+                reason: err.to_string(),
+            })
+            .map_err(DotLottiePlayerError::from)
+            .and_then(|snapshot| {
+                let mut state_machine = self
+                    .state_machine
+                    .try_write()
+                    .map_err(|_| DotLottiePlayerError::LockError)?;
+
+                match state_machine.as_mut() {
+                    Some(state_machine) => Ok(state_machine.restore(&snapshot)?),
+                    None => Err(StateMachineError::SnapshotError {
+                        reason: "No state machine is loaded!".to_string(),
                     }
-                } else if let Err(ParsingError { reason: _ }) = state_machine {
-                    return false;
+                    .into()),
                 }
-            }
-            None => {
-                return false;
-            }
+            });
+
+        self.record_result(result)
+    }
+
+    fn set_state_machine(&self, state_machine: &str) -> Result<(), DotLottiePlayerError> {
+        let state_machine = StateMachine::new(state_machine, self.player.clone())?;
+
+        self.install_state_machine(state_machine)
+    }
+
+    fn install_state_machine(
+        &self,
+        state_machine: StateMachine,
+    ) -> Result<(), DotLottiePlayerError> {
+        self.state_machine
+            .try_write()
+            .map_err(|_| DotLottiePlayerError::LockError)?
+            .replace(state_machine);
+
+        let mut player = self
+            .player
+            .try_write()
+            .map_err(|_| DotLottiePlayerError::LockError)?;
+
+        player.state_machine = self.state_machine.clone();
+
+        // The events posted to the previous state machine aren't handled by this one
+        if let Ok(pending_events) = player.pending_events.get_mut() {
+            pending_events.clear();
         }
-        true
+
+        Ok(())
     }
 
     pub fn load_theme_data(&self, theme_data: &str) -> bool {
-        self.player.write().unwrap().load_theme_data(theme_data)
+        self.try_load_theme_data(theme_data).is_ok()
+    }
+
+    pub fn try_load_theme_data(&self, theme_data: &str) -> Result<(), DotLottiePlayerError> {
+        self.record_result(self.player.write().unwrap().try_load_theme_data(theme_data))
     }
 
     pub fn markers(&self) -> Vec<Marker> {
@@ -1792,5 +2545,10 @@ impl DotLottiePlayer {
     }
 }
 
+// Negative and invalid times count as no time
+fn to_duration(time_ms: f64) -> Duration {
+    Duration::try_from_secs_f64(time_ms / 1000.0).unwrap_or_default()
+}
+
 unsafe impl Send for DotLottiePlayer {}
 unsafe impl Sync for DotLottiePlayer {}
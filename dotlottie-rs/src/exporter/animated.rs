@@ -0,0 +1,466 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use super::{write_error, ExportError, ExportedFrame, FrameExporter};
+use crate::{Config, Mode};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimatedFormat {
+    Gif,
+    Apng,
+    WebP,
+}
+
+impl AnimatedFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AnimatedFormat::Gif => "gif",
+            AnimatedFormat::Apng => "png",
+            AnimatedFormat::WebP => "webp",
+        }
+    }
+}
+
+/// The frames of one playback of the animation, as the player would show them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaybackTimeline {
+    /// Frame numbers in display order, each displayed for 1 / fps seconds.
+    pub frames: Vec<f32>,
+    pub fps: f32,
+    /// Whether the exported animation repeats forever or plays once.
+    pub loop_animation: bool,
+}
+
+impl PlaybackTimeline {
+    /// The display duration of every frame in units of 1 / units_per_second.
+    ///
+    /// Durations are rounded from the frame timestamps rather than one by one, so the
+    /// rounding errors don't add up over long animations.
+    fn delays(&self, units_per_second: f32) -> Vec<u32> {
+        let timestamp = |index: usize| (index as f32 * units_per_second / self.fps).round() as u32;
+
+        (0..self.frames.len())
+            .map(|index| timestamp(index + 1) - timestamp(index))
+            .collect()
+    }
+}
+
+impl FrameExporter {
+    /// Computes the frames shown by one playback following the segment, marker, mode,
+    /// speed, frame interpolation and loop settings of the player config.
+    ///
+    /// The layout and background color of the config are not used, they are set by the
+    /// ExportConfig the exporter was created with.
+    ///
+    /// fps: The frame rate of the output, the frame rate of the animation if None
+    pub fn playback_timeline(
+        &self,
+        config: &Config,
+        fps: Option<f32>,
+    ) -> Result<PlaybackTimeline, ExportError> {
+        let total_frames = self.renderer.total_frames()?;
+        let duration = self.renderer.duration()?;
+        let last_frame = total_frames - 1.0;
+
+        let fps = fps.unwrap_or(total_frames / duration);
+
+        if !fps.is_finite() || fps <= 0.0 {
+            return Err(ExportError::InvalidFrameSelection(format!(
+                "Frame rate must be greater than 0, got {}",
+                fps
+            )));
+        }
+
+        if !config.speed.is_finite() || config.speed <= 0.0 {
+            return Err(ExportError::InvalidFrameSelection(format!(
+                "Speed must be greater than 0, got {}",
+                config.speed
+            )));
+        }
+
+        // Same start & end frames as the player, the marker takes precedence over the segment
+        let marker = self.markers.get(&config.marker);
+
+        let (start_frame, end_frame) = match (marker, config.segment.as_slice()) {
+            (Some((time, duration)), _) => (time.max(0.0), (time + duration).min(last_frame)),
+            (None, [start, end]) => (start.max(0.0), end.min(last_frame)),
+            _ => (0.0, last_frame),
+        };
+
+        if start_frame > end_frame {
+            return Err(ExportError::InvalidFrameSelection(format!(
+                "Segment {}..={} is empty",
+                start_frame, end_frame
+            )));
+        }
+
+        let effective_total_frames = end_frame - start_frame;
+        let effective_duration = if last_frame > 0.0 {
+            (duration * effective_total_frames / last_frame) / config.speed
+        } else {
+            0.0
+        };
+
+        let steps = (effective_duration * fps).round().max(1.0) as usize;
+
+        let forward: Vec<f32> = (0..=steps)
+            .map(|step| {
+                let frame = start_frame + effective_total_frames * step as f32 / steps as f32;
+
+                let frame = if config.use_frame_interpolation {
+                    (frame * 1000.0).round() / 1000.0
+                } else {
+                    frame.round()
+                };
+
+                frame.clamp(start_frame, end_frame)
+            })
+            .collect();
+        let reverse: Vec<f32> = forward.iter().rev().copied().collect();
+
+        // A bouncing loop restarts where it ends, the turning frames are only shown once
+        let bounce = |first: &[f32], second: &[f32]| {
+            let second_end = if config.loop_animation {
+                second.len() - 1
+            } else {
+                second.len()
+            };
+
+            [first, &second[1..second_end]].concat()
+        };
+
+        // A loop restarts where it begins, the last frame would be shown twice at the seam
+        let once = |frames: Vec<f32>| {
+            if config.loop_animation {
+                frames[..frames.len() - 1].to_vec()
+            } else {
+                frames
+            }
+        };
+
+        let frames = match config.mode {
+            Mode::Forward => once(forward),
+            Mode::Reverse => once(reverse),
+            Mode::Bounce => bounce(&forward, &reverse),
+            Mode::ReverseBounce => bounce(&reverse, &forward),
+        };
+
+        Ok(PlaybackTimeline {
+            frames,
+            fps,
+            loop_animation: config.loop_animation,
+        })
+    }
+
+    /// Encodes one playback of the animation as an animated GIF, APNG or WebP.
+    ///
+    /// GIF only supports fully transparent pixels and 256 colors per frame, semi-transparent
+    /// pixels are best exported over an opaque background color.
+    pub fn export_animation<W: Write>(
+        &mut self,
+        writer: W,
+        format: AnimatedFormat,
+        config: &Config,
+        fps: Option<f32>,
+    ) -> Result<(), ExportError> {
+        let timeline = self.playback_timeline(config, fps)?;
+        let (width, height) = (self.width(), self.height());
+        let render = |frame| self.render_frame(frame);
+
+        match format {
+            AnimatedFormat::Gif => encode_gif(writer, width, height, &timeline, render),
+            AnimatedFormat::Apng => encode_apng(writer, width, height, &timeline, render),
+            AnimatedFormat::WebP => encode_webp(writer, width, height, &timeline, render),
+        }
+    }
+
+    pub fn write_animation(
+        &mut self,
+        path: &Path,
+        format: AnimatedFormat,
+        config: &Config,
+        fps: Option<f32>,
+    ) -> Result<(), ExportError> {
+        let file = File::create(path).map_err(|error| write_error(path, error))?;
+        let mut writer = BufWriter::new(file);
+
+        self.export_animation(&mut writer, format, config, fps)?;
+
+        writer.flush().map_err(|error| write_error(path, error))
+    }
+}
+
+fn encode_gif<W: Write>(
+    writer: W,
+    width: u32,
+    height: u32,
+    timeline: &PlaybackTimeline,
+    mut render: impl FnMut(f32) -> Result<ExportedFrame, ExportError>,
+) -> Result<(), ExportError> {
+    let (width, height) = match (u16::try_from(width), u16::try_from(height)) {
+        (Ok(width), Ok(height)) => (width, height),
+        _ => {
+            return Err(ExportError::EncodingError(
+                "GIF width and height must be at most 65535".to_string(),
+            ))
+        }
+    };
+
+    let mut encoder = gif::Encoder::new(writer, width, height, &[]).map_err(encoding_error)?;
+
+    if timeline.loop_animation {
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(encoding_error)?;
+    }
+
+    for (frame, delay) in timeline.frames.iter().zip(timeline.delays(100.0)) {
+        let mut rendered = render(*frame)?;
+
+        let mut gif_frame = gif::Frame::from_rgba_speed(width, height, &mut rendered.data, 10);
+        gif_frame.delay = delay.min(u16::MAX as u32) as u16;
+        gif_frame.dispose = gif::DisposalMethod::Background;
+
+        encoder.write_frame(&gif_frame).map_err(encoding_error)?;
+    }
+
+    Ok(())
+}
+
+fn encode_apng<W: Write>(
+    writer: W,
+    width: u32,
+    height: u32,
+    timeline: &PlaybackTimeline,
+    mut render: impl FnMut(f32) -> Result<ExportedFrame, ExportError>,
+) -> Result<(), ExportError> {
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(
+            timeline.frames.len() as u32,
+            if timeline.loop_animation { 0 } else { 1 },
+        )
+        .map_err(encoding_error)?;
+
+    let mut png_writer = encoder.write_header().map_err(encoding_error)?;
+
+    for (frame, delay) in timeline.frames.iter().zip(timeline.delays(1000.0)) {
+        let rendered = render(*frame)?;
+
+        png_writer
+            .set_frame_delay(delay.min(u16::MAX as u32) as u16, 1000)
+            .map_err(encoding_error)?;
+        png_writer
+            .write_image_data(&rendered.data)
+            .map_err(encoding_error)?;
+    }
+
+    png_writer.finish().map_err(encoding_error)
+}
+
+fn encode_webp<W: Write>(
+    mut writer: W,
+    width: u32,
+    height: u32,
+    timeline: &PlaybackTimeline,
+    mut render: impl FnMut(f32) -> Result<ExportedFrame, ExportError>,
+) -> Result<(), ExportError> {
+    if width > 1 << 14 || height > 1 << 14 {
+        return Err(ExportError::EncodingError(
+            "WebP width and height must be at most 16384".to_string(),
+        ));
+    }
+
+    let mut frame_chunks = vec![];
+
+    for (frame, delay) in timeline.frames.iter().zip(timeline.delays(1000.0)) {
+        let rendered = render(*frame)?;
+
+        let mut image = vec![];
+        image_webp::WebPEncoder::new(&mut image)
+            .encode(&rendered.data, width, height, image_webp::ColorType::Rgba8)
+            .map_err(encoding_error)?;
+
+        // A simple lossless WebP is the RIFF header followed by a single VP8L chunk
+        let vp8l_chunk = image
+            .get(12..)
+            .filter(|chunk| chunk.starts_with(b"VP8L"))
+            .ok_or_else(|| {
+                ExportError::EncodingError("Unexpected WebP frame layout".to_string())
+            })?;
+
+        let mut anmf = Vec::with_capacity(16 + vp8l_chunk.len());
+        anmf.extend_from_slice(&[0; 6]); // frame x & y
+        anmf.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+        anmf.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+        anmf.extend_from_slice(&delay.min(0xFFFFFF).to_le_bytes()[..3]);
+        // replace the previous frame instead of blending over it
+        anmf.push(0b10);
+        anmf.extend_from_slice(vp8l_chunk);
+
+        frame_chunks.push(anmf);
+    }
+
+    let mut vp8x = vec![0b0001_0010, 0, 0, 0]; // alpha & animation flags
+    vp8x.extend_from_slice(&(width - 1).to_le_bytes()[..3]);
+    vp8x.extend_from_slice(&(height - 1).to_le_bytes()[..3]);
+
+    let mut anim = vec![0; 4]; // transparent background
+    anim.extend_from_slice(&(if timeline.loop_animation { 0u16 } else { 1 }).to_le_bytes());
+
+    let mut webp = b"WEBP".to_vec();
+    write_riff_chunk(&mut webp, b"VP8X", &vp8x);
+    write_riff_chunk(&mut webp, b"ANIM", &anim);
+
+    for anmf in frame_chunks {
+        write_riff_chunk(&mut webp, b"ANMF", &anmf);
+    }
+
+    let mut riff = Vec::with_capacity(webp.len() + 8);
+    write_riff_chunk(&mut riff, b"RIFF", &webp);
+
+    writer
+        .write_all(&riff)
+        .map_err(|error| ExportError::EncodingError(error.to_string()))
+}
+
+fn write_riff_chunk(output: &mut Vec<u8>, name: &[u8; 4], data: &[u8]) {
+    output.extend_from_slice(name);
+    output.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    output.extend_from_slice(data);
+
+    // chunks are padded to an even size
+    if data.len() % 2 == 1 {
+        output.push(0);
+    }
+}
+
+fn encoding_error(error: impl std::fmt::Display) -> ExportError {
+    ExportError::EncodingError(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_delays() {
+        let timeline = PlaybackTimeline {
+            frames: vec![0.0; 6],
+            fps: 30.0,
+            loop_animation: false,
+        };
+
+        // 33.3ms per frame, the rounding errors don't accumulate
+        assert_eq!(timeline.delays(1000.0), vec![33, 34, 33, 33, 34, 33]);
+        assert_eq!(timeline.delays(1000.0).iter().sum::<u32>(), 200);
+        assert_eq!(timeline.delays(100.0).iter().sum::<u32>(), 20);
+    }
+
+    #[test]
+    fn test_write_riff_chunk() {
+        let mut output = vec![];
+
+        write_riff_chunk(&mut output, b"ANIM", &[1, 2, 3]);
+
+        assert_eq!(output, b"ANIM\x03\x00\x00\x00\x01\x02\x03\x00");
+    }
+
+    // Two 2x1 frames, one red and opaque, one transparent
+    fn timeline(loop_animation: bool) -> PlaybackTimeline {
+        PlaybackTimeline {
+            frames: vec![0.0, 1.0],
+            fps: 10.0,
+            loop_animation,
+        }
+    }
+
+    fn render(frame: f32) -> Result<ExportedFrame, ExportError> {
+        let pixel = if frame == 0.0 {
+            [255, 0, 0, 255]
+        } else {
+            [0, 0, 0, 0]
+        };
+
+        Ok(ExportedFrame {
+            frame,
+            width: 2,
+            height: 1,
+            data: [pixel, pixel].concat(),
+        })
+    }
+
+    #[test]
+    fn test_encode_gif() {
+        let mut output = vec![];
+        encode_gif(&mut output, 2, 1, &timeline(true), render).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(output.as_slice()).unwrap();
+
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(first.delay, 10);
+        assert_eq!(&first.buffer[..4], &[255, 0, 0, 255]);
+
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(second.buffer[3], 0);
+
+        assert!(decoder.read_next_frame().unwrap().is_none());
+        assert_eq!(decoder.repeat(), gif::Repeat::Infinite);
+    }
+
+    #[test]
+    fn test_encode_gif_too_large() {
+        assert!(matches!(
+            encode_gif(vec![], 70000, 1, &timeline(false), render),
+            Err(ExportError::EncodingError(_))
+        ));
+    }
+
+    #[test]
+    fn test_encode_apng() {
+        let mut output = vec![];
+        encode_apng(&mut output, 2, 1, &timeline(false), render).unwrap();
+
+        let mut reader = png::Decoder::new(output.as_slice()).read_info().unwrap();
+        let animation_control = reader.info().animation_control.unwrap();
+        assert_eq!(animation_control.num_frames, 2);
+        assert_eq!(animation_control.num_plays, 1);
+
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut buffer).unwrap();
+        assert_eq!(&buffer[..4], &[255, 0, 0, 255]);
+
+        let frame_control = reader.info().frame_control.unwrap();
+        assert_eq!(
+            (frame_control.delay_num, frame_control.delay_den),
+            (100, 1000)
+        );
+    }
+
+    #[test]
+    fn test_encode_webp() {
+        let mut output = vec![];
+        encode_webp(&mut output, 2, 1, &timeline(true), render).unwrap();
+
+        let mut decoder = image_webp::WebPDecoder::new(std::io::Cursor::new(output)).unwrap();
+        assert!(decoder.is_animated());
+        assert!(decoder.has_alpha());
+        assert_eq!(decoder.num_frames(), 2);
+        assert_eq!(decoder.loop_count(), image_webp::LoopCount::Forever);
+        assert_eq!(decoder.loop_duration(), 200);
+
+        let mut buffer = vec![0; decoder.output_buffer_size().unwrap()];
+        assert_eq!(decoder.read_frame(&mut buffer).unwrap(), 100);
+        assert_eq!(&buffer[..4], &[255, 0, 0, 255]);
+
+        decoder.read_frame(&mut buffer).unwrap();
+        assert_eq!(buffer[3], 0);
+    }
+}
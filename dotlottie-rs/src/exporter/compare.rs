@@ -0,0 +1,200 @@
+use super::{ExportError, ExportedFrame};
+
+// The largest possible YIQ color delta, between black and white
+const MAX_COLOR_DELTA: f32 = 35215.0;
+
+/// The result of comparing two frames pixel by pixel.
+pub struct FrameDiff {
+    pub diff_pixels: usize,
+    pub total_pixels: usize,
+    /// The expected frame faded to gray, with the differing pixels in red.
+    pub diff_image: ExportedFrame,
+}
+
+impl FrameDiff {
+    /// The share of differing pixels, between 0 and 1.
+    pub fn diff_ratio(&self) -> f32 {
+        if self.total_pixels == 0 {
+            0.0
+        } else {
+            self.diff_pixels as f32 / self.total_pixels as f32
+        }
+    }
+}
+
+impl ExportedFrame {
+    /// Decodes a PNG image, usually a reference image of the given frame.
+    pub fn from_png(frame: f32, png_data: &[u8]) -> Result<Self, ExportError> {
+        let mut decoder = png::Decoder::new(png_data);
+        // expand palettes and low bit depths to 8 bits per channel
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+        let mut reader = decoder
+            .read_info()
+            .map_err(|error| ExportError::EncodingError(error.to_string()))?;
+
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader
+            .next_frame(&mut buffer)
+            .map_err(|error| ExportError::EncodingError(error.to_string()))?;
+        buffer.truncate(info.buffer_size());
+
+        let data = match info.color_type {
+            png::ColorType::Rgba => buffer,
+            png::ColorType::Rgb => buffer
+                .chunks(3)
+                .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => buffer
+                .chunks(2)
+                .flat_map(|pixel| [pixel[0], pixel[0], pixel[0], pixel[1]])
+                .collect(),
+            png::ColorType::Grayscale => buffer
+                .iter()
+                .flat_map(|gray| [*gray, *gray, *gray, 255])
+                .collect(),
+            png::ColorType::Indexed => {
+                return Err(ExportError::EncodingError(
+                    "Indexed PNG images are not supported".to_string(),
+                ))
+            }
+        };
+
+        Ok(ExportedFrame {
+            frame,
+            width: info.width,
+            height: info.height,
+            data,
+        })
+    }
+}
+
+/// Compares two frames with a perceptual color difference.
+///
+/// Colors are compared in the YIQ color space after blending them over white, which
+/// weights the differences the way the eye notices them. Antialiasing noise and small
+/// rendering differences stay under the threshold, theme or layout changes don't.
+///
+/// threshold: The color difference at which pixels are counted as different, from 0 to 1
+pub fn compare_frames(
+    expected: &ExportedFrame,
+    actual: &ExportedFrame,
+    threshold: f32,
+) -> Result<FrameDiff, ExportError> {
+    if (expected.width, expected.height) != (actual.width, actual.height) {
+        return Err(ExportError::SizeMismatch {
+            expected: (expected.width, expected.height),
+            actual: (actual.width, actual.height),
+        });
+    }
+
+    let max_delta = MAX_COLOR_DELTA * threshold * threshold;
+    let mut diff_pixels = 0;
+    let mut diff_data = Vec::with_capacity(expected.data.len());
+
+    for (expected_pixel, actual_pixel) in expected.data.chunks(4).zip(actual.data.chunks(4)) {
+        if color_delta(expected_pixel, actual_pixel) > max_delta {
+            diff_pixels += 1;
+            diff_data.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let (y, _, _) = yiq(expected_pixel);
+            // fade the unchanged pixels so the differences stand out
+            let gray = (255.0 + (y - 255.0) * 0.1) as u8;
+            diff_data.extend_from_slice(&[gray, gray, gray, 255]);
+        }
+    }
+
+    Ok(FrameDiff {
+        diff_pixels,
+        total_pixels: (expected.width * expected.height) as usize,
+        diff_image: ExportedFrame {
+            frame: actual.frame,
+            width: actual.width,
+            height: actual.height,
+            data: diff_data,
+        },
+    })
+}
+
+fn color_delta(expected: &[u8], actual: &[u8]) -> f32 {
+    if expected == actual {
+        return 0.0;
+    }
+
+    let (y1, i1, q1) = yiq(expected);
+    let (y2, i2, q2) = yiq(actual);
+
+    let (y, i, q) = (y1 - y2, i1 - i2, q1 - q2);
+
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+fn yiq(pixel: &[u8]) -> (f32, f32, f32) {
+    // blend over white, so that transparent pixels compare equal whatever their color
+    let alpha = pixel[3] as f32 / 255.0;
+    let blend = |channel: u8| 255.0 + (channel as f32 - 255.0) * alpha;
+
+    let (r, g, b) = (blend(pixel[0]), blend(pixel[1]), blend(pixel[2]));
+
+    (
+        r * 0.298_895 + g * 0.586_622 + b * 0.114_482,
+        r * 0.595_978 - g * 0.274_176 - b * 0.321_802,
+        r * 0.211_470 - g * 0.522_617 + b * 0.311_147,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(pixels: &[[u8; 4]]) -> ExportedFrame {
+        ExportedFrame {
+            frame: 0.0,
+            width: pixels.len() as u32,
+            height: 1,
+            data: pixels.concat(),
+        }
+    }
+
+    #[test]
+    fn test_compare_frames() {
+        let expected = frame(&[[255, 0, 0, 255], [0, 0, 0, 0], [10, 20, 30, 255]]);
+        let actual = frame(&[[250, 2, 0, 255], [0, 255, 0, 0], [200, 20, 30, 255]]);
+
+        let diff = compare_frames(&expected, &actual, 0.1).unwrap();
+
+        // Only the last pixel is noticeably different
+        assert_eq!(diff.diff_pixels, 1);
+        assert_eq!(diff.total_pixels, 3);
+        assert_eq!(&diff.diff_image.data[8..], &[255, 0, 0, 255]);
+        assert_ne!(&diff.diff_image.data[..4], &[255, 0, 0, 255]);
+
+        assert_eq!(
+            compare_frames(&expected, &actual, 1.0).unwrap().diff_pixels,
+            0
+        );
+        assert_eq!(
+            compare_frames(&expected, &actual, 0.0).unwrap().diff_pixels,
+            2
+        );
+    }
+
+    #[test]
+    fn test_compare_frames_size_mismatch() {
+        assert!(matches!(
+            compare_frames(&frame(&[[0; 4]]), &frame(&[[0; 4], [0; 4]]), 0.1),
+            Err(ExportError::SizeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_png() {
+        let expected = frame(&[[255, 0, 0, 255], [0, 0, 255, 128]]);
+
+        let decoded = ExportedFrame::from_png(3.0, &expected.to_png().unwrap()).unwrap();
+
+        assert_eq!(decoded.frame, 3.0);
+        assert_eq!((decoded.width, decoded.height), (2, 1));
+        assert_eq!(decoded.data, expected.data);
+    }
+}
@@ -0,0 +1,420 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use crate::{
+    extract_markers, get_width_height, DotLottieError, DotLottieManager, Layout, LottieRenderer,
+    LottieRendererError, MarkersMap, TvgColorspace,
+};
+
+mod animated;
+mod compare;
+
+pub use animated::*;
+pub use compare::*;
+
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Renderer error: {0}")]
+    RendererError(#[from] LottieRendererError),
+
+    #[error("DotLottie error: {0}")]
+    DotLottieError(#[from] DotLottieError),
+
+    #[error("Invalid frame selection: {0}")]
+    InvalidFrameSelection(String),
+
+    #[error("Failed to write {path}: {reason}")]
+    WriteError { path: String, reason: String },
+
+    #[error("Failed to encode frame: {0}")]
+    EncodingError(String),
+
+    #[error("Frame size mismatch: expected {expected:?}, got {actual:?}")]
+    SizeMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+}
+
+/// The animation to export, either Lottie JSON or the bytes of a dotLottie file.
+pub enum AnimationSource<'a> {
+    Json(&'a str),
+    DotLottie(&'a [u8]),
+}
+
+/// The frames to render.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameSelection {
+    /// A single frame.
+    Frame(f32),
+    /// Every frame from start to end, both included.
+    Range { start: f32, end: f32 },
+    /// The whole animation sampled at a fixed frame rate.
+    Fps(f32),
+}
+
+#[derive(Clone)]
+pub struct ExportConfig {
+    /// Output width, the width of the animation if None.
+    pub width: Option<u32>,
+    /// Output height, the height of the animation if None.
+    pub height: Option<u32>,
+    /// Animation to export from a dotLottie file, the active animation if empty.
+    pub animation_id: String,
+    /// Theme to apply from a dotLottie file, no theme if empty.
+    pub theme_id: String,
+    /// Theme data to apply, takes precedence over theme_id.
+    pub theme_data: String,
+    pub layout: Layout,
+    /// Background color as 0xRRGGBBAA, transparent by default.
+    pub background_color: u32,
+    pub frames: FrameSelection,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        ExportConfig {
+            width: None,
+            height: None,
+            animation_id: String::new(),
+            theme_id: String::new(),
+            theme_data: String::new(),
+            layout: Layout::default(),
+            background_color: 0,
+            frames: FrameSelection::Frame(0.0),
+        }
+    }
+}
+
+/// A rendered frame as straight alpha RGBA bytes.
+pub struct ExportedFrame {
+    pub frame: f32,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl ExportedFrame {
+    pub fn to_png(&self) -> Result<Vec<u8>, ExportError> {
+        let mut png_data = Vec::new();
+
+        self.encode_png(&mut png_data)?;
+
+        Ok(png_data)
+    }
+
+    pub fn write_png(&self, path: &Path) -> Result<(), ExportError> {
+        let file = File::create(path).map_err(|error| write_error(path, error))?;
+
+        self.encode_png(BufWriter::new(file))
+    }
+
+    pub fn write_rgba(&self, path: &Path) -> Result<(), ExportError> {
+        fs::write(path, &self.data).map_err(|error| write_error(path, error))
+    }
+
+    fn encode_png<W: std::io::Write>(&self, writer: W) -> Result<(), ExportError> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.data))
+            .map_err(|error| ExportError::EncodingError(error.to_string()))
+    }
+}
+
+/// Renders frames of an animation without a player, for thumbnails and snapshots.
+pub struct FrameExporter {
+    renderer: LottieRenderer,
+    frames: FrameSelection,
+    markers: MarkersMap,
+}
+
+impl FrameExporter {
+    pub fn new(source: AnimationSource, config: ExportConfig) -> Result<Self, ExportError> {
+        let mut renderer = LottieRenderer::new();
+
+        let markers = match source {
+            AnimationSource::Json(animation_data) => {
+                let (width, height) = output_size(animation_data, &config)?;
+
+                renderer.load_data(animation_data, width, height, true)?;
+
+                extract_markers(animation_data)
+            }
+            AnimationSource::DotLottie(file_data) => {
+                let mut manager = DotLottieManager::new(Some(file_data.to_vec()))?;
+
+                let animation_id = if config.animation_id.is_empty() {
+                    manager.active_animation_id()
+                } else {
+                    config.animation_id.clone()
+                };
+
+                let animation_data = manager.get_animation_data(&animation_id)?;
+                let (width, height) = output_size(&animation_data, &config)?;
+
+                renderer.load_data_with_images(&animation_data, &manager, width, height, true)?;
+
+                if config.theme_data.is_empty() && !config.theme_id.is_empty() {
+                    let theme_data = manager.get_theme(&config.theme_id)?;
+
+                    renderer.load_theme_data(&theme_data)?;
+                }
+
+                extract_markers(&animation_data)
+            }
+        };
+
+        if !config.theme_data.is_empty() {
+            renderer.load_theme_data(&config.theme_data)?;
+        }
+
+        renderer.set_layout(&config.layout)?;
+        renderer.set_background_color(config.background_color)?;
+
+        Ok(FrameExporter {
+            renderer,
+            frames: config.frames,
+            markers,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.renderer.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.renderer.height
+    }
+
+    /// The frame numbers of the selection, in rendering order.
+    pub fn frames(&self) -> Result<Vec<f32>, ExportError> {
+        let total_frames = self.renderer.total_frames()?;
+        let last_frame = total_frames - 1.0;
+
+        match self.frames {
+            FrameSelection::Frame(frame) => {
+                if !(0.0..=last_frame).contains(&frame) {
+                    return Err(ExportError::InvalidFrameSelection(format!(
+                        "Frame {} is not between 0 and {}",
+                        frame, last_frame
+                    )));
+                }
+
+                Ok(vec![frame])
+            }
+            FrameSelection::Range { start, end } => {
+                if start < 0.0 || start > end || start > last_frame {
+                    return Err(ExportError::InvalidFrameSelection(format!(
+                        "Range {}..={} is not between 0 and {}",
+                        start, end, last_frame
+                    )));
+                }
+
+                let end = end.min(last_frame);
+                let count = (end - start).floor() as usize + 1;
+
+                Ok((0..count).map(|index| start + index as f32).collect())
+            }
+            FrameSelection::Fps(fps) => {
+                if !fps.is_finite() || fps <= 0.0 {
+                    return Err(ExportError::InvalidFrameSelection(format!(
+                        "Frame rate must be greater than 0, got {}",
+                        fps
+                    )));
+                }
+
+                let duration = self.renderer.duration()?;
+                let count = (duration * fps).ceil().max(1.0) as usize;
+                let step = total_frames / (duration * fps);
+
+                Ok((0..count)
+                    .map(|index| index as f32 * step)
+                    .filter(|frame| *frame < total_frames)
+                    .collect())
+            }
+        }
+    }
+
+    pub fn render_frame(&mut self, frame: f32) -> Result<ExportedFrame, ExportError> {
+        self.renderer.set_frame(frame)?;
+        self.renderer.render()?;
+
+        let pixel_count = (self.renderer.width * self.renderer.height) as usize;
+
+        Ok(ExportedFrame {
+            frame,
+            width: self.renderer.width,
+            height: self.renderer.height,
+            data: to_rgba(
+                &self.renderer.buffer[..pixel_count],
+                self.renderer.color_space(),
+            ),
+        })
+    }
+
+    /// Renders every selected frame.
+    pub fn render_frames(&mut self) -> Result<Vec<ExportedFrame>, ExportError> {
+        self.frames()?
+            .into_iter()
+            .map(|frame| self.render_frame(frame))
+            .collect()
+    }
+
+    /// Writes the selected frames to frame_0000.png, frame_0001.png... in the output directory.
+    pub fn export_png_sequence(&mut self, output_dir: &Path) -> Result<Vec<PathBuf>, ExportError> {
+        self.export_sequence(output_dir, "png", ExportedFrame::write_png)
+    }
+
+    /// Writes the selected frames as raw RGBA bytes to frame_0000.rgba, frame_0001.rgba...
+    pub fn export_rgba_sequence(&mut self, output_dir: &Path) -> Result<Vec<PathBuf>, ExportError> {
+        self.export_sequence(output_dir, "rgba", ExportedFrame::write_rgba)
+    }
+
+    fn export_sequence(
+        &mut self,
+        output_dir: &Path,
+        extension: &str,
+        write: fn(&ExportedFrame, &Path) -> Result<(), ExportError>,
+    ) -> Result<Vec<PathBuf>, ExportError> {
+        fs::create_dir_all(output_dir).map_err(|error| write_error(output_dir, error))?;
+
+        let mut paths = vec![];
+
+        for (index, frame) in self.frames()?.into_iter().enumerate() {
+            let path = output_dir.join(format!("frame_{:04}.{}", index, extension));
+
+            write(&self.render_frame(frame)?, &path)?;
+
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+}
+
+/// Converts pixels rendered in the given color space to straight alpha RGBA bytes.
+pub fn to_rgba(pixels: &[u32], color_space: TvgColorspace) -> Vec<u8> {
+    let premultiplied = matches!(
+        color_space,
+        TvgColorspace::ABGR8888 | TvgColorspace::ARGB8888
+    );
+
+    let mut rgba = Vec::with_capacity(pixels.len() * 4);
+
+    for pixel in pixels {
+        let alpha = (pixel >> 24) as u8;
+        let (red, green, blue) = match color_space {
+            TvgColorspace::ABGR8888 | TvgColorspace::ABGR8888S => {
+                (*pixel as u8, (pixel >> 8) as u8, (pixel >> 16) as u8)
+            }
+            TvgColorspace::ARGB8888 | TvgColorspace::ARGB8888S => {
+                ((pixel >> 16) as u8, (pixel >> 8) as u8, *pixel as u8)
+            }
+        };
+
+        if premultiplied && alpha == 0 {
+            rgba.extend_from_slice(&[0, 0, 0, 0]);
+        } else if premultiplied && alpha < 255 {
+            let unpremultiply =
+                |channel: u8| ((channel as u32 * 255 + alpha as u32 / 2) / alpha as u32).min(255);
+
+            rgba.extend_from_slice(&[
+                unpremultiply(red) as u8,
+                unpremultiply(green) as u8,
+                unpremultiply(blue) as u8,
+                alpha,
+            ]);
+        } else {
+            rgba.extend_from_slice(&[red, green, blue, alpha]);
+        }
+    }
+
+    rgba
+}
+
+fn output_size(animation_data: &str, config: &ExportConfig) -> Result<(u32, u32), ExportError> {
+    if let (Some(width), Some(height)) = (config.width, config.height) {
+        return Ok((width, height));
+    }
+
+    let (animation_width, animation_height) = get_width_height(animation_data)?;
+
+    Ok((
+        config.width.unwrap_or(animation_width),
+        config.height.unwrap_or(animation_height),
+    ))
+}
+
+fn write_error(path: &Path, error: std::io::Error) -> ExportError {
+    ExportError::WriteError {
+        path: path.display().to_string(),
+        reason: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_rgba_abgr() {
+        assert_eq!(
+            to_rgba(&[0xFF332211, 0x00000000], TvgColorspace::ABGR8888S),
+            vec![0x11, 0x22, 0x33, 0xFF, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            to_rgba(&[0xFF332211], TvgColorspace::ARGB8888S),
+            vec![0x33, 0x22, 0x11, 0xFF]
+        );
+    }
+
+    #[test]
+    fn test_to_rgba_premultiplied() {
+        // 50% red, premultiplied
+        assert_eq!(
+            to_rgba(&[0x80000080], TvgColorspace::ABGR8888),
+            vec![0xFF, 0, 0, 0x80]
+        );
+        assert_eq!(
+            to_rgba(&[0x80800000], TvgColorspace::ARGB8888),
+            vec![0xFF, 0, 0, 0x80]
+        );
+        // The same pixel in straight alpha is left untouched
+        assert_eq!(
+            to_rgba(&[0x80000080], TvgColorspace::ABGR8888S),
+            vec![0x80, 0, 0, 0x80]
+        );
+        assert_eq!(
+            to_rgba(&[0x00FFFFFF], TvgColorspace::ABGR8888),
+            vec![0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_exported_frame_to_png() {
+        let frame = ExportedFrame {
+            frame: 0.0,
+            width: 2,
+            height: 1,
+            data: vec![255, 0, 0, 255, 0, 0, 255, 128],
+        };
+
+        let png_data = frame.to_png().unwrap();
+        let mut reader = png::Decoder::new(png_data.as_slice()).read_info().unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+
+        assert_eq!((info.width, info.height), (2, 1));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        assert_eq!(decoded, frame.data);
+    }
+}
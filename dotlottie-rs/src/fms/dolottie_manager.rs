@@ -1,11 +1,19 @@
-use std::{collections::HashMap, ops::Index};
+use std::{borrow::Cow, collections::HashMap, io::Cursor, ops::Index, sync::Arc};
 
-use super::{get_manifest, AnimationContainer, DotLottieError, Manifest, ManifestAnimation};
+use zip::ZipArchive;
+
+use super::{
+    get_manifest, read_animation_data, read_image, AnimationContainer, DotLottieError, Manifest,
+    ManifestAnimation,
+};
+use crate::{embed_images, ImageResolver};
 
 pub struct DotLottieManager {
     active_animation_id: String,
     manifest: Manifest,
-    zip_data: Vec<u8>,
+    zip_data: Arc<[u8]>,
+    // Opened once, its clones share the file entries and the data
+    archive: Option<ZipArchive<Cursor<Arc<[u8]>>>>,
     animation_settings_cache: HashMap<String, ManifestAnimation>,
     animation_data_cache: HashMap<String, String>,
     theme_cache: HashMap<String, String>,
@@ -29,10 +37,13 @@ impl DotLottieManager {
                         return Err(DotLottieError::AnimationsNotFound);
                     }
 
+                    let zip_data: Arc<[u8]> = dotlottie.into();
+
                     Ok(DotLottieManager {
                         active_animation_id: id,
                         manifest,
-                        zip_data: dotlottie,
+                        archive: Some(open_archive(&zip_data)?),
+                        zip_data,
                         animation_settings_cache: HashMap::new(),
                         animation_data_cache: HashMap::new(),
                         theme_cache: HashMap::new(),
@@ -44,7 +55,8 @@ impl DotLottieManager {
             Ok(DotLottieManager {
                 active_animation_id: String::new(),
                 manifest: Manifest::new(),
-                zip_data: vec![],
+                zip_data: Arc::new([]),
+                archive: None,
                 animation_settings_cache: HashMap::new(),
                 animation_data_cache: HashMap::new(),
                 theme_cache: HashMap::new(),
@@ -70,7 +82,8 @@ impl DotLottieManager {
 
                 self.active_animation_id = id;
                 self.manifest = manifest;
-                self.zip_data = dotlottie.to_vec();
+                self.zip_data = dotlottie.into();
+                self.archive = Some(open_archive(&self.zip_data)?);
 
                 Ok(true)
             }
@@ -178,15 +191,28 @@ impl DotLottieManager {
         self.get_playback_settings(&animation_id)
     }
 
-    /// Returns the animation data for the animation with the given ID.
-    /// Memoizes the animation data in a HashMap for faster access.
+    /// Returns the animation data for the animation with the given ID, with its image assets
+    /// inlined.
     pub fn get_animation(&mut self, animation_id: &str) -> Result<String, DotLottieError> {
+        let animation = self.get_animation_data(animation_id)?;
+
+        Ok(embed_images(&animation, self).into_owned())
+    }
+
+    /// Returns the animation data for the animation with the given ID, as stored in the archive.
+    /// Image assets aren't inlined, the manager resolves them as an `ImageResolver`.
+    /// Memoizes the animation data in a HashMap for faster access.
+    pub fn get_animation_data(&mut self, animation_id: &str) -> Result<String, DotLottieError> {
         if let Some(animation) = self.animation_data_cache.get(animation_id) {
             let cloned_animation = animation.clone(); // Clone the value
 
             Ok(cloned_animation)
         } else {
-            let animation = crate::get_animation(&self.zip_data, animation_id);
+            let animation = self
+                .archive
+                .clone()
+                .ok_or(DotLottieError::ArchiveOpenError)
+                .and_then(|mut archive| read_animation_data(&mut archive, animation_id));
 
             if let Ok(animation) = animation {
                 self.animation_data_cache
@@ -263,3 +289,18 @@ impl DotLottieManager {
         Ok(theme)
     }
 }
+
+fn open_archive(zip_data: &Arc<[u8]>) -> Result<ZipArchive<Cursor<Arc<[u8]>>>, DotLottieError> {
+    ZipArchive::new(Cursor::new(zip_data.clone())).map_err(|_| DotLottieError::ArchiveOpenError)
+}
+
+/// Resolves the image assets from the images folder of the archive.
+///
+/// Images are decompressed on demand and not cached, so they only live as long as the load.
+impl ImageResolver for DotLottieManager {
+    fn resolve(&self, _asset_id: &str, path: &str) -> Option<Cow<'_, [u8]>> {
+        let mut archive = self.archive.clone()?;
+
+        read_image(&mut archive, path).ok().map(Cow::Owned)
+    }
+}
@@ -1,9 +1,9 @@
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum DotLottieError {
     #[error("Failed to open archive")]
     ArchiveOpenError,
 
-    #[error("State machine error")]
+    #[error("State machine error: {reason}")]
     StateMachineError { reason: String },
 
     #[error("Unable to find the file: {file_name}")]
@@ -15,7 +15,7 @@ pub enum DotLottieError {
     #[error("Unable to lock the animations mutex")]
     MutexLockError,
 
-    #[error("Animation not found")]
+    #[error("Animation not found: {animation_id}")]
     AnimationNotFound { animation_id: String },
 
     #[error("No animations found in dotLottie file")]
@@ -24,6 +24,30 @@ pub enum DotLottieError {
     #[error("No manifest found")]
     ManifestNotFound,
 
+    #[error("Invalid manifest: {reason}")]
+    InvalidManifest { reason: String },
+
+    #[error("Invalid JSON: {reason}")]
+    InvalidJson { reason: String },
+
+    #[error("Animation is missing its width or height")]
+    MissingDimensions,
+
     #[error("Invalid UTF-8")]
     InvalidUtf8Error,
+
+    #[error("Failed to write archive")]
+    ArchiveWriteError,
+
+    #[error("Duplicate id: {id}")]
+    DuplicateId { id: String },
+
+    #[error("Invalid animation data: {animation_id}")]
+    InvalidAnimationData { animation_id: String },
+
+    #[error("Invalid theme data: {theme_id}")]
+    InvalidThemeData { theme_id: String },
+
+    #[error("Unsupported asset path: {path}")]
+    UnsupportedAssetPath { path: String },
 }
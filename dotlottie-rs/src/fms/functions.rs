@@ -1,5 +1,5 @@
 use super::{AnimationContainer, DotLottieError, Manifest};
-use std::io::{self, Read};
+use std::io::{self, Read, Seek};
 use std::path::Path;
 
 use base64::{engine::general_purpose, Engine};
@@ -12,7 +12,7 @@ use zip::ZipArchive;
 /// animation_id: The id of the animation to extract
 /// Result<String, DotLottieError>: The extracted animation, or an error
 /// Notes: This function uses jzon rather than serde as serde was exporting invalid JSON
-pub fn get_animation(bytes: &Vec<u8>, animation_id: &str) -> Result<String, DotLottieError> {
+pub fn get_animation(bytes: &[u8], animation_id: &str) -> Result<String, DotLottieError> {
     let mut archive =
         ZipArchive::new(io::Cursor::new(bytes)).map_err(|_| DotLottieError::ArchiveOpenError)?;
 
@@ -34,29 +34,28 @@ pub fn get_animation(bytes: &Vec<u8>, animation_id: &str) -> Result<String, DotL
     // We can drop result so that we can use archive later, everything has been read in to content variable
     drop(result);
 
-    let animation_data = String::from_utf8(content).unwrap();
+    let animation_data =
+        String::from_utf8(content).map_err(|_| DotLottieError::InvalidUtf8Error)?;
 
     // Untyped JSON value
-    let mut lottie_animation = jzon::parse(&animation_data).unwrap();
+    let mut lottie_animation =
+        jzon::parse(&animation_data).map_err(|_| DotLottieError::InvalidAnimationData {
+            animation_id: animation_id.to_string(),
+        })?;
 
     // Loop through the parsed lottie animation and check for image assets
     if let Some(assets) = lottie_animation["assets"].as_array_mut() {
         for asset in assets {
-            if let Some(p) = asset["p"].as_str() {
+            if let Some(p) = asset["p"].as_str().map(str::to_string) {
                 if p.starts_with("data:image/") {
                     // if the asset is already inlined, force the embed flag to 1
                     asset["e"] = 1.into();
                 } else {
-                    let image_asset_filename =
-                        format!("images/{}", asset["p"].to_string().replace('"', ""));
+                    validate_asset_path(&p)?;
 
-                    let image_ext = asset["p"]
-                        .to_string()
-                        .split('.')
-                        .last()
-                        .unwrap()
-                        .to_string()
-                        .replace('"', "");
+                    let image_asset_filename = format!("images/{}", p);
+
+                    let image_ext = p.rsplit('.').next().unwrap_or_default();
 
                     let mut result = archive.by_name(&image_asset_filename).map_err(|_| {
                         DotLottieError::FileFindError {
@@ -86,17 +85,81 @@ pub fn get_animation(bytes: &Vec<u8>, animation_id: &str) -> Result<String, DotL
     Ok(jzon::stringify(lottie_animation))
 }
 
+/// Extract the Lottie JSON of a single animation as stored in the archive.
+///
+/// Unlike `get_animation`, image assets keep referencing their files in the archive,
+/// they can be read with `get_image` when needed.
+///
+/// bytes: The bytes of the dotLottie file
+/// animation_id: The id of the animation to extract
+/// Result<String, DotLottieError>: The extracted animation, or an error
+pub fn get_animation_data(bytes: &[u8], animation_id: &str) -> Result<String, DotLottieError> {
+    let mut archive =
+        ZipArchive::new(io::Cursor::new(bytes)).map_err(|_| DotLottieError::ArchiveOpenError)?;
+
+    read_animation_data(&mut archive, animation_id)
+}
+
+/// Extract an image asset.
+///
+/// bytes: The bytes of the dotLottie file
+/// file_name: The file name of the image, as referenced by the "p" of the asset
+/// Result<Vec<u8>, DotLottieError>: The bytes of the image, or an error
+pub fn get_image(bytes: &[u8], file_name: &str) -> Result<Vec<u8>, DotLottieError> {
+    let mut archive =
+        ZipArchive::new(io::Cursor::new(bytes)).map_err(|_| DotLottieError::ArchiveOpenError)?;
+
+    read_image(&mut archive, file_name)
+}
+
+// Reads the Lottie JSON of an animation from an opened archive
+pub(crate) fn read_animation_data<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    animation_id: &str,
+) -> Result<String, DotLottieError> {
+    let content = read_file(archive, format!("animations/{}.json", animation_id))?;
+
+    String::from_utf8(content).map_err(|_| DotLottieError::InvalidUtf8Error)
+}
+
+// Reads an image asset from an opened archive, the path is validated first
+pub(crate) fn read_image<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    file_name: &str,
+) -> Result<Vec<u8>, DotLottieError> {
+    validate_asset_path(file_name)?;
+
+    read_file(archive, format!("images/{}", file_name))
+}
+
+fn read_file<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    file_name: String,
+) -> Result<Vec<u8>, DotLottieError> {
+    let mut content = Vec::new();
+
+    archive
+        .by_name(&file_name)
+        .map_err(|_| DotLottieError::FileFindError { file_name })?
+        .read_to_end(&mut content)
+        .map_err(|_| DotLottieError::ReadContentError)?;
+
+    Ok(content)
+}
+
 /// Extract every animation with its image assets inlined.
 ///
 /// bytes: The bytes of the dotLottie file
 /// Result<Vec<AnimationData>, DotLottieError>: The extracted animations, or an error
-pub fn get_animations(bytes: &Vec<u8>) -> Result<Vec<AnimationContainer>, DotLottieError> {
+pub fn get_animations(bytes: &[u8]) -> Result<Vec<AnimationContainer>, DotLottieError> {
     let mut archive =
         ZipArchive::new(io::Cursor::new(bytes)).map_err(|_| DotLottieError::ArchiveOpenError)?;
     let mut file_contents = Vec::new();
 
     for i in 0..archive.len() {
-        let file = archive.by_index(i).unwrap();
+        let file = archive
+            .by_index(i)
+            .map_err(|_| DotLottieError::ReadContentError)?;
 
         if (*file.name()).starts_with("animations/") && (*file.name()).ends_with(".json") {
             // Create a Path from the file path string
@@ -105,7 +168,7 @@ pub fn get_animations(bytes: &Vec<u8>) -> Result<Vec<AnimationContainer>, DotLot
             // Get the file stem (file name without extension)
             if let Some(file_stem) = path.file_stem() {
                 if let Some(file_stem_str) = file_stem.to_str() {
-                    let animation = get_animation(bytes, file_stem_str).unwrap();
+                    let animation = get_animation(bytes, file_stem_str)?;
 
                     let item = AnimationContainer {
                         id: file_stem_str.to_string(),
@@ -145,19 +208,33 @@ pub fn get_manifest(bytes: &[u8]) -> Result<Manifest, DotLottieError> {
         .map_err(|_| DotLottieError::ReadContentError)?;
 
     let manifest_string = String::from_utf8_lossy(&content).to_string();
-    let manifest: Manifest = serde_json::from_str(&manifest_string).unwrap();
 
-    Ok(manifest)
+    serde_json::from_str(&manifest_string).map_err(|error| DotLottieError::InvalidManifest {
+        reason: error.to_string(),
+    })
 }
 
-/// Get the width and height of a dotLottie file.
-pub fn get_width_height(animation_data: &str) -> (u32, u32) {
-    let lottie_animation: Value = serde_json::from_str(animation_data).unwrap();
-
-    let width = lottie_animation["w"].as_u64().unwrap() as u32;
-    let height = lottie_animation["h"].as_u64().unwrap() as u32;
-
-    (width, height)
+/// Get the width and height of an animation.
+///
+/// animation_data: The Lottie JSON of the animation
+/// Result<(u32, u32), DotLottieError>: The width and height, or an error
+pub fn get_width_height(animation_data: &str) -> Result<(u32, u32), DotLottieError> {
+    let lottie_animation: Value =
+        serde_json::from_str(animation_data).map_err(|error| DotLottieError::InvalidJson {
+            reason: error.to_string(),
+        })?;
+
+    let dimension = |key: &str| {
+        lottie_animation[key]
+            .as_f64()
+            .filter(|value| value.is_finite() && *value >= 0.0)
+            .map(|value| value as u32)
+    };
+
+    match (dimension("w"), dimension("h")) {
+        (Some(width), Some(height)) => Ok((width, height)),
+        _ => Err(DotLottieError::MissingDimensions),
+    }
 }
 
 pub fn get_theme(bytes: &[u8], theme_id: &str) -> Result<String, DotLottieError> {
@@ -193,3 +270,19 @@ pub fn get_state_machine(bytes: &[u8], state_machine_id: &str) -> Result<String,
 
     String::from_utf8(content).map_err(|_| DotLottieError::InvalidUtf8Error)
 }
+
+// Image assets are looked up in the images folder, their paths can't point outside of it
+fn validate_asset_path(path: &str) -> Result<(), DotLottieError> {
+    if path.is_empty()
+        || path.contains('\\')
+        || path
+            .split('/')
+            .any(|component| component.is_empty() || component == "..")
+    {
+        return Err(DotLottieError::UnsupportedAssetPath {
+            path: path.to_string(),
+        });
+    }
+
+    Ok(())
+}
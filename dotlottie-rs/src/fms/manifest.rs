@@ -7,6 +7,7 @@ use std::fmt::Display;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Manifest {
+    #[serde(alias = "activeAnimationId")]
     pub active_animation_id: Option<String>,
     pub animations: Vec<ManifestAnimation>,
     pub author: Option<String>,
@@ -13,6 +13,7 @@ pub struct ManifestAnimation {
     pub id: String,
     pub intermission: Option<u32>,
     pub r#loop: Option<bool>,
+    #[serde(alias = "loopCount")]
     pub loop_count: Option<u32>,
     pub playMode: Option<String>,
     pub speed: Option<f32>,
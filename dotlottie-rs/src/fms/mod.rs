@@ -6,6 +6,7 @@ mod manifest;
 mod manifest_animation;
 mod manifest_themes;
 mod tests;
+mod writer;
 
 pub use animation::*;
 pub use dolottie_manager::*;
@@ -14,3 +15,4 @@ pub use functions::*;
 pub use manifest::*;
 pub use manifest_animation::*;
 pub use manifest_themes::*;
+pub use writer::*;
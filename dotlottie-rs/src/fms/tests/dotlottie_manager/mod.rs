@@ -94,3 +94,40 @@ fn get_manifest_test() {
 
     assert!(last_animation.id == "yummy");
 }
+
+#[test]
+fn get_animation_with_image_assets_test() {
+    use crate::{DotLottieManager, ImageResolver};
+
+    let dotlottie_bytes = include_bytes!("../resources/bull.lottie").to_vec();
+    let archived_animation = crate::get_animation_data(&dotlottie_bytes, "animation_1").unwrap();
+    let inlined_animation = crate::get_animation(&dotlottie_bytes, "animation_1").unwrap();
+
+    let mut dotlottie = DotLottieManager::new(Some(dotlottie_bytes)).unwrap();
+
+    // The animation data keeps referencing the image assets of the archive
+    let animation_data = dotlottie.get_animation_data("animation_1").unwrap();
+
+    assert_eq!(animation_data, archived_animation);
+
+    let lottie_json = jzon::parse(&animation_data).unwrap();
+    let asset = &lottie_json["assets"][0];
+    let path = asset["p"].as_str().unwrap();
+
+    assert!(!path.starts_with("data:"));
+
+    let image = dotlottie
+        .resolve(asset["id"].as_str().unwrap(), path)
+        .unwrap();
+
+    assert!(!image.is_empty());
+    assert!(dotlottie.resolve("missing", "missing.png").is_none());
+
+    // The animation has its image assets inlined
+    let animation = dotlottie.get_animation("animation_1").unwrap();
+
+    assert_eq!(
+        jzon::parse(&animation).unwrap(),
+        jzon::parse(&inlined_animation).unwrap()
+    );
+}
@@ -115,4 +115,82 @@ mod tests {
             );
         }
     }
+
+    fn create_archive(files: &[(&str, &str)]) -> Vec<u8> {
+        use std::io::Write;
+        use zip::{write::SimpleFileOptions, ZipWriter};
+
+        let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+
+        for (file_name, content) in files {
+            writer
+                .start_file(*file_name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn get_manifest_invalid_json_test() {
+        let archive = create_archive(&[("manifest.json", "{ not json")]);
+
+        assert!(matches!(
+            crate::get_manifest(&archive),
+            Err(crate::DotLottieError::InvalidManifest { .. })
+        ));
+        assert!(matches!(
+            crate::get_manifest(&[0, 1, 2, 3]),
+            Err(crate::DotLottieError::ArchiveOpenError)
+        ));
+    }
+
+    #[test]
+    fn get_animation_invalid_data_test() {
+        let archive = create_archive(&[
+            ("animations/broken.json", "{ not json"),
+            (
+                "animations/escaping.json",
+                r#"{"assets":[{"id":"image_0","p":"../manifest.json","e":0}]}"#,
+            ),
+        ]);
+
+        assert!(matches!(
+            crate::get_animation(&archive, "broken"),
+            Err(crate::DotLottieError::InvalidAnimationData { .. })
+        ));
+        assert!(matches!(
+            crate::get_animation(&archive, "escaping"),
+            Err(crate::DotLottieError::UnsupportedAssetPath { .. })
+        ));
+        assert!(matches!(
+            crate::get_animations(&archive),
+            Err(crate::DotLottieError::InvalidAnimationData { .. })
+        ));
+        assert!(matches!(
+            crate::get_image(&archive, "../manifest.json"),
+            Err(crate::DotLottieError::UnsupportedAssetPath { .. })
+        ));
+    }
+
+    #[test]
+    fn get_width_height_test() {
+        assert_eq!(
+            crate::get_width_height(r#"{"w":512,"h":256.0}"#).unwrap(),
+            (512, 256)
+        );
+        assert!(matches!(
+            crate::get_width_height(r#"{"w":512}"#),
+            Err(crate::DotLottieError::MissingDimensions)
+        ));
+        assert!(matches!(
+            crate::get_width_height(r#"{"w":-1,"h":256}"#),
+            Err(crate::DotLottieError::MissingDimensions)
+        ));
+        assert!(matches!(
+            crate::get_width_height("{ not json"),
+            Err(crate::DotLottieError::InvalidJson { .. })
+        ));
+    }
 }
@@ -1,3 +1,4 @@
 mod dotlottie_manager;
 mod functions;
 mod manifest;
+mod writer;
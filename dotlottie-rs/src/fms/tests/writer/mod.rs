@@ -0,0 +1,152 @@
+#[cfg(test)]
+use crate::{DotLottieError, DotLottieManager, DotLottieWriter, ManifestAnimation, ManifestTheme};
+
+#[cfg(test)]
+const IMAGE_ANIMATION: &str = r#"{"v":"5.5.2","fr":30,"ip":0,"op":10,"w":10,"h":10,"assets":[{"id":"image_0","w":1,"h":1,"u":"/images/","p":"img_0.png","e":0}],"layers":[]}"#;
+
+#[test]
+fn write_and_read_round_trip_test() {
+    let anger_animation = include_str!("../resources/anger.json");
+    let state_machine = include_str!("../resources/pigeon_fsm.json");
+    let theme = r#"{"rules":[]}"#;
+
+    let mut writer = DotLottieWriter::new();
+
+    writer
+        .add_animation(
+            ManifestAnimation::new_with_id("anger".to_string()),
+            anger_animation,
+        )
+        .unwrap();
+    writer
+        .add_theme(
+            ManifestTheme::new("dark".to_string(), vec!["anger".to_string()]),
+            theme,
+        )
+        .unwrap();
+    writer
+        .add_state_machine("pigeon_fsm", state_machine)
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+
+    let mut dotlottie = DotLottieManager::new(Some(bytes)).unwrap();
+    let manifest = dotlottie.manifest().unwrap();
+
+    assert_eq!(manifest.animations.len(), 1);
+    assert_eq!(manifest.animations[0].id, "anger");
+    assert_eq!(
+        manifest.themes,
+        Some(vec![ManifestTheme::new(
+            "dark".to_string(),
+            vec!["anger".to_string()]
+        )])
+    );
+    assert_eq!(manifest.states, Some(vec!["pigeon_fsm".to_string()]));
+
+    assert_eq!(dotlottie.get_animation("anger").unwrap(), anger_animation);
+    assert_eq!(dotlottie.get_theme("dark").unwrap(), theme);
+    assert_eq!(
+        dotlottie.get_state_machine("pigeon_fsm").unwrap(),
+        state_machine
+    );
+}
+
+#[test]
+fn write_active_animation_id_test() {
+    let mut writer = DotLottieWriter::new();
+
+    writer
+        .add_animation(
+            ManifestAnimation::new_with_id("first".to_string()),
+            IMAGE_ANIMATION,
+        )
+        .unwrap();
+    writer
+        .add_animation(
+            ManifestAnimation::new_with_id("second".to_string()),
+            IMAGE_ANIMATION,
+        )
+        .unwrap();
+    writer.add_image("img_0.png", &[1, 2, 3]).unwrap();
+    writer.set_active_animation_id("second").unwrap();
+
+    let dotlottie = DotLottieManager::new(Some(writer.to_bytes().unwrap())).unwrap();
+
+    assert_eq!(dotlottie.active_animation_id(), "second");
+}
+
+#[test]
+fn write_image_assets_test() {
+    let mut writer = DotLottieWriter::new();
+
+    writer
+        .add_animation(
+            ManifestAnimation::new_with_id("animation_1".to_string()),
+            IMAGE_ANIMATION,
+        )
+        .unwrap();
+    writer.add_image("img_0.png", &[1, 2, 3]).unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let animation = crate::get_animation(&bytes, "animation_1").unwrap();
+    let lottie_json = jzon::parse(&animation).unwrap();
+
+    // [1, 2, 3] encoded in base64
+    assert_eq!(
+        lottie_json["assets"][0]["p"].as_str(),
+        Some("data:image/png;base64,AQID")
+    );
+}
+
+#[test]
+fn write_invalid_entries_test() {
+    let mut writer = DotLottieWriter::new();
+
+    assert!(matches!(
+        writer.to_bytes(),
+        Err(DotLottieError::AnimationsNotFound)
+    ));
+
+    writer
+        .add_animation(
+            ManifestAnimation::new_with_id("animation".to_string()),
+            IMAGE_ANIMATION,
+        )
+        .unwrap();
+
+    assert!(matches!(
+        writer.add_animation(
+            ManifestAnimation::new_with_id("animation".to_string()),
+            IMAGE_ANIMATION,
+        ),
+        Err(DotLottieError::DuplicateId { .. })
+    ));
+    assert!(matches!(
+        writer.add_animation(
+            ManifestAnimation::new_with_id("broken".to_string()),
+            "{ not json",
+        ),
+        Err(DotLottieError::InvalidAnimationData { .. })
+    ));
+    assert!(matches!(
+        writer.add_image("../img_0.png", &[]),
+        Err(DotLottieError::UnsupportedAssetPath { .. })
+    ));
+    assert!(matches!(
+        writer.set_active_animation_id("missing"),
+        Err(DotLottieError::AnimationNotFound { .. })
+    ));
+
+    writer
+        .add_theme(
+            ManifestTheme::new("theme".to_string(), vec!["missing".to_string()]),
+            "{}",
+        )
+        .unwrap();
+
+    assert!(matches!(
+        writer.to_bytes(),
+        Err(DotLottieError::AnimationNotFound { .. })
+    ));
+}
@@ -0,0 +1,258 @@
+use std::io::{self, Write};
+
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use super::{DotLottieError, Manifest, ManifestAnimation, ManifestTheme};
+
+/// Assembles a dotLottie archive from animations, images, themes and state machines.
+///
+/// The produced archive follows the layout read back by `DotLottieManager`:
+///
+/// manifest.json
+/// animations/<animation_id>.json
+/// images/<file_name>
+/// themes/<theme_id>.json
+/// states/<state_machine_id>.json
+pub struct DotLottieWriter {
+    manifest: Manifest,
+    animations: Vec<(String, String)>,
+    images: Vec<(String, Vec<u8>)>,
+    themes: Vec<(String, String)>,
+    state_machines: Vec<(String, String)>,
+}
+
+impl Default for DotLottieWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DotLottieWriter {
+    pub fn new() -> Self {
+        Self {
+            manifest: Manifest::new(),
+            animations: vec![],
+            images: vec![],
+            themes: vec![],
+            state_machines: vec![],
+        }
+    }
+
+    /// Adds an animation to the archive.
+    ///
+    /// animation: The manifest entry describing the animation, its id is used as the file name
+    /// animation_data: The Lottie JSON of the animation
+    pub fn add_animation(
+        &mut self,
+        animation: ManifestAnimation,
+        animation_data: &str,
+    ) -> Result<(), DotLottieError> {
+        validate_id(&animation.id)?;
+
+        if self.animations.iter().any(|(id, _)| *id == animation.id) {
+            return Err(DotLottieError::DuplicateId { id: animation.id });
+        }
+
+        if jzon::parse(animation_data).is_err() {
+            return Err(DotLottieError::InvalidAnimationData {
+                animation_id: animation.id,
+            });
+        }
+
+        self.animations
+            .push((animation.id.clone(), animation_data.to_string()));
+        self.manifest.animations.push(animation);
+
+        Ok(())
+    }
+
+    /// Adds an image asset, referenced from the animations by its file name.
+    pub fn add_image(&mut self, file_name: &str, data: &[u8]) -> Result<(), DotLottieError> {
+        validate_id(file_name)?;
+
+        if self.images.iter().any(|(name, _)| name == file_name) {
+            return Err(DotLottieError::DuplicateId {
+                id: file_name.to_string(),
+            });
+        }
+
+        self.images.push((file_name.to_string(), data.to_vec()));
+
+        Ok(())
+    }
+
+    /// Adds a theme. Its animations must reference animations added to the writer.
+    pub fn add_theme(
+        &mut self,
+        theme: ManifestTheme,
+        theme_data: &str,
+    ) -> Result<(), DotLottieError> {
+        validate_id(&theme.id)?;
+
+        if self.themes.iter().any(|(id, _)| *id == theme.id) {
+            return Err(DotLottieError::DuplicateId { id: theme.id });
+        }
+
+        if jzon::parse(theme_data).is_err() {
+            return Err(DotLottieError::InvalidThemeData { theme_id: theme.id });
+        }
+
+        self.themes.push((theme.id.clone(), theme_data.to_string()));
+        self.manifest
+            .themes
+            .get_or_insert_with(Vec::new)
+            .push(theme);
+
+        Ok(())
+    }
+
+    pub fn add_state_machine(
+        &mut self,
+        state_machine_id: &str,
+        state_machine_data: &str,
+    ) -> Result<(), DotLottieError> {
+        validate_id(state_machine_id)?;
+
+        if self
+            .state_machines
+            .iter()
+            .any(|(id, _)| id == state_machine_id)
+        {
+            return Err(DotLottieError::DuplicateId {
+                id: state_machine_id.to_string(),
+            });
+        }
+
+        if jzon::parse(state_machine_data).is_err() {
+            return Err(DotLottieError::StateMachineError {
+                reason: format!("Invalid JSON in state machine: {}", state_machine_id),
+            });
+        }
+
+        self.state_machines
+            .push((state_machine_id.to_string(), state_machine_data.to_string()));
+        self.manifest
+            .states
+            .get_or_insert_with(Vec::new)
+            .push(state_machine_id.to_string());
+
+        Ok(())
+    }
+
+    /// Sets the animation loaded first by the players. Defaults to the first animation added.
+    pub fn set_active_animation_id(&mut self, animation_id: &str) -> Result<(), DotLottieError> {
+        if !self.animations.iter().any(|(id, _)| id == animation_id) {
+            return Err(DotLottieError::AnimationNotFound {
+                animation_id: animation_id.to_string(),
+            });
+        }
+
+        self.manifest.active_animation_id = Some(animation_id.to_string());
+
+        Ok(())
+    }
+
+    pub fn set_author(&mut self, author: Option<String>) {
+        self.manifest.author = author;
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.manifest.description = description;
+    }
+
+    pub fn set_generator(&mut self, generator: Option<String>) {
+        self.manifest.generator = generator;
+    }
+
+    pub fn set_keywords(&mut self, keywords: Option<String>) {
+        self.manifest.keywords = keywords;
+    }
+
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Writes the archive.
+    ///
+    /// Result<Vec<u8>, DotLottieError>: The bytes of the dotLottie file, or an error
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DotLottieError> {
+        if self.animations.is_empty() {
+            return Err(DotLottieError::AnimationsNotFound);
+        }
+
+        // Themes scoped to animations must point at animations that are part of the archive
+        if let Some(themes) = &self.manifest.themes {
+            for theme in themes {
+                if let Some(animation_id) = theme
+                    .animations
+                    .iter()
+                    .find(|animation_id| !self.animations.iter().any(|(id, _)| id == *animation_id))
+                {
+                    return Err(DotLottieError::AnimationNotFound {
+                        animation_id: animation_id.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut writer = ZipWriter::new(io::Cursor::new(Vec::new()));
+
+        write_file(
+            &mut writer,
+            "manifest.json",
+            self.manifest.to_string().as_bytes(),
+        )?;
+
+        for (id, data) in &self.animations {
+            write_file(
+                &mut writer,
+                &format!("animations/{}.json", id),
+                data.as_bytes(),
+            )?;
+        }
+
+        for (file_name, data) in &self.images {
+            write_file(&mut writer, &format!("images/{}", file_name), data)?;
+        }
+
+        for (id, data) in &self.themes {
+            write_file(&mut writer, &format!("themes/{}.json", id), data.as_bytes())?;
+        }
+
+        for (id, data) in &self.state_machines {
+            write_file(&mut writer, &format!("states/{}.json", id), data.as_bytes())?;
+        }
+
+        writer
+            .finish()
+            .map(|cursor| cursor.into_inner())
+            .map_err(|_| DotLottieError::ArchiveWriteError)
+    }
+}
+
+fn write_file(
+    writer: &mut ZipWriter<io::Cursor<Vec<u8>>>,
+    file_name: &str,
+    content: &[u8],
+) -> Result<(), DotLottieError> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    writer
+        .start_file(file_name, options)
+        .map_err(|_| DotLottieError::ArchiveWriteError)?;
+
+    writer
+        .write_all(content)
+        .map_err(|_| DotLottieError::ArchiveWriteError)
+}
+
+// Ids are used as file names inside the archive, so they can't escape their folder
+fn validate_id(id: &str) -> Result<(), DotLottieError> {
+    if id.is_empty() || id.contains('/') || id.contains('\\') || id == "." || id == ".." {
+        return Err(DotLottieError::UnsupportedAssetPath {
+            path: id.to_string(),
+        });
+    }
+
+    Ok(())
+}
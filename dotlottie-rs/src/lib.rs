@@ -1,16 +1,28 @@
+mod clock;
 mod dotlottie_player;
+#[cfg(feature = "export")]
+mod exporter;
 mod fms;
 mod layout;
 mod lottie_renderer;
 mod markers;
 mod state_machine;
 mod thorvg;
+mod transition;
 
+pub use clock::*;
 pub use dotlottie_player::*;
+#[cfg(feature = "export")]
+pub use exporter::*;
 pub use fms::*;
 pub use layout::*;
 pub use lottie_renderer::*;
 pub use markers::*;
+pub use state_machine::actions::*;
+pub use state_machine::builder::*;
+pub use state_machine::diagnostics::*;
 pub use state_machine::events::*;
+pub use state_machine::introspection::*;
 pub use state_machine::*;
 pub use thorvg::*;
+pub use transition::*;
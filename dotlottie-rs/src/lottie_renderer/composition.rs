@@ -0,0 +1,731 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use super::{get_color_space_for_target, hex_to_rgba};
+use crate::{
+    Animation, Canvas, Clock, DotLottieError, DotLottieManager, ManualClock, Mode, PlaybackState,
+    Shape, SystemClock, TvgColorspace, TvgEngine, TvgError,
+};
+
+#[derive(Error, Debug, Clone)]
+pub enum CompositionError {
+    #[error("Thorvg error: {0}")]
+    ThorvgError(#[from] TvgError),
+
+    #[error("DotLottie error: {0}")]
+    DotLottieError(#[from] DotLottieError),
+
+    #[error("No dotLottie file loaded")]
+    NoDotLottieLoaded,
+
+    #[error("Item not found: {0}")]
+    ItemNotFound(u32),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+}
+
+/// Where an item is drawn on the canvas.
+///
+/// The animation is scaled from its own size, rotated around its top left corner and then
+/// moved to x, y in canvas pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub x: f32,
+    pub y: f32,
+    pub scale: f32,
+    /// Rotation in degrees, clockwise.
+    pub rotation: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform {
+            x: 0.0,
+            y: 0.0,
+            scale: 1.0,
+            rotation: 0.0,
+        }
+    }
+}
+
+/// How an item is placed and played in a composition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemConfig {
+    pub transform: Transform,
+    /// From 0 (transparent) to 1 (opaque).
+    pub opacity: f32,
+    /// Items with a higher z-index are drawn over the ones with a lower z-index, items with the
+    /// same z-index are drawn in the order they were added.
+    pub z_index: i32,
+    pub mode: Mode,
+    pub loop_animation: bool,
+    pub speed: f32,
+    pub use_frame_interpolation: bool,
+    pub autoplay: bool,
+    pub segment: Vec<f32>,
+}
+
+impl Default for ItemConfig {
+    fn default() -> Self {
+        ItemConfig {
+            transform: Transform::default(),
+            opacity: 1.0,
+            z_index: 0,
+            mode: Mode::Forward,
+            loop_animation: false,
+            speed: 1.0,
+            use_frame_interpolation: true,
+            autoplay: true,
+            segment: vec![],
+        }
+    }
+}
+
+// The frames and timing of one item, independent of the clock
+#[derive(Debug, Clone, PartialEq)]
+struct Timeline {
+    start_frame: f32,
+    end_frame: f32,
+    // seconds to play from start_frame to end_frame once
+    duration: f64,
+    mode: Mode,
+    loop_animation: bool,
+    use_frame_interpolation: bool,
+}
+
+// The state of a timeline at a point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TimelinePosition {
+    frame: f32,
+    loop_count: u32,
+    complete: bool,
+}
+
+impl Timeline {
+    fn new(total_frames: f32, duration: f32, config: &ItemConfig) -> Self {
+        // the last frame is total_frames - 1, as in the player
+        let last_frame = (total_frames - 1.0).max(0.0);
+
+        let (start_frame, end_frame) = match config.segment.as_slice() {
+            [start, end] => (start.clamp(0.0, last_frame), end.clamp(0.0, last_frame)),
+            _ => (0.0, last_frame),
+        };
+
+        let duration = if last_frame > 0.0 && config.speed > 0.0 {
+            (duration * (end_frame - start_frame) / last_frame / config.speed) as f64
+        } else {
+            0.0
+        };
+
+        Timeline {
+            start_frame,
+            end_frame,
+            duration,
+            mode: config.mode,
+            loop_animation: config.loop_animation,
+            use_frame_interpolation: config.use_frame_interpolation,
+        }
+    }
+
+    fn first_frame(&self) -> f32 {
+        match self.mode {
+            Mode::Forward | Mode::Bounce => self.start_frame,
+            Mode::Reverse | Mode::ReverseBounce => self.end_frame,
+        }
+    }
+
+    fn position(&self, elapsed: f64) -> TimelinePosition {
+        let cycle = match self.mode {
+            Mode::Forward | Mode::Reverse => self.duration,
+            Mode::Bounce | Mode::ReverseBounce => self.duration * 2.0,
+        };
+
+        if !cycle.is_finite() || cycle <= 0.0 || elapsed <= 0.0 {
+            return TimelinePosition {
+                frame: self.first_frame(),
+                loop_count: 0,
+                complete: false,
+            };
+        }
+
+        let progress = elapsed / cycle;
+
+        let (loop_count, phase, complete) = if progress < 1.0 {
+            (0, progress, false)
+        } else if self.loop_animation {
+            (progress.floor() as u32, progress.fract(), false)
+        } else {
+            (0, 1.0, true)
+        };
+
+        let span = self.end_frame - self.start_frame;
+        let phase = phase as f32;
+
+        let frame = match self.mode {
+            Mode::Forward => self.start_frame + span * phase,
+            Mode::Reverse => self.end_frame - span * phase,
+            Mode::Bounce => self.end_frame - span * (1.0 - 2.0 * phase).abs(),
+            Mode::ReverseBounce => self.start_frame + span * (1.0 - 2.0 * phase).abs(),
+        };
+
+        let frame = if self.use_frame_interpolation {
+            (frame * 1000.0).round() / 1000.0
+        } else {
+            frame.round()
+        };
+
+        TimelinePosition {
+            frame: frame.clamp(self.start_frame, self.end_frame),
+            loop_count,
+            complete,
+        }
+    }
+}
+
+struct CompositionItem {
+    id: u32,
+    animation: Animation,
+    picture_width: f32,
+    picture_height: f32,
+    config: ItemConfig,
+    timeline: Timeline,
+    playback_state: PlaybackState,
+    // composition time at which the playback started, in seconds
+    start_time: f64,
+    // playback time reached when paused or stopped, in seconds
+    elapsed: f64,
+    position: TimelinePosition,
+}
+
+impl CompositionItem {
+    fn elapsed(&self, now: f64) -> f64 {
+        match self.playback_state {
+            PlaybackState::Playing => now - self.start_time,
+            PlaybackState::Paused | PlaybackState::Stopped => self.elapsed,
+        }
+    }
+
+    fn apply_transform(&mut self) -> Result<(), TvgError> {
+        let transform = self.config.transform;
+
+        self.animation.set_size(
+            self.picture_width * transform.scale,
+            self.picture_height * transform.scale,
+        )?;
+        self.animation.rotate(transform.rotation)?;
+        self.animation.translate(transform.x, transform.y)?;
+
+        let opacity = (self.config.opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+        self.animation.set_opacity(opacity)
+    }
+}
+
+/// Draws several animations on one canvas and one pixel buffer.
+///
+/// Each item has its own transform, opacity, z-index and playback, all driven by the clock of
+/// the composition. Items are animations of a dotLottie file or Lottie JSON data.
+pub struct Composition {
+    items: Vec<CompositionItem>,
+    thorvg_background_shape: Shape,
+    thorvg_canvas: Canvas,
+    buffer: Vec<u32>,
+    width: u32,
+    height: u32,
+    background_color: u32,
+    dotlottie_manager: Option<DotLottieManager>,
+    clock: Arc<dyn Clock>,
+    manual_clock: Option<ManualClock>,
+    next_id: u32,
+    needs_restack: bool,
+}
+
+impl Composition {
+    pub fn new(width: u32, height: u32) -> Result<Self, CompositionError> {
+        if width == 0 || height == 0 {
+            return Err(CompositionError::InvalidArgument(
+                "Width and height must be greater than 0".to_string(),
+            ));
+        }
+
+        let mut composition = Composition {
+            items: vec![],
+            thorvg_background_shape: Shape::new(),
+            thorvg_canvas: Canvas::new(TvgEngine::TvgEngineSw, 0),
+            buffer: vec![],
+            width: 0,
+            height: 0,
+            background_color: 0,
+            dotlottie_manager: None,
+            clock: Arc::new(SystemClock::new()),
+            manual_clock: None,
+            next_id: 1,
+            needs_restack: true,
+        };
+
+        composition.resize(width, height)?;
+
+        Ok(composition)
+    }
+
+    /// Load the dotLottie file whose animations are added with `add_animation`.
+    pub fn load_dotlottie_data(&mut self, file_data: &[u8]) -> Result<(), CompositionError> {
+        self.dotlottie_manager = Some(DotLottieManager::new(Some(file_data.to_vec()))?);
+
+        Ok(())
+    }
+
+    /// Add an animation of the loaded dotLottie file and return the id of the new item.
+    pub fn add_animation(
+        &mut self,
+        animation_id: &str,
+        config: ItemConfig,
+    ) -> Result<u32, CompositionError> {
+        let manager = self
+            .dotlottie_manager
+            .as_mut()
+            .ok_or(CompositionError::NoDotLottieLoaded)?;
+
+        let animation_data = manager.get_animation(animation_id)?;
+
+        self.add_item(&animation_data, config)
+    }
+
+    /// Add an animation from Lottie JSON data and return the id of the new item.
+    pub fn add_animation_data(
+        &mut self,
+        animation_data: &str,
+        config: ItemConfig,
+    ) -> Result<u32, CompositionError> {
+        self.add_item(animation_data, config)
+    }
+
+    fn add_item(
+        &mut self,
+        animation_data: &str,
+        config: ItemConfig,
+    ) -> Result<u32, CompositionError> {
+        let mut animation = Animation::new();
+        animation.load_data(animation_data, "lottie", true)?;
+
+        let (picture_width, picture_height) = animation.get_size()?;
+        let timeline = Timeline::new(
+            animation.get_total_frame()?,
+            animation.get_duration()?,
+            &config,
+        );
+
+        let playback_state = if config.autoplay {
+            PlaybackState::Playing
+        } else {
+            PlaybackState::Stopped
+        };
+
+        let mut item = CompositionItem {
+            id: self.next_id,
+            animation,
+            picture_width,
+            picture_height,
+            config,
+            position: timeline.position(0.0),
+            timeline,
+            playback_state,
+            start_time: self.now(),
+            elapsed: 0.0,
+        };
+
+        item.apply_transform()?;
+        item.animation.set_frame(item.position.frame)?;
+
+        self.next_id += 1;
+        self.items.push(item);
+        self.needs_restack = true;
+
+        Ok(self.next_id - 1)
+    }
+
+    pub fn remove_item(&mut self, id: u32) -> Result<(), CompositionError> {
+        let index = self
+            .items
+            .iter()
+            .position(|item| item.id == id)
+            .ok_or(CompositionError::ItemNotFound(id))?;
+
+        // take the animation off the canvas before it is freed
+        self.thorvg_canvas.sync()?;
+        self.thorvg_canvas.clear(false)?;
+        self.needs_restack = true;
+
+        self.items.remove(index);
+
+        Ok(())
+    }
+
+    /// The ids of the items, from the bottom to the top of the composition.
+    pub fn items(&self) -> Vec<u32> {
+        let mut items: Vec<&CompositionItem> = self.items.iter().collect();
+        items.sort_by_key(|item| item.config.z_index);
+
+        items.iter().map(|item| item.id).collect()
+    }
+
+    /// The size of the item's animation before its transform is applied.
+    pub fn item_size(&self, id: u32) -> Result<(f32, f32), CompositionError> {
+        let item = self.item(id)?;
+
+        Ok((item.picture_width, item.picture_height))
+    }
+
+    pub fn item_config(&self, id: u32) -> Result<ItemConfig, CompositionError> {
+        Ok(self.item(id)?.config.clone())
+    }
+
+    pub fn set_transform(&mut self, id: u32, transform: Transform) -> Result<(), CompositionError> {
+        let item = self.item_mut(id)?;
+
+        item.config.transform = transform;
+        item.apply_transform()?;
+
+        Ok(())
+    }
+
+    pub fn set_opacity(&mut self, id: u32, opacity: f32) -> Result<(), CompositionError> {
+        let item = self.item_mut(id)?;
+
+        item.config.opacity = opacity;
+        item.apply_transform()?;
+
+        Ok(())
+    }
+
+    pub fn set_z_index(&mut self, id: u32, z_index: i32) -> Result<(), CompositionError> {
+        self.item_mut(id)?.config.z_index = z_index;
+        self.needs_restack = true;
+
+        Ok(())
+    }
+
+    /// Start or resume the playback of an item, a completed item restarts from its first frame.
+    pub fn play(&mut self, id: u32) -> Result<(), CompositionError> {
+        let now = self.now();
+        let item = self.item_mut(id)?;
+
+        if item.playback_state == PlaybackState::Playing {
+            return Ok(());
+        }
+
+        if item.position.complete {
+            item.elapsed = 0.0;
+        }
+
+        item.start_time = now - item.elapsed;
+        item.playback_state = PlaybackState::Playing;
+
+        Ok(())
+    }
+
+    pub fn pause(&mut self, id: u32) -> Result<(), CompositionError> {
+        let now = self.now();
+        let item = self.item_mut(id)?;
+
+        if item.playback_state == PlaybackState::Playing {
+            item.elapsed = item.elapsed(now);
+            item.playback_state = PlaybackState::Paused;
+        }
+
+        Ok(())
+    }
+
+    /// Stop the playback of an item and go back to its first frame.
+    pub fn stop(&mut self, id: u32) -> Result<(), CompositionError> {
+        let item = self.item_mut(id)?;
+
+        item.elapsed = 0.0;
+        item.playback_state = PlaybackState::Stopped;
+        item.position = item.timeline.position(0.0);
+        item.animation.set_frame(item.position.frame)?;
+
+        Ok(())
+    }
+
+    pub fn is_playing(&self, id: u32) -> Result<bool, CompositionError> {
+        let item = self.item(id)?;
+
+        Ok(item.playback_state == PlaybackState::Playing && !item.position.complete)
+    }
+
+    pub fn is_complete(&self, id: u32) -> Result<bool, CompositionError> {
+        Ok(self.item(id)?.position.complete)
+    }
+
+    pub fn current_frame(&self, id: u32) -> Result<f32, CompositionError> {
+        Ok(self.item(id)?.position.frame)
+    }
+
+    pub fn loop_count(&self, id: u32) -> Result<u32, CompositionError> {
+        Ok(self.item(id)?.position.loop_count)
+    }
+
+    fn now(&self) -> f64 {
+        self.clock.now().as_secs_f64()
+    }
+
+    /// Replace the time source of the composition, the items keep their current frame.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        let now = self.now();
+
+        for item in self.items.iter_mut() {
+            item.elapsed = item.elapsed(now);
+        }
+
+        self.clock = clock;
+        self.manual_clock = None;
+
+        let now = self.now();
+
+        for item in self.items.iter_mut() {
+            item.start_time = now - item.elapsed;
+        }
+    }
+
+    // The clock driven by tick and update_at, installed on first use
+    fn manual_clock(&mut self, initial_time: Duration) -> ManualClock {
+        match &self.manual_clock {
+            Some(clock) => clock.clone(),
+            None => {
+                let clock = ManualClock::new(initial_time);
+
+                self.set_clock(Arc::new(clock.clone()));
+                self.manual_clock = Some(clock.clone());
+
+                clock
+            }
+        }
+    }
+
+    /// Move every playing item to its frame at the current time of the clock.
+    ///
+    /// Returns true if any frame changed and the composition needs to be rendered again.
+    pub fn update(&mut self) -> Result<bool, CompositionError> {
+        let now = self.now();
+        let mut changed = false;
+
+        for item in self.items.iter_mut() {
+            if item.playback_state != PlaybackState::Playing || item.position.complete {
+                continue;
+            }
+
+            let position = item.timeline.position(item.elapsed(now));
+
+            if position.complete {
+                item.elapsed = item.timeline.duration;
+            }
+
+            if position.frame != item.position.frame {
+                item.animation.set_frame(position.frame)?;
+                changed = true;
+            }
+
+            item.position = position;
+        }
+
+        Ok(changed)
+    }
+
+    /// Advance the time of the composition by delta and update the items.
+    pub fn tick(&mut self, delta: Duration) -> Result<bool, CompositionError> {
+        self.manual_clock(Duration::ZERO).advance(delta);
+
+        self.update()
+    }
+
+    /// Update the items to the given time of the host clock.
+    pub fn update_at(&mut self, time: Duration) -> Result<bool, CompositionError> {
+        self.manual_clock(time).set_time(time);
+
+        self.update()
+    }
+
+    pub fn render(&mut self) -> Result<(), CompositionError> {
+        if self.needs_restack {
+            self.restack()?;
+        }
+
+        self.thorvg_canvas.update()?;
+        self.thorvg_canvas.draw()?;
+        self.thorvg_canvas.sync()?;
+
+        Ok(())
+    }
+
+    // Push the background and the items in z-order, the canvas draws them in push order
+    fn restack(&mut self) -> Result<(), CompositionError> {
+        self.thorvg_canvas.clear(false)?;
+        self.thorvg_canvas.push(&self.thorvg_background_shape)?;
+
+        // stable, items with the same z-index stay in insertion order
+        self.items.sort_by_key(|item| item.config.z_index);
+
+        for item in self.items.iter() {
+            self.thorvg_canvas.push(&item.animation)?;
+        }
+
+        self.needs_restack = false;
+
+        Ok(())
+    }
+
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), CompositionError> {
+        if (width, height) == (self.width, self.height) {
+            return Ok(());
+        }
+
+        if width == 0 || height == 0 {
+            return Err(CompositionError::InvalidArgument(
+                "Width and height must be greater than 0".to_string(),
+            ));
+        }
+
+        let _ = self.thorvg_canvas.sync();
+
+        self.width = width;
+        self.height = height;
+
+        self.buffer.resize((width * height) as usize, 0);
+        self.thorvg_canvas.set_target(
+            &mut self.buffer,
+            width,
+            width,
+            height,
+            get_color_space_for_target(),
+        )?;
+
+        self.thorvg_background_shape.reset()?;
+        self.thorvg_background_shape.append_rect(
+            0.0,
+            0.0,
+            width as f32,
+            height as f32,
+            0.0,
+            0.0,
+        )?;
+
+        self.set_background_color(self.background_color)
+    }
+
+    pub fn set_background_color(&mut self, hex_color: u32) -> Result<(), CompositionError> {
+        self.background_color = hex_color;
+
+        self.thorvg_background_shape
+            .fill(hex_to_rgba(hex_color))
+            .map_err(CompositionError::ThorvgError)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn buffer(&self) -> &[u32] {
+        &self.buffer
+    }
+
+    /// The color space the composition is rendered in.
+    pub fn color_space(&self) -> TvgColorspace {
+        get_color_space_for_target()
+    }
+
+    fn item(&self, id: u32) -> Result<&CompositionItem, CompositionError> {
+        self.items
+            .iter()
+            .find(|item| item.id == id)
+            .ok_or(CompositionError::ItemNotFound(id))
+    }
+
+    fn item_mut(&mut self, id: u32) -> Result<&mut CompositionItem, CompositionError> {
+        self.items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .ok_or(CompositionError::ItemNotFound(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 61 frames played in 2 seconds
+    fn timeline(config: ItemConfig) -> Timeline {
+        Timeline::new(61.0, 2.0, &config)
+    }
+
+    #[test]
+    fn test_timeline_forward() {
+        let timeline = timeline(ItemConfig::default());
+
+        assert_eq!(timeline.position(0.0).frame, 0.0);
+        assert_eq!(timeline.position(1.0).frame, 30.0);
+
+        let end = timeline.position(3.0);
+        assert_eq!(end.frame, 60.0);
+        assert!(end.complete);
+    }
+
+    #[test]
+    fn test_timeline_loop() {
+        let timeline = timeline(ItemConfig {
+            loop_animation: true,
+            ..ItemConfig::default()
+        });
+
+        let position = timeline.position(5.0);
+
+        assert_eq!(position.frame, 30.0);
+        assert_eq!(position.loop_count, 2);
+        assert!(!position.complete);
+    }
+
+    #[test]
+    fn test_timeline_modes() {
+        let reverse = timeline(ItemConfig {
+            mode: Mode::Reverse,
+            ..ItemConfig::default()
+        });
+
+        assert_eq!(reverse.position(0.0).frame, 60.0);
+        assert_eq!(reverse.position(0.5).frame, 45.0);
+        assert_eq!(reverse.position(4.0).frame, 0.0);
+
+        let bounce = timeline(ItemConfig {
+            mode: Mode::Bounce,
+            ..ItemConfig::default()
+        });
+
+        assert_eq!(bounce.position(1.0).frame, 30.0);
+        assert_eq!(bounce.position(2.0).frame, 60.0);
+        assert_eq!(bounce.position(3.0).frame, 30.0);
+        assert_eq!(bounce.position(5.0).frame, 0.0);
+        assert!(bounce.position(5.0).complete);
+    }
+
+    #[test]
+    fn test_timeline_segment_and_speed() {
+        let timeline = timeline(ItemConfig {
+            segment: vec![10.0, 40.0],
+            speed: 2.0,
+            use_frame_interpolation: false,
+            ..ItemConfig::default()
+        });
+
+        // 30 frames of 1/30 second at twice the speed
+        assert_eq!(timeline.duration, 0.5);
+        assert_eq!(timeline.position(0.0).frame, 10.0);
+        assert_eq!(timeline.position(0.26).frame, 26.0);
+        assert_eq!(timeline.position(1.0).frame, 40.0);
+    }
+}
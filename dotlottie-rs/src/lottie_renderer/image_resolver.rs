@@ -0,0 +1,172 @@
+use std::{borrow::Cow, collections::HashMap, ops::Range};
+
+use base64::{engine::general_purpose, Engine};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+/// Provides the bytes of the image assets referenced by a Lottie animation.
+///
+/// Animations keep referencing their images by path, the bytes are only looked up
+/// when the animation is handed to the renderer, which embeds them for ThorVG.
+pub trait ImageResolver {
+    /// Returns the bytes of an image asset, or None to leave the asset as it is.
+    ///
+    /// asset_id: The "id" of the asset in the Lottie JSON
+    /// path: The "p" of the asset, usually the file name of the image
+    fn resolve(&self, asset_id: &str, path: &str) -> Option<Cow<'_, [u8]>>;
+}
+
+/// Images keyed by their path, as stored in the "p" property of the assets.
+impl ImageResolver for HashMap<String, Vec<u8>> {
+    fn resolve(&self, _asset_id: &str, path: &str) -> Option<Cow<'_, [u8]>> {
+        self.get(path).map(|bytes| Cow::Borrowed(bytes.as_slice()))
+    }
+}
+
+#[derive(Deserialize)]
+struct LottieAssets<'a> {
+    #[serde(borrow, default)]
+    assets: Vec<&'a RawValue>,
+}
+
+#[derive(Deserialize)]
+struct ImageAsset {
+    id: Option<String>,
+    p: Option<String>,
+}
+
+/// Embeds the resolved images into the animation data.
+///
+/// ThorVG only reads image assets from the Lottie JSON itself, so the resolved bytes
+/// are written as base64 data URLs in place of the image assets, taking a third more
+/// memory than the images. Everything outside of those
+/// assets is copied as is, the layers are neither parsed nor re-serialized.
+/// Animations without resolvable images are returned untouched.
+pub(crate) fn embed_images<'a>(data: &'a str, resolver: &dyn ImageResolver) -> Cow<'a, str> {
+    let lottie = match serde_json::from_str::<LottieAssets>(data) {
+        Ok(lottie) => lottie,
+        Err(_) => return Cow::Borrowed(data),
+    };
+
+    let mut replacements: Vec<(Range<usize>, String)> = vec![];
+
+    for raw_asset in lottie.assets {
+        let asset: ImageAsset = match serde_json::from_str(raw_asset.get()) {
+            Ok(asset) => asset,
+            Err(_) => continue,
+        };
+
+        let path = match asset.p {
+            Some(path) if !path.starts_with("data:") => path,
+            _ => continue,
+        };
+
+        let image = match resolver.resolve(asset.id.as_deref().unwrap_or_default(), &path) {
+            Some(image) => image,
+            None => continue,
+        };
+
+        let mut asset_json = match jzon::parse(raw_asset.get()) {
+            Ok(asset_json) => asset_json,
+            Err(_) => continue,
+        };
+
+        let image_ext = path.rsplit('.').next().unwrap_or_default();
+
+        asset_json["u"] = "".into();
+        asset_json["p"] = format!(
+            "data:image/{};base64,{}",
+            image_ext,
+            general_purpose::STANDARD.encode(&image)
+        )
+        .into();
+        // explicitly indicate that the image asset is inlined
+        asset_json["e"] = 1.into();
+
+        // The raw value borrows from data, its position gives the range to replace
+        let start = raw_asset.get().as_ptr() as usize - data.as_ptr() as usize;
+
+        replacements.push((
+            start..start + raw_asset.get().len(),
+            jzon::stringify(asset_json),
+        ));
+    }
+
+    if replacements.is_empty() {
+        return Cow::Borrowed(data);
+    }
+
+    let replaced_len: usize = replacements.iter().map(|(range, _)| range.len()).sum();
+    let inserted_len: usize = replacements.iter().map(|(_, asset)| asset.len()).sum();
+
+    let mut embedded = String::with_capacity(data.len() - replaced_len + inserted_len);
+    let mut position = 0;
+
+    for (range, asset) in replacements {
+        embedded.push_str(&data[position..range.start]);
+        embedded.push_str(&asset);
+        position = range.end;
+    }
+
+    embedded.push_str(&data[position..]);
+
+    Cow::Owned(embedded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANIMATION: &str = r#"{"v":"5.5.2","w":10,"h":10,"assets":[{"id":"image_0","w":1,"h":1,"u":"/images/","p":"img_0.png","e":0},{"id":"comp_0","layers":[{"ty":2,"refId":"image_0"}]}],"layers":[{"ty":0,"refId":"comp_0","nm":"unchanged"}]}"#;
+
+    fn images() -> HashMap<String, Vec<u8>> {
+        HashMap::from([("img_0.png".to_string(), vec![1, 2, 3])])
+    }
+
+    #[test]
+    fn test_embed_images() {
+        let embedded = embed_images(ANIMATION, &images());
+        let lottie = jzon::parse(&embedded).unwrap();
+
+        // [1, 2, 3] encoded in base64
+        assert_eq!(
+            lottie["assets"][0]["p"].as_str(),
+            Some("data:image/png;base64,AQID")
+        );
+        assert_eq!(lottie["assets"][0]["u"].as_str(), Some(""));
+        assert_eq!(lottie["assets"][0]["e"].as_i64(), Some(1));
+        assert_eq!(lottie["assets"][0]["id"].as_str(), Some("image_0"));
+
+        // Precompositions and layers are copied verbatim
+        assert!(embedded.ends_with(
+            r#"{"id":"comp_0","layers":[{"ty":2,"refId":"image_0"}]}],"layers":[{"ty":0,"refId":"comp_0","nm":"unchanged"}]}"#
+        ));
+    }
+
+    #[test]
+    fn test_embed_images_without_resolved_images() {
+        let embedded = embed_images(ANIMATION, &HashMap::new());
+
+        assert!(matches!(embedded, Cow::Borrowed(data) if data == ANIMATION));
+    }
+
+    #[test]
+    fn test_embed_images_keeps_inlined_images() {
+        let animation = r#"{"assets":[{"id":"image_0","p":"data:image/png;base64,AQID","e":1}]}"#;
+        let images = HashMap::from([("data:image/png;base64,AQID".to_string(), vec![4, 5, 6])]);
+
+        assert!(matches!(embed_images(animation, &images), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_embed_images_invalid_data() {
+        assert!(matches!(
+            embed_images("{ not json", &images()),
+            Cow::Borrowed(_)
+        ));
+        assert!(matches!(
+            embed_images(r#"{"layers":[]}"#, &images()),
+            Cow::Borrowed(_)
+        ));
+    }
+}
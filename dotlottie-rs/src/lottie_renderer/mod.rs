@@ -1,8 +1,16 @@
 use thiserror::Error;
 
-use crate::{Animation, Canvas, Layout, Shape, TvgColorspace, TvgEngine, TvgError};
+use crate::{
+    Animation, Canvas, Layout, Shape, TransitionEffect, TvgColorspace, TvgEngine, TvgError,
+};
 
-#[derive(Error, Debug)]
+mod composition;
+mod image_resolver;
+
+pub use composition::*;
+pub use image_resolver::*;
+
+#[derive(Error, Debug, Clone)]
 pub enum LottieRendererError {
     #[error("Thorvg error: {0}")]
     ThorvgError(#[from] TvgError),
@@ -26,6 +34,10 @@ pub struct LottieRenderer {
     pub background_color: u32,
     pub current_frame: f32,
     layout: Layout,
+    // the animation being replaced during a transition, drawn under the current one
+    outgoing_animation: Option<Animation>,
+    outgoing_shift: (f32, f32),
+    keep_outgoing_animation: bool,
 }
 
 impl Default for LottieRenderer {
@@ -52,6 +64,9 @@ impl LottieRenderer {
             background_color: 0,
             current_frame: 0.0,
             layout: Layout::default(),
+            outgoing_animation: None,
+            outgoing_shift: (0.0, 0.0),
+            keep_outgoing_animation: false,
         }
     }
 
@@ -62,7 +77,52 @@ impl LottieRenderer {
         height: u32,
         copy: bool,
     ) -> Result<(), LottieRendererError> {
-        self.thorvg_canvas.clear(true)?;
+        self.load(
+            |animation| animation.load_data(data, "lottie", copy),
+            width,
+            height,
+        )
+    }
+
+    /// Loads an animation whose image assets are provided by the resolver.
+    ///
+    /// The resolved images are embedded as data URLs in a copy of the data handed to ThorVG,
+    /// the caller keeps the animation data and the images as they are.
+    pub fn load_data_with_images(
+        &mut self,
+        data: &str,
+        images: &dyn ImageResolver,
+        width: u32,
+        height: u32,
+        copy: bool,
+    ) -> Result<(), LottieRendererError> {
+        let data = embed_images(data, images);
+
+        self.load(
+            |animation| animation.load_data(&data, "lottie", copy),
+            width,
+            height,
+        )
+    }
+
+    fn load<F>(&mut self, loader: F, width: u32, height: u32) -> Result<(), LottieRendererError>
+    where
+        F: FnOnce(&mut Animation) -> Result<(), TvgError>,
+    {
+        let keep_outgoing_animation =
+            std::mem::take(&mut self.keep_outgoing_animation) && self.picture_width > 0.0;
+
+        if keep_outgoing_animation {
+            // take the paints off the canvas without freeing them, the current animation
+            // stays on screen as the outgoing one
+            self.thorvg_canvas.clear(false)?;
+            self.outgoing_animation = None;
+
+            let (_, _, shift_x, shift_y) = self.layout_transform();
+            self.outgoing_shift = (shift_x, shift_y);
+        } else {
+            self.thorvg_canvas.clear(true)?;
+        }
 
         self.picture_width = 0.0;
         self.picture_height = 0.0;
@@ -82,10 +142,17 @@ impl LottieRenderer {
             )
             .map_err(LottieRendererError::ThorvgError)?;
 
-        self.thorvg_animation = Animation::new();
-        self.thorvg_background_shape = Shape::new();
+        let previous_animation = std::mem::take(&mut self.thorvg_animation);
+
+        if keep_outgoing_animation {
+            self.outgoing_animation = Some(previous_animation);
+            self.thorvg_background_shape.reset()?;
+        } else {
+            self.outgoing_animation = None;
+            self.thorvg_background_shape = Shape::new();
+        }
 
-        self.thorvg_animation.load_data(data, "lottie", copy)?;
+        loader(&mut self.thorvg_animation)?;
 
         let (pw, ph) = self.thorvg_animation.get_size()?;
         self.picture_width = pw;
@@ -115,6 +182,110 @@ impl LottieRenderer {
         self.thorvg_background_shape
             .fill((red, green, blue, alpha))?;
 
+        self.thorvg_canvas.push(&self.thorvg_background_shape)?;
+        if let Some(outgoing_animation) = &self.outgoing_animation {
+            self.thorvg_canvas.push(outgoing_animation)?;
+        }
+        self.thorvg_canvas.push(&self.thorvg_animation)?;
+
+        Ok(())
+    }
+
+    fn layout_transform(&self) -> (f32, f32, f32, f32) {
+        self.layout.compute_layout_transform(
+            self.width as f32,
+            self.height as f32,
+            self.picture_width,
+            self.picture_height,
+        )
+    }
+
+    /// Keep the current animation on screen when the next one is loaded, until `end_transition`
+    /// is called. `update_transition` blends the two animations in between.
+    pub fn begin_transition(&mut self) {
+        self.keep_outgoing_animation = true;
+    }
+
+    pub fn is_transitioning(&self) -> bool {
+        self.outgoing_animation.is_some()
+    }
+
+    /// Blend the outgoing and the current animation, progress goes from 0 (only the outgoing
+    /// animation is visible) to 1 (only the current one is).
+    pub fn update_transition(
+        &mut self,
+        effect: &TransitionEffect,
+        progress: f32,
+    ) -> Result<(), LottieRendererError> {
+        let (_, _, shift_x, shift_y) = self.layout_transform();
+        let (outgoing_x, outgoing_y) = self.outgoing_shift;
+        let (width, height) = (self.width as f32, self.height as f32);
+
+        let outgoing_animation = match self.outgoing_animation.as_mut() {
+            Some(outgoing_animation) => outgoing_animation,
+            None => return Ok(()),
+        };
+        let progress = progress.clamp(0.0, 1.0);
+
+        match effect {
+            TransitionEffect::Crossfade => {
+                outgoing_animation.set_opacity(((1.0 - progress) * 255.0).round() as u8)?;
+                self.thorvg_animation
+                    .set_opacity((progress * 255.0).round() as u8)?;
+            }
+            TransitionEffect::Slide { direction } => {
+                let (dx, dy) = direction.vector();
+                let (dx, dy) = (dx * width, dy * height);
+
+                outgoing_animation
+                    .translate(outgoing_x + dx * progress, outgoing_y + dy * progress)?;
+                self.thorvg_animation.translate(
+                    shift_x - dx * (1.0 - progress),
+                    shift_y - dy * (1.0 - progress),
+                )?;
+            }
+            TransitionEffect::Wipe { direction } => {
+                let (x, y, w, h) = match direction.vector() {
+                    (dx, _) if dx < 0.0 => {
+                        (width * (1.0 - progress), 0.0, width * progress, height)
+                    }
+                    (dx, _) if dx > 0.0 => (0.0, 0.0, width * progress, height),
+                    (_, dy) if dy < 0.0 => {
+                        (0.0, height * (1.0 - progress), width, height * progress)
+                    }
+                    _ => (0.0, 0.0, width, height * progress),
+                };
+
+                // the clip follows the transform of the animation, it's placed relative to it
+                let mut clip = Shape::new();
+                clip.append_rect(x - shift_x, y - shift_y, w, h, 0.0, 0.0)?;
+                clip.fill((0, 0, 0, 255))?;
+
+                self.thorvg_animation.set_clip(clip)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove the outgoing animation and restore the current one as it's drawn without a transition.
+    pub fn end_transition(&mut self) -> Result<(), LottieRendererError> {
+        self.keep_outgoing_animation = false;
+
+        if self.outgoing_animation.is_none() {
+            return Ok(());
+        }
+
+        let (_, _, shift_x, shift_y) = self.layout_transform();
+
+        self.thorvg_canvas.sync()?;
+        self.thorvg_canvas.clear(false)?;
+        self.outgoing_animation = None;
+
+        self.thorvg_animation.set_opacity(255)?;
+        self.thorvg_animation.translate(shift_x, shift_y)?;
+        self.thorvg_animation.clear_clip()?;
+
         self.thorvg_canvas.push(&self.thorvg_background_shape)?;
         self.thorvg_canvas.push(&self.thorvg_animation)?;
 
@@ -244,6 +415,11 @@ impl LottieRenderer {
         self.buffer.len()
     }
 
+    /// The color space the frames are rendered in.
+    pub fn color_space(&self) -> TvgColorspace {
+        get_color_space_for_target()
+    }
+
     pub fn set_background_color(&mut self, hex_color: u32) -> Result<(), LottieRendererError> {
         self.background_color = hex_color;
 
@@ -288,7 +464,10 @@ impl LottieRenderer {
             .map_err(LottieRendererError::ThorvgError)
     }
 
-    pub fn get_layer_bounds(&self, layer_name: &str) -> Result<(f32, f32, f32, f32), LottieRendererError> {
+    pub fn get_layer_bounds(
+        &self,
+        layer_name: &str,
+    ) -> Result<(f32, f32, f32, f32), LottieRendererError> {
         self.thorvg_animation
             .get_layer_bounds(layer_name)
             .map_err(LottieRendererError::ThorvgError)
@@ -0,0 +1,197 @@
+use crate::parser::{ContextActionJson, StateActionJson, StringNumberBool};
+
+/// An action run when a state is entered or exited.
+///
+/// Theme actions are applied to the player and custom events are passed to the
+/// `on_custom_event` callback of the state machine observers. The other actions are passed to
+/// their `on_action` callback for the host to perform.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateAction {
+    Url {
+        url: String,
+        target: Option<String>,
+    },
+    Theme {
+        theme_id: String,
+        target: Option<String>,
+    },
+    Sound {
+        sound_id: String,
+        target: Option<String>,
+    },
+    Log {
+        message: String,
+    },
+    FireCustomEvent {
+        event: String,
+    },
+}
+
+impl From<StateActionJson> for StateAction {
+    fn from(action: StateActionJson) -> Self {
+        match action {
+            StateActionJson::URLAction { url, target } => StateAction::Url { url, target },
+            StateActionJson::ThemeAction { theme_id, target } => {
+                StateAction::Theme { theme_id, target }
+            }
+            StateActionJson::SoundAction { sound_id, target } => {
+                StateAction::Sound { sound_id, target }
+            }
+            StateActionJson::LogAction { message } => StateAction::Log { message },
+            StateActionJson::FireCustomEvent { event } => StateAction::FireCustomEvent { event },
+        }
+    }
+}
+
+impl From<StateAction> for StateActionJson {
+    fn from(action: StateAction) -> Self {
+        match action {
+            StateAction::Url { url, target } => StateActionJson::URLAction { url, target },
+            StateAction::Theme { theme_id, target } => {
+                StateActionJson::ThemeAction { theme_id, target }
+            }
+            StateAction::Sound { sound_id, target } => {
+                StateActionJson::SoundAction { sound_id, target }
+            }
+            StateAction::Log { message } => StateActionJson::LogAction { message },
+            StateAction::FireCustomEvent { event } => StateActionJson::FireCustomEvent { event },
+        }
+    }
+}
+
+/// A change to a context variable, made when a transition is taken or a listener is triggered.
+///
+/// The type of the value given to `Set` decides which context it is written to. The other
+/// actions only change variables that exist in the context of their type: `Increment`,
+/// `Decrement` and `Clamp` numeric ones, `Toggle` boolean ones and `Append` string ones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextAction {
+    Set {
+        context_key: String,
+        value: StringNumberBool,
+    },
+    Increment {
+        context_key: String,
+        value: f32,
+    },
+    Decrement {
+        context_key: String,
+        value: f32,
+    },
+    Toggle {
+        context_key: String,
+    },
+    Clamp {
+        context_key: String,
+        min: f32,
+        max: f32,
+    },
+    Append {
+        context_key: String,
+        value: String,
+    },
+}
+
+impl From<ContextActionJson> for ContextAction {
+    fn from(action: ContextActionJson) -> Self {
+        match action {
+            ContextActionJson::Set { context_key, value } => {
+                ContextAction::Set { context_key, value }
+            }
+            ContextActionJson::Increment { context_key, value } => ContextAction::Increment {
+                context_key,
+                value: value.unwrap_or(1.0),
+            },
+            ContextActionJson::Decrement { context_key, value } => ContextAction::Decrement {
+                context_key,
+                value: value.unwrap_or(1.0),
+            },
+            ContextActionJson::Toggle { context_key } => ContextAction::Toggle { context_key },
+            ContextActionJson::Clamp {
+                context_key,
+                min,
+                max,
+            } => ContextAction::Clamp {
+                context_key,
+                min,
+                max,
+            },
+            ContextActionJson::Append { context_key, value } => {
+                ContextAction::Append { context_key, value }
+            }
+        }
+    }
+}
+
+impl From<ContextAction> for ContextActionJson {
+    fn from(action: ContextAction) -> Self {
+        match action {
+            ContextAction::Set { context_key, value } => {
+                ContextActionJson::Set { context_key, value }
+            }
+            ContextAction::Increment { context_key, value } => ContextActionJson::Increment {
+                context_key,
+                value: Some(value),
+            },
+            ContextAction::Decrement { context_key, value } => ContextActionJson::Decrement {
+                context_key,
+                value: Some(value),
+            },
+            ContextAction::Toggle { context_key } => ContextActionJson::Toggle { context_key },
+            ContextAction::Clamp {
+                context_key,
+                min,
+                max,
+            } => ContextActionJson::Clamp {
+                context_key,
+                min,
+                max,
+            },
+            ContextAction::Append { context_key, value } => {
+                ContextActionJson::Append { context_key, value }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_action_from_json() {
+        let actions: Vec<ContextActionJson> = serde_json::from_str(
+            r#"[
+                { "type": "Set", "context_key": "rated", "value": true },
+                { "type": "Increment", "context_key": "stars" },
+                { "type": "Decrement", "context_key": "stars", "value": 2 },
+                { "type": "Append", "context_key": "comment", "value": "!" }
+            ]"#,
+        )
+        .unwrap();
+
+        let actions: Vec<ContextAction> = actions.into_iter().map(ContextAction::from).collect();
+
+        assert_eq!(
+            actions,
+            vec![
+                ContextAction::Set {
+                    context_key: "rated".to_string(),
+                    value: StringNumberBool::Bool(true),
+                },
+                ContextAction::Increment {
+                    context_key: "stars".to_string(),
+                    value: 1.0,
+                },
+                ContextAction::Decrement {
+                    context_key: "stars".to_string(),
+                    value: 2.0,
+                },
+                ContextAction::Append {
+                    context_key: "comment".to_string(),
+                    value: "!".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,764 @@
+use std::rc::Rc;
+use std::sync::RwLock;
+
+use crate::errors::StateMachineError;
+use crate::listeners::ListenerType;
+use crate::parser::{
+    AfterEventJson, AnimationTransitionJson, AnimationTransitionJsonType, BooleanEventJson,
+    ContextJson, ContextJsonType, DelayEventJson, DescriptorJson, EasingJson, EveryEventJson,
+    ListenerJson, ListenerJsonType, NumericEventJson, OnBlurEventJson, OnCompleteEventJson,
+    OnDoubleClickEventJson, OnFocusEventJson, OnFrameReachedEventJson, OnKeyDownEventJson,
+    OnKeyUpEventJson, OnLongPressEventJson, OnLoopCompleteEventJson, OnMarkerReachedEventJson,
+    OnPointerDownEventJson, OnPointerEnterEventJson, OnPointerExitEventJson,
+    OnPointerMoveEventJson, OnPointerUpEventJson, OnScrollEventJson, OnSwipeEventJson,
+    StateActionJson, StateJson, StateMachineJson, StateReferenceJson, StringEventJson,
+    StringNumberBool, TransitionDirectionJson, TransitionJson,
+};
+use crate::transitions::guard::Guard;
+use crate::{
+    validate_definition, AnimationTransition, ContextAction, DiagnosticSeverity,
+    DotLottiePlayerContainer, Easing, InternalEvent, Mode, StateAction, StateMachine,
+    TransitionDirection, TransitionEffect, ValidationTarget,
+};
+
+/// A state playing an animation, or a part of it.
+#[derive(Debug, Clone)]
+pub struct PlaybackStateBuilder {
+    name: String,
+    animation_id: Option<String>,
+    r#loop: Option<bool>,
+    autoplay: Option<bool>,
+    mode: Option<Mode>,
+    speed: Option<f32>,
+    marker: Option<String>,
+    segment: Option<Vec<f32>>,
+    background_color: Option<u32>,
+    use_frame_interpolation: Option<bool>,
+    entry_actions: Vec<StateAction>,
+    exit_actions: Vec<StateAction>,
+    reset_context: Option<String>,
+    parent: Option<String>,
+    initial: Option<String>,
+    parallel: Option<bool>,
+}
+
+impl PlaybackStateBuilder {
+    pub fn new(name: &str) -> Self {
+        PlaybackStateBuilder {
+            name: name.to_string(),
+            animation_id: None,
+            r#loop: None,
+            autoplay: None,
+            mode: None,
+            speed: None,
+            marker: None,
+            segment: None,
+            background_color: None,
+            use_frame_interpolation: None,
+            entry_actions: Vec::new(),
+            exit_actions: Vec::new(),
+            reset_context: None,
+            parent: None,
+            initial: None,
+            parallel: None,
+        }
+    }
+
+    pub fn animation_id(mut self, animation_id: &str) -> Self {
+        self.animation_id = Some(animation_id.to_string());
+        self
+    }
+
+    pub fn looping(mut self, r#loop: bool) -> Self {
+        self.r#loop = Some(r#loop);
+        self
+    }
+
+    pub fn autoplay(mut self, autoplay: bool) -> Self {
+        self.autoplay = Some(autoplay);
+        self
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    pub fn marker(mut self, marker: &str) -> Self {
+        self.marker = Some(marker.to_string());
+        self
+    }
+
+    pub fn segment(mut self, start: f32, end: f32) -> Self {
+        self.segment = Some(vec![start, end]);
+        self
+    }
+
+    pub fn background_color(mut self, background_color: u32) -> Self {
+        self.background_color = Some(background_color);
+        self
+    }
+
+    pub fn use_frame_interpolation(mut self, use_frame_interpolation: bool) -> Self {
+        self.use_frame_interpolation = Some(use_frame_interpolation);
+        self
+    }
+
+    pub fn entry_action(mut self, action: StateAction) -> Self {
+        self.entry_actions.push(action);
+        self
+    }
+
+    pub fn exit_action(mut self, action: StateAction) -> Self {
+        self.exit_actions.push(action);
+        self
+    }
+
+    /// Restores the context variable with the given key, or all of them with "*", when the state
+    /// is entered.
+    pub fn reset_context(mut self, key: &str) -> Self {
+        self.reset_context = Some(key.to_string());
+        self
+    }
+
+    /// Nests the state in the state with the given name.
+    pub fn parent(mut self, parent: &str) -> Self {
+        self.parent = Some(parent.to_string());
+        self
+    }
+
+    /// The child state entered along with this state.
+    pub fn initial(mut self, initial: &str) -> Self {
+        self.initial = Some(initial.to_string());
+        self
+    }
+
+    /// Makes the children of the state parallel regions, all active at once.
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = Some(parallel);
+        self
+    }
+}
+
+impl From<PlaybackStateBuilder> for StateJson {
+    fn from(state: PlaybackStateBuilder) -> Self {
+        StateJson::PlaybackState {
+            name: state.name,
+            animation_id: state.animation_id,
+            r#loop: state.r#loop,
+            autoplay: state.autoplay,
+            mode: state.mode.map(|mode| {
+                match mode {
+                    Mode::Forward => "Forward",
+                    Mode::Reverse => "Reverse",
+                    Mode::Bounce => "Bounce",
+                    Mode::ReverseBounce => "ReverseBounce",
+                }
+                .to_string()
+            }),
+            speed: state.speed,
+            marker: state.marker,
+            segment: state.segment,
+            background_color: state.background_color,
+            use_frame_interpolation: state.use_frame_interpolation,
+            entry_actions: state_actions(state.entry_actions),
+            exit_actions: state_actions(state.exit_actions),
+            reset_context: state.reset_context,
+            parent: state.parent.map(StateReferenceJson::Name),
+            initial: state.initial.map(StateReferenceJson::Name),
+            parallel: state.parallel,
+        }
+    }
+}
+
+/// A state showing the frame held by a numeric context variable.
+#[derive(Debug, Clone)]
+pub struct SyncStateBuilder {
+    name: String,
+    frame_context_key: String,
+    animation_id: Option<String>,
+    background_color: Option<u32>,
+    segment: Option<Vec<f32>>,
+    entry_actions: Vec<StateAction>,
+    exit_actions: Vec<StateAction>,
+    reset_context: Option<String>,
+    parent: Option<String>,
+    initial: Option<String>,
+    parallel: Option<bool>,
+}
+
+impl SyncStateBuilder {
+    pub fn new(name: &str, frame_context_key: &str) -> Self {
+        SyncStateBuilder {
+            name: name.to_string(),
+            frame_context_key: frame_context_key.to_string(),
+            animation_id: None,
+            background_color: None,
+            segment: None,
+            entry_actions: Vec::new(),
+            exit_actions: Vec::new(),
+            reset_context: None,
+            parent: None,
+            initial: None,
+            parallel: None,
+        }
+    }
+
+    pub fn animation_id(mut self, animation_id: &str) -> Self {
+        self.animation_id = Some(animation_id.to_string());
+        self
+    }
+
+    pub fn background_color(mut self, background_color: u32) -> Self {
+        self.background_color = Some(background_color);
+        self
+    }
+
+    pub fn segment(mut self, start: f32, end: f32) -> Self {
+        self.segment = Some(vec![start, end]);
+        self
+    }
+
+    pub fn entry_action(mut self, action: StateAction) -> Self {
+        self.entry_actions.push(action);
+        self
+    }
+
+    pub fn exit_action(mut self, action: StateAction) -> Self {
+        self.exit_actions.push(action);
+        self
+    }
+
+    pub fn reset_context(mut self, key: &str) -> Self {
+        self.reset_context = Some(key.to_string());
+        self
+    }
+
+    pub fn parent(mut self, parent: &str) -> Self {
+        self.parent = Some(parent.to_string());
+        self
+    }
+
+    pub fn initial(mut self, initial: &str) -> Self {
+        self.initial = Some(initial.to_string());
+        self
+    }
+
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = Some(parallel);
+        self
+    }
+}
+
+impl From<SyncStateBuilder> for StateJson {
+    fn from(state: SyncStateBuilder) -> Self {
+        StateJson::SyncState {
+            name: state.name,
+            frame_context_key: state.frame_context_key,
+            animation_id: state.animation_id,
+            background_color: state.background_color,
+            segment: state.segment,
+            entry_actions: state_actions(state.entry_actions),
+            exit_actions: state_actions(state.exit_actions),
+            reset_context: state.reset_context,
+            parent: state.parent.map(StateReferenceJson::Name),
+            initial: state.initial.map(StateReferenceJson::Name),
+            parallel: state.parallel,
+        }
+    }
+}
+
+/// The state whose transitions can be taken from any state.
+#[derive(Debug, Clone)]
+pub struct GlobalStateBuilder {
+    name: String,
+    entry_actions: Vec<StateAction>,
+    exit_actions: Vec<StateAction>,
+    reset_context: Option<String>,
+}
+
+impl GlobalStateBuilder {
+    pub fn new(name: &str) -> Self {
+        GlobalStateBuilder {
+            name: name.to_string(),
+            entry_actions: Vec::new(),
+            exit_actions: Vec::new(),
+            reset_context: None,
+        }
+    }
+
+    pub fn entry_action(mut self, action: StateAction) -> Self {
+        self.entry_actions.push(action);
+        self
+    }
+
+    pub fn exit_action(mut self, action: StateAction) -> Self {
+        self.exit_actions.push(action);
+        self
+    }
+
+    pub fn reset_context(mut self, key: &str) -> Self {
+        self.reset_context = Some(key.to_string());
+        self
+    }
+}
+
+impl From<GlobalStateBuilder> for StateJson {
+    fn from(state: GlobalStateBuilder) -> Self {
+        StateJson::GlobalState {
+            name: state.name,
+            entry_actions: state_actions(state.entry_actions),
+            exit_actions: state_actions(state.exit_actions),
+            reset_context: state.reset_context,
+        }
+    }
+}
+
+/// Assembles a state machine in code instead of from a JSON definition. States are referenced by
+/// their name, and the definition is validated when building, so a state machine that builds
+/// behaves exactly like one loaded from the equivalent JSON.
+///
+/// `guard`, `action` and `animation_transition` apply to the transition added last.
+#[derive(Debug, Clone)]
+pub struct StateMachineBuilder {
+    id: String,
+    initial: Option<String>,
+    states: Vec<StateJson>,
+    transitions: Vec<TransitionJson>,
+    listeners: Vec<ListenerJson>,
+    context_variables: Vec<ContextJson>,
+    // misuses of the builder, reported when building
+    errors: Vec<String>,
+}
+
+impl StateMachineBuilder {
+    pub fn new(id: &str) -> Self {
+        StateMachineBuilder {
+            id: id.to_string(),
+            initial: None,
+            states: Vec::new(),
+            transitions: Vec::new(),
+            listeners: Vec::new(),
+            context_variables: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// The state the state machine starts in, the first state when not set.
+    pub fn initial(mut self, initial: &str) -> Self {
+        self.initial = Some(initial.to_string());
+        self
+    }
+
+    pub fn state(mut self, state: impl Into<StateJson>) -> Self {
+        self.states.push(state.into());
+        self
+    }
+
+    pub fn transition(mut self, from_state: &str, to_state: &str, event: InternalEvent) -> Self {
+        let mut transition = TransitionJson::Transition {
+            from_state: StateReferenceJson::Name(from_state.to_string()),
+            to_state: StateReferenceJson::Name(to_state.to_string()),
+            guards: None,
+            numeric_event: None,
+            string_event: None,
+            boolean_event: None,
+            on_complete_event: None,
+            on_pointer_down_event: None,
+            on_pointer_up_event: None,
+            on_pointer_enter_event: None,
+            on_pointer_exit_event: None,
+            on_pointer_move_event: None,
+            on_key_down_event: None,
+            on_key_up_event: None,
+            on_focus_event: None,
+            on_blur_event: None,
+            on_scroll_event: None,
+            on_long_press_event: None,
+            on_double_click_event: None,
+            on_swipe_event: None,
+            on_marker_reached_event: None,
+            on_frame_reached_event: None,
+            on_loop_complete_event: None,
+            after_event: None,
+            delay_event: None,
+            every_event: None,
+            animation_transition: None,
+            actions: None,
+        };
+
+        let TransitionJson::Transition {
+            numeric_event,
+            string_event,
+            boolean_event,
+            on_complete_event,
+            on_pointer_down_event,
+            on_pointer_up_event,
+            on_pointer_enter_event,
+            on_pointer_exit_event,
+            on_pointer_move_event,
+            on_key_down_event,
+            on_key_up_event,
+            on_focus_event,
+            on_blur_event,
+            on_scroll_event,
+            on_long_press_event,
+            on_double_click_event,
+            on_swipe_event,
+            on_marker_reached_event,
+            on_frame_reached_event,
+            on_loop_complete_event,
+            after_event,
+            delay_event,
+            every_event,
+            ..
+        } = &mut transition;
+
+        match event {
+            InternalEvent::Bool { value } => *boolean_event = Some(BooleanEventJson { value }),
+            InternalEvent::String { value } => *string_event = Some(StringEventJson { value }),
+            InternalEvent::Numeric { value } => *numeric_event = Some(NumericEventJson { value }),
+            InternalEvent::OnPointerDown { target } => {
+                *on_pointer_down_event = Some(OnPointerDownEventJson { target })
+            }
+            InternalEvent::OnPointerUp { target } => {
+                *on_pointer_up_event = Some(OnPointerUpEventJson { target })
+            }
+            InternalEvent::OnPointerMove { target } => {
+                *on_pointer_move_event = Some(OnPointerMoveEventJson { target })
+            }
+            InternalEvent::OnPointerEnter { target } => {
+                *on_pointer_enter_event = Some(OnPointerEnterEventJson { target })
+            }
+            InternalEvent::OnPointerExit { target } => {
+                *on_pointer_exit_event = Some(OnPointerExitEventJson { target })
+            }
+            InternalEvent::OnComplete => *on_complete_event = Some(OnCompleteEventJson {}),
+            InternalEvent::SetNumericContext { .. } => self.errors.push(format!(
+                "transitions[{}]: Setting a context variable can't trigger a transition",
+                self.transitions.len()
+            )),
+            InternalEvent::OnMarkerReached { name } => {
+                *on_marker_reached_event = Some(OnMarkerReachedEventJson { name })
+            }
+            InternalEvent::OnFrameReached { frame } => {
+                *on_frame_reached_event = Some(OnFrameReachedEventJson { frame })
+            }
+            InternalEvent::OnLoopComplete => {
+                *on_loop_complete_event = Some(OnLoopCompleteEventJson {})
+            }
+            InternalEvent::OnKeyDown { key } => {
+                *on_key_down_event = Some(OnKeyDownEventJson { key })
+            }
+            InternalEvent::OnKeyUp { key } => *on_key_up_event = Some(OnKeyUpEventJson { key }),
+            InternalEvent::OnFocus => *on_focus_event = Some(OnFocusEventJson {}),
+            InternalEvent::OnBlur => *on_blur_event = Some(OnBlurEventJson {}),
+            InternalEvent::OnScroll => *on_scroll_event = Some(OnScrollEventJson {}),
+            InternalEvent::OnLongPress { target } => {
+                *on_long_press_event = Some(OnLongPressEventJson { target })
+            }
+            InternalEvent::OnDoubleClick { target } => {
+                *on_double_click_event = Some(OnDoubleClickEventJson { target })
+            }
+            InternalEvent::OnSwipe { direction } => {
+                *on_swipe_event = Some(OnSwipeEventJson { direction })
+            }
+            InternalEvent::After { duration } => *after_event = Some(AfterEventJson { duration }),
+            InternalEvent::Delay { duration } => *delay_event = Some(DelayEventJson { duration }),
+            InternalEvent::Every { interval } => *every_event = Some(EveryEventJson { interval }),
+        }
+
+        self.transitions.push(transition);
+        self
+    }
+
+    /// Adds a guard to the last transition, all of its guards have to be met for it to be taken.
+    pub fn guard(mut self, guard: Guard) -> Self {
+        match self.transitions.last_mut() {
+            Some(TransitionJson::Transition { guards, .. }) => {
+                guards.get_or_insert_with(Vec::new).push(guard.into())
+            }
+            None => self
+                .errors
+                .push("A guard was added before any transition".to_string()),
+        }
+
+        self
+    }
+
+    /// Adds a context action to the last transition, performed when it's taken.
+    pub fn action(mut self, action: ContextAction) -> Self {
+        match self.transitions.last_mut() {
+            Some(TransitionJson::Transition { actions, .. }) => {
+                actions.get_or_insert_with(Vec::new).push(action.into())
+            }
+            None => self
+                .errors
+                .push("An action was added before any transition".to_string()),
+        }
+
+        self
+    }
+
+    /// Blends the animations of the states of the last transition.
+    pub fn animation_transition(mut self, transition: AnimationTransition) -> Self {
+        match self.transitions.last_mut() {
+            Some(TransitionJson::Transition {
+                animation_transition,
+                ..
+            }) => *animation_transition = Some(animation_transition_json(&transition)),
+            None => self
+                .errors
+                .push("An animation transition was added before any transition".to_string()),
+        }
+
+        self
+    }
+
+    /// Adds a listener performing the context actions when it's triggered. See `ListenerJson`
+    /// for the meaning of the target of each type of listener.
+    pub fn listener(
+        mut self,
+        r#type: ListenerType,
+        target: Option<&str>,
+        actions: Vec<ContextAction>,
+    ) -> Self {
+        self.listeners.push(ListenerJson {
+            r#type: listener_type_json(r#type),
+            target: target.map(str::to_string),
+            action: None,
+            value: None,
+            context_key: None,
+            actions: (!actions.is_empty()).then(|| actions.into_iter().map(Into::into).collect()),
+        });
+
+        self
+    }
+
+    pub fn numeric_context(self, key: &str, value: f32) -> Self {
+        self.context(ContextJsonType::Numeric, key, StringNumberBool::F32(value))
+    }
+
+    pub fn string_context(self, key: &str, value: &str) -> Self {
+        self.context(
+            ContextJsonType::String,
+            key,
+            StringNumberBool::String(value.to_string()),
+        )
+    }
+
+    pub fn bool_context(self, key: &str, value: bool) -> Self {
+        self.context(ContextJsonType::Boolean, key, StringNumberBool::Bool(value))
+    }
+
+    fn context(mut self, r#type: ContextJsonType, key: &str, value: StringNumberBool) -> Self {
+        self.context_variables.push(ContextJson {
+            r#type,
+            key: key.to_string(),
+            value,
+        });
+
+        self
+    }
+
+    /// The definition of the state machine, the same as parsing its JSON. Fails with all of the
+    /// errors found by `validate_definition`, warnings are ignored.
+    pub fn build_definition(self) -> Result<StateMachineJson, StateMachineError> {
+        let definition = StateMachineJson {
+            descriptor: DescriptorJson {
+                id: self.id,
+                initial: match self.initial {
+                    Some(initial) => StateReferenceJson::Name(initial),
+                    None => StateReferenceJson::Index(0),
+                },
+            },
+            states: self.states,
+            transitions: self.transitions,
+            listeners: self.listeners,
+            context_variables: self.context_variables,
+        };
+
+        let mut errors = self.errors;
+
+        errors.extend(
+            validate_definition(&definition, &ValidationTarget::default())
+                .into_iter()
+                .filter(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error)
+                .map(|diagnostic| format!("{}: {}", diagnostic.path, diagnostic.message)),
+        );
+
+        if !errors.is_empty() {
+            return Err(StateMachineError::ValidationError {
+                reason: errors.join("; "),
+            });
+        }
+
+        Ok(definition)
+    }
+
+    pub fn build(
+        self,
+        player: Rc<RwLock<DotLottiePlayerContainer>>,
+    ) -> Result<StateMachine, StateMachineError> {
+        let definition = self.build_definition()?;
+
+        StateMachine::default().create_from_definition(definition, &player)
+    }
+}
+
+fn state_actions(actions: Vec<StateAction>) -> Option<Vec<StateActionJson>> {
+    (!actions.is_empty()).then(|| actions.into_iter().map(Into::into).collect())
+}
+
+fn listener_type_json(r#type: ListenerType) -> ListenerJsonType {
+    match r#type {
+        ListenerType::PointerUp => ListenerJsonType::PointerUp,
+        ListenerType::PointerDown => ListenerJsonType::PointerDown,
+        ListenerType::PointerEnter => ListenerJsonType::PointerEnter,
+        ListenerType::PointerExit => ListenerJsonType::PointerExit,
+        ListenerType::PointerMove => ListenerJsonType::PointerMove,
+        ListenerType::KeyDown => ListenerJsonType::KeyDown,
+        ListenerType::KeyUp => ListenerJsonType::KeyUp,
+        ListenerType::Focus => ListenerJsonType::Focus,
+        ListenerType::Blur => ListenerJsonType::Blur,
+        ListenerType::Scroll => ListenerJsonType::Scroll,
+        ListenerType::LongPress => ListenerJsonType::LongPress,
+        ListenerType::DoubleClick => ListenerJsonType::DoubleClick,
+        ListenerType::Swipe => ListenerJsonType::Swipe,
+    }
+}
+
+fn animation_transition_json(transition: &AnimationTransition) -> AnimationTransitionJson {
+    let direction = |direction: &TransitionDirection| match direction {
+        TransitionDirection::Left => TransitionDirectionJson::Left,
+        TransitionDirection::Right => TransitionDirectionJson::Right,
+        TransitionDirection::Up => TransitionDirectionJson::Up,
+        TransitionDirection::Down => TransitionDirectionJson::Down,
+    };
+
+    let (r#type, direction) = match &transition.effect {
+        TransitionEffect::Crossfade => (AnimationTransitionJsonType::Crossfade, None),
+        TransitionEffect::Slide { direction: d } => {
+            (AnimationTransitionJsonType::Slide, Some(direction(d)))
+        }
+        TransitionEffect::Wipe { direction: d } => {
+            (AnimationTransitionJsonType::Wipe, Some(direction(d)))
+        }
+    };
+
+    AnimationTransitionJson {
+        r#type,
+        direction,
+        duration: transition.duration,
+        easing: Some(match transition.easing {
+            Easing::Linear => EasingJson::Linear,
+            Easing::EaseIn => EasingJson::EaseIn,
+            Easing::EaseOut => EasingJson::EaseOut,
+            Easing::EaseInOut => EasingJson::EaseInOut,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{
+        state_machine_parse, state_machine_serialize, TransitionGuardConditionType,
+    };
+    use crate::transitions::guard::GuardOperand;
+
+    fn builder() -> StateMachineBuilder {
+        StateMachineBuilder::new("builder")
+            .initial("pigeon")
+            .state(
+                PlaybackStateBuilder::new("pigeon")
+                    .marker("bird")
+                    .autoplay(true),
+            )
+            .state(
+                PlaybackStateBuilder::new("explosion")
+                    .marker("explosion")
+                    .mode(Mode::Bounce),
+            )
+            .transition(
+                "pigeon",
+                "explosion",
+                InternalEvent::String {
+                    value: "explode".to_string(),
+                },
+            )
+            .guard(Guard::Numeric {
+                context_key: "rating".to_string(),
+                condition_type: TransitionGuardConditionType::GreaterThan,
+                compare_to: GuardOperand::Value(StringNumberBool::F32(2.0)),
+            })
+            .action(ContextAction::Increment {
+                context_key: "rating".to_string(),
+                value: 1.0,
+            })
+            .listener(ListenerType::PointerDown, Some("pigeon"), Vec::new())
+            .numeric_context("rating", 0.0)
+    }
+
+    #[test]
+    fn test_build_definition() {
+        let definition = builder().build_definition().unwrap();
+
+        let expected = state_machine_parse(
+            r#"{
+                "descriptor": { "id": "builder", "initial": "pigeon" },
+                "states": [
+                    { "name": "pigeon", "type": "PlaybackState", "marker": "bird", "autoplay": true },
+                    { "name": "explosion", "type": "PlaybackState", "marker": "explosion", "mode": "Bounce" }
+                ],
+                "transitions": [{
+                    "type": "Transition",
+                    "from_state": "pigeon",
+                    "to_state": "explosion",
+                    "string_event": { "value": "explode" },
+                    "guards": [{ "type": "Numeric", "context_key": "rating", "condition_type": "GreaterThan", "compare_to": 2 }],
+                    "actions": [{ "type": "Increment", "context_key": "rating", "value": 1 }]
+                }],
+                "listeners": [{ "type": "PointerDown", "target": "pigeon" }],
+                "context_variables": [{ "type": "Numeric", "key": "rating", "value": 0 }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            state_machine_serialize(&definition).unwrap(),
+            state_machine_serialize(&expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let error = builder()
+            .transition("explosion", "feather", InternalEvent::OnComplete)
+            .build_definition()
+            .unwrap_err();
+
+        assert!(error.to_string().contains("transitions[1].to_state"));
+        assert!(error
+            .to_string()
+            .contains("There is no state named 'feather'"));
+
+        let error = StateMachineBuilder::new("builder")
+            .state(PlaybackStateBuilder::new("pigeon"))
+            .guard(Guard::Boolean {
+                context_key: "exploded".to_string(),
+                condition_type: TransitionGuardConditionType::Equal,
+                compare_to: GuardOperand::Value(StringNumberBool::Bool(true)),
+            })
+            .build_definition()
+            .unwrap_err();
+
+        assert!(matches!(error, StateMachineError::ValidationError { .. }));
+        assert!(error.to_string().contains("before any transition"));
+    }
+}
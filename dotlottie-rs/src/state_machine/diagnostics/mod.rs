@@ -0,0 +1,747 @@
+use std::collections::HashMap;
+
+use crate::errors::StateMachineError;
+use crate::parser::{
+    state_machine_parse, ContextActionJson, ContextJsonType, ListenerJsonType, StateJson,
+    StateMachineJson, StateReferenceJson, TransitionGuardConditionType, TransitionGuardJson,
+    TransitionGuardType, TransitionJson,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiagnosticSeverity {
+    /// The state machine can't be loaded, or part of it can never work.
+    Error,
+    /// The state machine loads, but probably doesn't behave as intended.
+    Warning,
+}
+
+impl std::fmt::Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DiagnosticSeverity::Error => write!(f, "Error"),
+            DiagnosticSeverity::Warning => write!(f, "Warning"),
+        }
+    }
+}
+
+/// A problem found in a state machine definition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: DiagnosticSeverity,
+    /// The JSON path of the offending value, e.g. "transitions[2].to_state", empty when the
+    /// definition can't be parsed.
+    pub path: String,
+    pub message: String,
+}
+
+/// What is known about the animation a state machine is validated against. Checks needing
+/// something that isn't known are skipped.
+#[derive(Default)]
+pub struct ValidationTarget<'a> {
+    /// The ids of the animations of the manifest.
+    pub animation_ids: Option<Vec<String>>,
+    /// Whether the loaded animation has a layer with the given name.
+    pub has_layer: Option<&'a dyn Fn(&str) -> bool>,
+}
+
+/// Lints a state machine definition, returning every problem found instead of stopping at the
+/// first one. A definition without errors loads, warnings point at parts of it that are likely
+/// mistakes.
+pub fn validate_state_machine(definition: &str, target: &ValidationTarget) -> Vec<Diagnostic> {
+    let parsed_state_machine = match state_machine_parse(definition) {
+        Ok(parsed_state_machine) => parsed_state_machine,
+        Err(error) => {
+            return vec![Diagnostic {
+                severity: DiagnosticSeverity::Error,
+                path: String::new(),
+                message: error.to_string(),
+            }]
+        }
+    };
+
+    validate_definition(&parsed_state_machine, target)
+}
+
+/// Lints a parsed state machine definition, see `validate_state_machine`.
+pub fn validate_definition(
+    parsed_state_machine: &StateMachineJson,
+    target: &ValidationTarget,
+) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        target,
+        state_names: parsed_state_machine
+            .states
+            .iter()
+            .map(|state| state.name().to_string())
+            .collect(),
+        context: HashMap::new(),
+        diagnostics: Vec::new(),
+    };
+
+    for (index, context) in parsed_state_machine.context_variables.iter().enumerate() {
+        if validator
+            .context
+            .insert(context.key.clone(), context.r#type)
+            .is_some()
+        {
+            validator.warning(
+                format!("context_variables[{}].key", index),
+                format!("Context variable '{}' is declared twice", context.key),
+            );
+        }
+    }
+
+    validator.resolve_state(
+        "descriptor.initial".to_string(),
+        &parsed_state_machine.descriptor.initial,
+    );
+    validator.validate_states(&parsed_state_machine.states);
+
+    for (index, transition) in parsed_state_machine.transitions.iter().enumerate() {
+        validator.validate_transition(&format!("transitions[{}]", index), transition);
+    }
+
+    for (index, listener) in parsed_state_machine.listeners.iter().enumerate() {
+        let path = format!("listeners[{}]", index);
+
+        if matches!(
+            listener.r#type,
+            ListenerJsonType::PointerUp
+                | ListenerJsonType::PointerDown
+                | ListenerJsonType::PointerEnter
+                | ListenerJsonType::PointerExit
+                | ListenerJsonType::PointerMove
+                | ListenerJsonType::LongPress
+                | ListenerJsonType::DoubleClick
+        ) {
+            validator.validate_layer(&format!("{}.target", path), listener.target.as_deref());
+        }
+
+        if let Some(actions) = &listener.actions {
+            validator.validate_context_actions(&format!("{}.actions", path), actions);
+        }
+    }
+
+    validator.diagnostics
+}
+
+struct Validator<'a> {
+    target: &'a ValidationTarget<'a>,
+    state_names: Vec<String>,
+    context: HashMap<String, ContextJsonType>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&mut self, path: String, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Error,
+            path,
+            message,
+        });
+    }
+
+    fn warning(&mut self, path: String, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity: DiagnosticSeverity::Warning,
+            path,
+            message,
+        });
+    }
+
+    // Reports a reference to a state that doesn't exist, or to several states
+    fn resolve_state(&mut self, path: String, reference: &StateReferenceJson) -> Option<usize> {
+        match reference.resolve(&self.state_names) {
+            Ok(index) if (index as usize) < self.state_names.len() => Some(index as usize),
+            Ok(index) => {
+                self.error(path, format!("State index {} is out of range", index));
+                None
+            }
+            Err(
+                StateMachineError::ParsingError { reason }
+                | StateMachineError::SnapshotError { reason }
+                | StateMachineError::ValidationError { reason },
+            ) => {
+                self.error(path, reason.trim_end_matches('!').to_string());
+                None
+            }
+        }
+    }
+
+    fn validate_states(&mut self, states: &[StateJson]) {
+        let mut names: HashMap<&str, usize> = HashMap::new();
+        let mut parents: Vec<Option<usize>> = vec![None; states.len()];
+
+        for (index, state) in states.iter().enumerate() {
+            let path = format!("states[{}]", index);
+
+            let (name, animation_id, reset_context) = match state {
+                StateJson::PlaybackState {
+                    name,
+                    animation_id,
+                    reset_context,
+                    ..
+                } => (name, animation_id, reset_context),
+                StateJson::SyncState {
+                    name,
+                    animation_id,
+                    reset_context,
+                    frame_context_key,
+                    ..
+                } => {
+                    match self.context.get(frame_context_key) {
+                        None => self.error(
+                            format!("{}.frame_context_key", path),
+                            format!("Context variable '{}' isn't declared", frame_context_key),
+                        ),
+                        Some(ContextJsonType::Numeric) => {}
+                        Some(_) => self.error(
+                            format!("{}.frame_context_key", path),
+                            format!("Context variable '{}' isn't numeric", frame_context_key),
+                        ),
+                    }
+
+                    (name, animation_id, reset_context)
+                }
+                StateJson::GlobalState {
+                    name,
+                    reset_context,
+                    ..
+                } => (name, &None, reset_context),
+            };
+
+            if let Some(first) = names.insert(name, index) {
+                self.warning(
+                    format!("{}.name", path),
+                    format!("State name '{}' is already used by state {}", name, first),
+                );
+            }
+
+            if let (Some(animation_id), Some(animation_ids)) =
+                (animation_id, &self.target.animation_ids)
+            {
+                if !animation_ids.contains(animation_id) {
+                    self.error(
+                        format!("{}.animation_id", path),
+                        format!("Animation '{}' isn't in the manifest", animation_id),
+                    );
+                }
+            }
+
+            if let Some(key) = reset_context {
+                if !key.is_empty() && key != "*" && !self.context.contains_key(key) {
+                    self.warning(
+                        format!("{}.reset_context", path),
+                        format!("Context variable '{}' isn't declared", key),
+                    );
+                }
+            }
+
+            let (StateJson::PlaybackState { parent, .. } | StateJson::SyncState { parent, .. }) =
+                state
+            else {
+                continue;
+            };
+
+            let Some(parent) = parent
+                .as_ref()
+                .and_then(|parent| self.resolve_state(format!("{}.parent", path), parent))
+            else {
+                continue;
+            };
+
+            if parent == index {
+                self.error(
+                    format!("{}.parent", path),
+                    "A state can't be its own parent".to_string(),
+                );
+            } else if matches!(states[parent], StateJson::GlobalState { .. }) {
+                self.error(
+                    format!("{}.parent", path),
+                    "The global state can't have children".to_string(),
+                );
+            } else {
+                parents[index] = Some(parent);
+            }
+        }
+
+        for index in 0..states.len() {
+            let mut parent = parents[index];
+            let mut depth = 0;
+
+            while let Some(ancestor) = parent {
+                depth += 1;
+
+                if ancestor == index || depth > states.len() {
+                    self.error(
+                        format!("states[{}].parent", index),
+                        "State is nested within itself".to_string(),
+                    );
+                    break;
+                }
+
+                parent = parents[ancestor];
+            }
+        }
+
+        for (index, state) in states.iter().enumerate() {
+            let (StateJson::PlaybackState {
+                initial: Some(initial),
+                ..
+            }
+            | StateJson::SyncState {
+                initial: Some(initial),
+                ..
+            }) = state
+            else {
+                continue;
+            };
+
+            let path = format!("states[{}].initial", index);
+
+            match self.resolve_state(path.clone(), initial) {
+                Some(child) if parents[child] != Some(index) => self.error(
+                    path,
+                    format!("Initial state {} isn't a child of this state", initial),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    fn validate_transition(&mut self, path: &str, transition: &TransitionJson) {
+        let TransitionJson::Transition {
+            from_state,
+            to_state,
+            guards,
+            numeric_event,
+            string_event,
+            boolean_event,
+            on_complete_event,
+            on_pointer_down_event,
+            on_pointer_up_event,
+            on_pointer_enter_event,
+            on_pointer_exit_event,
+            on_pointer_move_event,
+            on_key_down_event,
+            on_key_up_event,
+            on_focus_event,
+            on_blur_event,
+            on_scroll_event,
+            on_long_press_event,
+            on_double_click_event,
+            on_swipe_event,
+            on_marker_reached_event,
+            on_frame_reached_event,
+            on_loop_complete_event,
+            after_event,
+            delay_event,
+            every_event,
+            actions,
+            ..
+        } = transition;
+
+        self.resolve_state(format!("{}.from_state", path), from_state);
+        self.resolve_state(format!("{}.to_state", path), to_state);
+
+        let events = [
+            numeric_event.is_some(),
+            string_event.is_some(),
+            boolean_event.is_some(),
+            on_complete_event.is_some(),
+            on_pointer_down_event.is_some(),
+            on_pointer_up_event.is_some(),
+            on_pointer_enter_event.is_some(),
+            on_pointer_exit_event.is_some(),
+            on_pointer_move_event.is_some(),
+            on_key_down_event.is_some(),
+            on_key_up_event.is_some(),
+            on_focus_event.is_some(),
+            on_blur_event.is_some(),
+            on_scroll_event.is_some(),
+            on_long_press_event.is_some(),
+            on_double_click_event.is_some(),
+            on_swipe_event.is_some(),
+            on_marker_reached_event.is_some(),
+            on_frame_reached_event.is_some(),
+            on_loop_complete_event.is_some(),
+            after_event.is_some(),
+            delay_event.is_some(),
+            every_event.is_some(),
+        ]
+        .iter()
+        .filter(|event| **event)
+        .count();
+
+        match events {
+            0 => self.warning(
+                path.to_string(),
+                "Transition has no event and is never taken".to_string(),
+            ),
+            1 => {}
+            _ => self.warning(
+                path.to_string(),
+                "Transition has several events, only the first one is used".to_string(),
+            ),
+        }
+
+        let layer_targets = [
+            (
+                "on_pointer_down_event",
+                on_pointer_down_event.as_ref().map(|e| &e.target),
+            ),
+            (
+                "on_pointer_up_event",
+                on_pointer_up_event.as_ref().map(|e| &e.target),
+            ),
+            (
+                "on_pointer_enter_event",
+                on_pointer_enter_event.as_ref().map(|e| &e.target),
+            ),
+            (
+                "on_pointer_exit_event",
+                on_pointer_exit_event.as_ref().map(|e| &e.target),
+            ),
+            (
+                "on_pointer_move_event",
+                on_pointer_move_event.as_ref().map(|e| &e.target),
+            ),
+            (
+                "on_long_press_event",
+                on_long_press_event.as_ref().map(|e| &e.target),
+            ),
+            (
+                "on_double_click_event",
+                on_double_click_event.as_ref().map(|e| &e.target),
+            ),
+        ];
+
+        for (event, target) in layer_targets {
+            if let Some(target) = target {
+                self.validate_layer(&format!("{}.{}.target", path, event), target.as_deref());
+            }
+        }
+
+        if let Some(guards) = guards {
+            self.validate_guards(&format!("{}.guards", path), guards);
+        }
+
+        if let Some(actions) = actions {
+            self.validate_context_actions(&format!("{}.actions", path), actions);
+        }
+    }
+
+    fn validate_guards(&mut self, path: &str, guards: &[TransitionGuardJson]) {
+        for (index, guard) in guards.iter().enumerate() {
+            let path = format!("{}[{}]", path, index);
+            let nested = guard.guards.as_deref().unwrap_or_default();
+
+            let expected_type = match guard.r#type {
+                TransitionGuardType::And | TransitionGuardType::Or => {
+                    self.validate_guards(&format!("{}.guards", path), nested);
+                    continue;
+                }
+                TransitionGuardType::Not => {
+                    if nested.len() != 1 {
+                        self.error(
+                            format!("{}.guards", path),
+                            "A Not guard must contain exactly one guard".to_string(),
+                        );
+                    }
+
+                    self.validate_guards(&format!("{}.guards", path), nested);
+                    continue;
+                }
+                TransitionGuardType::Numeric => ContextJsonType::Numeric,
+                TransitionGuardType::String => ContextJsonType::String,
+                TransitionGuardType::Boolean => ContextJsonType::Boolean,
+            };
+
+            self.validate_context_key(
+                &format!("{}.context_key", path),
+                &guard.context_key,
+                &expected_type,
+                DiagnosticSeverity::Error,
+            );
+
+            match guard.condition_type {
+                None => self.error(
+                    format!("{}.condition_type", path),
+                    "Guard is missing its condition type".to_string(),
+                ),
+                Some(TransitionGuardConditionType::InRange) => {
+                    if guard.min.is_none() || guard.max.is_none() {
+                        self.error(path, "InRange guard needs a min and a max".to_string());
+                    }
+                }
+                Some(_) => match &guard.compare_to_key {
+                    Some(compare_to_key) => self.validate_context_key(
+                        &format!("{}.compare_to_key", path),
+                        compare_to_key,
+                        &expected_type,
+                        DiagnosticSeverity::Error,
+                    ),
+                    None if guard.compare_to.is_none() => {
+                        self.error(path, "Guard has nothing to compare to".to_string())
+                    }
+                    None => {}
+                },
+            }
+        }
+    }
+
+    fn validate_context_actions(&mut self, path: &str, actions: &[ContextActionJson]) {
+        for (index, action) in actions.iter().enumerate() {
+            let path = format!("{}[{}].context_key", path, index);
+
+            // Set declares the variable when it doesn't exist yet
+            let (context_key, expected_type) = match action {
+                ContextActionJson::Set { .. } => continue,
+                ContextActionJson::Increment { context_key, .. }
+                | ContextActionJson::Decrement { context_key, .. }
+                | ContextActionJson::Clamp { context_key, .. } => {
+                    (context_key, ContextJsonType::Numeric)
+                }
+                ContextActionJson::Toggle { context_key } => {
+                    (context_key, ContextJsonType::Boolean)
+                }
+                ContextActionJson::Append { context_key, .. } => {
+                    (context_key, ContextJsonType::String)
+                }
+            };
+
+            self.validate_context_key(
+                &path,
+                context_key,
+                &expected_type,
+                DiagnosticSeverity::Warning,
+            );
+        }
+    }
+
+    // Reports an undeclared key with the given severity, and a key of another type as a warning
+    fn validate_context_key(
+        &mut self,
+        path: &str,
+        key: &str,
+        expected_type: &ContextJsonType,
+        severity: DiagnosticSeverity,
+    ) {
+        match self.context.get(key) {
+            None => self.diagnostics.push(Diagnostic {
+                severity,
+                path: path.to_string(),
+                message: format!("Context variable '{}' isn't declared", key),
+            }),
+            Some(context_type) if context_type != expected_type => self.warning(
+                path.to_string(),
+                format!(
+                    "Context variable '{}' is {:?}, not {:?}",
+                    key, context_type, expected_type
+                ),
+            ),
+            Some(_) => {}
+        }
+    }
+
+    fn validate_layer(&mut self, path: &str, layer: Option<&str>) {
+        if let (Some(layer), Some(has_layer)) = (layer, self.target.has_layer) {
+            if !has_layer(layer) {
+                self.warning(
+                    path.to_string(),
+                    format!("Layer '{}' doesn't exist in the animation", layer),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_machine(states: &str, transitions: &str, listeners: &str) -> String {
+        format!(
+            r#"{{
+                "descriptor": {{ "id": "lint", "initial": 0 }},
+                "states": {},
+                "transitions": {},
+                "listeners": {},
+                "context_variables": [
+                    {{ "type": "Numeric", "key": "rating", "value": 0 }},
+                    {{ "type": "Boolean", "key": "enabled", "value": true }}
+                ]
+            }}"#,
+            states, transitions, listeners
+        )
+    }
+
+    fn paths(diagnostics: &[Diagnostic], severity: DiagnosticSeverity) -> Vec<&str> {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .map(|diagnostic| diagnostic.path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_state_machine() {
+        let definition = state_machine(
+            r#"[
+                { "name": "idle", "type": "PlaybackState", "animation_id": "a" },
+                { "name": "rated", "type": "PlaybackState" }
+            ]"#,
+            r#"[{
+                "type": "Transition",
+                "from_state": 0,
+                "to_state": 1,
+                "on_pointer_down_event": { "target": "star" },
+                "guards": [{ "type": "Numeric", "context_key": "rating", "condition_type": "GreaterThan", "compare_to": 2 }],
+                "actions": [{ "type": "Increment", "context_key": "rating" }]
+            }]"#,
+            r#"[{ "type": "PointerUp", "target": "star" }]"#,
+        );
+        let has_layer = |layer: &str| layer == "star";
+        let target = ValidationTarget {
+            animation_ids: Some(vec!["a".to_string()]),
+            has_layer: Some(&has_layer),
+        };
+
+        assert!(validate_state_machine(&definition, &target).is_empty());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let diagnostics = validate_state_machine("{}", &ValidationTarget::default());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostics[0].path, "");
+    }
+
+    #[test]
+    fn test_invalid_references() {
+        let definition = state_machine(
+            r#"[
+                { "name": "idle", "type": "PlaybackState", "animation_id": "missing", "parent": 4 },
+                { "name": "idle", "type": "SyncState", "frame_context_key": "progress", "reset_context": "nope" }
+            ]"#,
+            r#"[
+                { "type": "Transition", "from_state": 0, "to_state": 5, "string_event": { "value": "go" } },
+                {
+                    "type": "Transition",
+                    "from_state": 1,
+                    "to_state": 0,
+                    "on_pointer_down_event": { "target": "ghost" },
+                    "guards": [
+                        { "type": "String", "context_key": "unknown", "condition_type": "Equal", "compare_to": "a" },
+                        { "type": "Not", "guards": [
+                            { "type": "Boolean", "context_key": "rating", "condition_type": "Equal", "compare_to_key": "other" }
+                        ] }
+                    ],
+                    "actions": [{ "type": "Toggle", "context_key": "missing" }]
+                },
+                { "type": "Transition", "from_state": 1, "to_state": 0 }
+            ]"#,
+            r#"[{ "type": "PointerDown", "target": "ghost" }, { "type": "KeyDown", "target": "Enter" }]"#,
+        );
+        let has_layer = |layer: &str| layer == "star";
+        let target = ValidationTarget {
+            animation_ids: Some(vec!["a".to_string()]),
+            has_layer: Some(&has_layer),
+        };
+        let diagnostics = validate_state_machine(&definition, &target);
+
+        assert_eq!(
+            paths(&diagnostics, DiagnosticSeverity::Error),
+            vec![
+                "states[0].animation_id",
+                "states[0].parent",
+                "states[1].frame_context_key",
+                "transitions[0].to_state",
+                "transitions[1].guards[0].context_key",
+                "transitions[1].guards[1].guards[0].compare_to_key",
+            ]
+        );
+        assert_eq!(
+            paths(&diagnostics, DiagnosticSeverity::Warning),
+            vec![
+                "states[1].name",
+                "states[1].reset_context",
+                "transitions[1].on_pointer_down_event.target",
+                "transitions[1].guards[1].guards[0].context_key",
+                "transitions[1].actions[0].context_key",
+                "transitions[2]",
+                "listeners[0].target",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_animation_is_skipped() {
+        let definition = state_machine(
+            r#"[{ "name": "idle", "type": "PlaybackState", "animation_id": "a" }]"#,
+            r#"[{ "type": "Transition", "from_state": 0, "to_state": 0, "on_pointer_down_event": { "target": "ghost" } }]"#,
+            "[]",
+        );
+
+        assert!(validate_state_machine(&definition, &ValidationTarget::default()).is_empty());
+    }
+
+    #[test]
+    fn test_state_references() {
+        let definition = state_machine(
+            r#"[
+                { "name": "idle", "type": "PlaybackState", "initial": "idle" },
+                { "name": "hover", "type": "PlaybackState", "parent": "idle" },
+                { "name": "hover", "type": "PlaybackState" }
+            ]"#,
+            r#"[
+                { "type": "Transition", "from_state": "idle", "to_state": "hover", "on_complete_event": {} },
+                { "type": "Transition", "from_state": "missing", "to_state": 0, "on_complete_event": {} }
+            ]"#,
+            "[]",
+        );
+        let diagnostics = validate_state_machine(&definition, &ValidationTarget::default());
+
+        assert_eq!(
+            paths(&diagnostics, DiagnosticSeverity::Error),
+            vec![
+                "states[0].initial",
+                "transitions[0].to_state",
+                "transitions[1].from_state",
+            ]
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .find(|diagnostic| diagnostic.path == "transitions[0].to_state")
+                .map(|diagnostic| diagnostic.message.as_str()),
+            Some("Several states are named 'hover'")
+        );
+        assert_eq!(
+            paths(&diagnostics, DiagnosticSeverity::Warning),
+            vec!["states[2].name"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_nesting() {
+        let definition = state_machine(
+            r#"[
+                { "name": "a", "type": "PlaybackState", "parent": 1, "initial": 1 },
+                { "name": "b", "type": "PlaybackState", "parent": 0 },
+                { "name": "c", "type": "GlobalState" },
+                { "name": "d", "type": "PlaybackState", "parent": 2 }
+            ]"#,
+            "[]",
+            "[]",
+        );
+        let diagnostics = validate_state_machine(&definition, &ValidationTarget::default());
+
+        assert_eq!(
+            paths(&diagnostics, DiagnosticSeverity::Error),
+            vec!["states[3].parent", "states[0].parent", "states[1].parent",]
+        );
+    }
+}
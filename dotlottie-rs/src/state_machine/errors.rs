@@ -1,5 +1,11 @@
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum StateMachineError {
-    #[error("Failed to parse JSON state machine definition")]
+    #[error("Failed to parse JSON state machine definition: {reason}")]
     ParsingError { reason: String },
+
+    #[error("Failed to restore state machine snapshot: {reason}")]
+    SnapshotError { reason: String },
+
+    #[error("Invalid state machine definition: {reason}")]
+    ValidationError { reason: String },
 }
@@ -0,0 +1,229 @@
+use super::{Event, SwipeDirection};
+
+// Seconds a pointer has to stay down without moving for a long press
+const LONG_PRESS_DURATION: f64 = 0.5;
+// Seconds between the two clicks of a double click
+const DOUBLE_CLICK_INTERVAL: f64 = 0.3;
+// Seconds a swipe can last at most
+const SWIPE_MAX_DURATION: f64 = 1.0;
+// Distance the pointer has to travel for a swipe
+const SWIPE_MIN_DISTANCE: f32 = 30.0;
+// Distance the pointer can move and still be pressing or clicking in place
+const MOVE_TOLERANCE: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy)]
+struct PointerDown {
+    x: f32,
+    y: f32,
+    time: f64,
+    // moved past the tolerance since going down
+    moved: bool,
+    long_pressed: bool,
+}
+
+/// Recognizes long presses, double clicks and swipes from the pointer events posted by the
+/// host, so that every platform recognizes the same gestures.
+#[derive(Debug, Default)]
+pub(crate) struct GestureRecognizer {
+    down: Option<PointerDown>,
+    // position and time of the last click, waiting for a second one
+    last_click: Option<(f32, f32, f64)>,
+}
+
+fn distance(x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt()
+}
+
+impl GestureRecognizer {
+    /// Returns the gestures completed by a pointer event posted at `now`, in seconds.
+    pub fn pointer_event(&mut self, event: &Event, now: f64) -> Vec<Event> {
+        match event {
+            Event::OnPointerDown { x, y } => {
+                self.down = Some(PointerDown {
+                    x: *x,
+                    y: *y,
+                    time: now,
+                    moved: false,
+                    long_pressed: false,
+                });
+
+                vec![]
+            }
+            Event::OnPointerMove { x, y } => {
+                if let Some(down) = self.down.as_mut() {
+                    if distance(down.x, down.y, *x, *y) > MOVE_TOLERANCE {
+                        down.moved = true;
+                    }
+                }
+
+                vec![]
+            }
+            Event::OnPointerUp { x, y } => {
+                let Some(down) = self.down.take() else {
+                    return vec![];
+                };
+
+                if down.long_pressed {
+                    self.last_click = None;
+
+                    return vec![];
+                }
+
+                let (dx, dy) = (x - down.x, y - down.y);
+
+                if distance(down.x, down.y, *x, *y) >= SWIPE_MIN_DISTANCE {
+                    self.last_click = None;
+
+                    if now - down.time > SWIPE_MAX_DURATION {
+                        return vec![];
+                    }
+
+                    // Pointer coordinates grow to the right and downwards
+                    let direction = if dx.abs() >= dy.abs() {
+                        if dx > 0.0 {
+                            SwipeDirection::Right
+                        } else {
+                            SwipeDirection::Left
+                        }
+                    } else if dy > 0.0 {
+                        SwipeDirection::Down
+                    } else {
+                        SwipeDirection::Up
+                    };
+
+                    return vec![Event::OnSwipe { direction }];
+                }
+
+                if down.moved {
+                    self.last_click = None;
+
+                    return vec![];
+                }
+
+                match self.last_click.take() {
+                    Some((click_x, click_y, click_time))
+                        if now - click_time <= DOUBLE_CLICK_INTERVAL
+                            && distance(click_x, click_y, *x, *y) <= MOVE_TOLERANCE =>
+                    {
+                        vec![Event::OnDoubleClick { x: *x, y: *y }]
+                    }
+                    _ => {
+                        self.last_click = Some((*x, *y, now));
+
+                        vec![]
+                    }
+                }
+            }
+            Event::OnPointerExit { .. } => {
+                self.down = None;
+                self.last_click = None;
+
+                vec![]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Returns a long press once the pointer was held down in place for long enough.
+    pub fn update(&mut self, now: f64) -> Option<Event> {
+        let down = self.down.as_mut()?;
+
+        if down.moved || down.long_pressed || now - down.time < LONG_PRESS_DURATION {
+            return None;
+        }
+
+        down.long_pressed = true;
+
+        Some(Event::OnLongPress {
+            x: down.x,
+            y: down.y,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn down(x: f32, y: f32) -> Event {
+        Event::OnPointerDown { x, y }
+    }
+
+    fn up(x: f32, y: f32) -> Event {
+        Event::OnPointerUp { x, y }
+    }
+
+    #[test]
+    fn test_long_press() {
+        let mut gestures = GestureRecognizer::default();
+
+        gestures.pointer_event(&down(10.0, 10.0), 0.0);
+        assert!(gestures.update(0.25).is_none());
+        assert!(matches!(
+            gestures.update(0.5),
+            Some(Event::OnLongPress { x, y }) if x == 10.0 && y == 10.0
+        ));
+        // Only once per press, and the release isn't a click
+        assert!(gestures.update(1.0).is_none());
+        assert!(gestures.pointer_event(&up(10.0, 10.0), 1.0).is_empty());
+
+        // Moving cancels the long press
+        gestures.pointer_event(&down(10.0, 10.0), 2.0);
+        gestures.pointer_event(&Event::OnPointerMove { x: 25.0, y: 10.0 }, 2.1);
+        assert!(gestures.update(3.0).is_none());
+    }
+
+    #[test]
+    fn test_double_click() {
+        let mut gestures = GestureRecognizer::default();
+
+        gestures.pointer_event(&down(10.0, 10.0), 0.0);
+        assert!(gestures.pointer_event(&up(10.0, 10.0), 0.05).is_empty());
+        gestures.pointer_event(&down(12.0, 10.0), 0.2);
+        assert!(matches!(
+            gestures.pointer_event(&up(12.0, 10.0), 0.25)[..],
+            [Event::OnDoubleClick { .. }]
+        ));
+
+        // A third click starts over
+        gestures.pointer_event(&down(12.0, 10.0), 0.3);
+        assert!(gestures.pointer_event(&up(12.0, 10.0), 0.35).is_empty());
+
+        // Too slow
+        gestures.pointer_event(&down(12.0, 10.0), 1.0);
+        assert!(gestures.pointer_event(&up(12.0, 10.0), 1.05).is_empty());
+    }
+
+    #[test]
+    fn test_swipe() {
+        let mut gestures = GestureRecognizer::default();
+
+        let mut swipe = |from: (f32, f32), to: (f32, f32), duration: f64| {
+            gestures.pointer_event(&down(from.0, from.1), 0.0);
+            match gestures.pointer_event(&up(to.0, to.1), duration)[..] {
+                [Event::OnSwipe { direction }] => Some(direction),
+                _ => None,
+            }
+        };
+
+        assert_eq!(
+            swipe((50.0, 50.0), (100.0, 60.0), 0.25),
+            Some(SwipeDirection::Right)
+        );
+        assert_eq!(
+            swipe((50.0, 50.0), (0.0, 40.0), 0.25),
+            Some(SwipeDirection::Left)
+        );
+        assert_eq!(
+            swipe((50.0, 50.0), (40.0, 0.0), 0.25),
+            Some(SwipeDirection::Up)
+        );
+        assert_eq!(
+            swipe((50.0, 50.0), (60.0, 100.0), 0.25),
+            Some(SwipeDirection::Down)
+        );
+        // Too short and too slow
+        assert_eq!(swipe((50.0, 50.0), (60.0, 50.0), 0.25), None);
+        assert_eq!(swipe((50.0, 50.0), (100.0, 50.0), 2.0), None);
+    }
+}
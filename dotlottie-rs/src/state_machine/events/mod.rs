@@ -1,8 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+mod gesture;
+
+pub(crate) use gesture::GestureRecognizer;
+
 pub trait PointerEvent {
     fn x(&self) -> f32;
     fn y(&self) -> f32;
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl std::fmt::Display for SwipeDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SwipeDirection::Left => write!(f, "Left"),
+            SwipeDirection::Right => write!(f, "Right"),
+            SwipeDirection::Up => write!(f, "Up"),
+            SwipeDirection::Down => write!(f, "Down"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Event {
     Bool { value: bool },
@@ -15,6 +40,19 @@ pub enum Event {
     OnPointerExit { x: f32, y: f32 },
     OnComplete,
     SetNumericContext { key: String, value: f32 },
+    OnMarkerReached { name: String },
+    OnFrameReached { frame: f32 },
+    OnLoopComplete,
+    // Key names as reported by the platform, e.g. "Enter" or "ArrowLeft"
+    OnKeyDown { key: String },
+    OnKeyUp { key: String },
+    OnFocus,
+    OnBlur,
+    OnScroll { delta_x: f32, delta_y: f32 },
+    // Gestures, recognized by the state machine from the pointer events
+    OnLongPress { x: f32, y: f32 },
+    OnDoubleClick { x: f32, y: f32 },
+    OnSwipe { direction: SwipeDirection },
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +67,23 @@ pub enum InternalEvent {
     OnPointerExit { target: Option<String> },
     OnComplete,
     SetNumericContext { key: String, value: f32 },
+    OnMarkerReached { name: String },
+    OnFrameReached { frame: f32 },
+    OnLoopComplete,
+    // A missing key or direction matches any
+    OnKeyDown { key: Option<String> },
+    OnKeyUp { key: Option<String> },
+    OnFocus,
+    OnBlur,
+    OnScroll,
+    OnLongPress { target: Option<String> },
+    OnDoubleClick { target: Option<String> },
+    OnSwipe { direction: Option<SwipeDirection> },
+    // Timed events, in seconds. After counts from the last posted event, Delay and Every from
+    // entering the state
+    After { duration: f32 },
+    Delay { duration: f32 },
+    Every { interval: f32 },
 }
 
 // Display for InternalEvent
@@ -75,6 +130,35 @@ impl std::fmt::Display for InternalEvent {
             }
             InternalEvent::OnComplete => write!(f, "OnCompleteEvent"),
             InternalEvent::SetNumericContext { key, value } => write!(f, "{}, {}", key, value),
+            InternalEvent::OnMarkerReached { name } => write!(f, "{}", name),
+            InternalEvent::OnFrameReached { frame } => write!(f, "{}", frame),
+            InternalEvent::OnLoopComplete => write!(f, "OnLoopCompleteEvent"),
+            InternalEvent::OnKeyDown { key } => match key {
+                Some(key) => write!(f, "{}", key),
+                None => write!(f, "OnKeyDownEvent"),
+            },
+            InternalEvent::OnKeyUp { key } => match key {
+                Some(key) => write!(f, "{}", key),
+                None => write!(f, "OnKeyUpEvent"),
+            },
+            InternalEvent::OnFocus => write!(f, "OnFocusEvent"),
+            InternalEvent::OnBlur => write!(f, "OnBlurEvent"),
+            InternalEvent::OnScroll => write!(f, "OnScrollEvent"),
+            InternalEvent::OnLongPress { target } => match target {
+                Some(target) => write!(f, "{}", target),
+                None => write!(f, "OnLongPressEvent"),
+            },
+            InternalEvent::OnDoubleClick { target } => match target {
+                Some(target) => write!(f, "{}", target),
+                None => write!(f, "OnDoubleClickEvent"),
+            },
+            InternalEvent::OnSwipe { direction } => match direction {
+                Some(direction) => write!(f, "{}", direction),
+                None => write!(f, "OnSwipeEvent"),
+            },
+            InternalEvent::After { .. } => write!(f, "AfterEvent"),
+            InternalEvent::Delay { .. } => write!(f, "DelayEvent"),
+            InternalEvent::Every { .. } => write!(f, "EveryEvent"),
         }
     }
 }
@@ -85,7 +169,9 @@ impl PointerEvent for Event {
             Event::OnPointerDown { x, .. }
             | Event::OnPointerUp { x, .. }
             | Event::OnPointerMove { x, .. }
-            | Event::OnPointerEnter { x, .. } => *x,
+            | Event::OnPointerEnter { x, .. }
+            | Event::OnLongPress { x, .. }
+            | Event::OnDoubleClick { x, .. } => *x,
             _ => 0.0,
         }
     }
@@ -95,7 +181,9 @@ impl PointerEvent for Event {
             Event::OnPointerDown { y, .. }
             | Event::OnPointerUp { y, .. }
             | Event::OnPointerMove { y, .. }
-            | Event::OnPointerEnter { y, .. } => *y,
+            | Event::OnPointerEnter { y, .. }
+            | Event::OnLongPress { y, .. }
+            | Event::OnDoubleClick { y, .. } => *y,
             _ => 0.0,
         }
     }
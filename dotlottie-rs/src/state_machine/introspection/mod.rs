@@ -0,0 +1,269 @@
+use serde::Serialize;
+
+use crate::listeners::ListenerTrait;
+use crate::parser::{serialize_without_nulls, ContextActionJson, TransitionGuardJson};
+use crate::states::StateTrait;
+use crate::transitions::TransitionTrait;
+use crate::{InternalEvent, StateMachine, StateMachineStatus};
+
+/// A state of a state machine.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StateInfo {
+    pub name: String,
+    /// The path of the state, see `StateMachineObserver`.
+    pub path: String,
+    /// "PlaybackState", "SyncState" or "GlobalState".
+    pub state_type: String,
+    pub active: bool,
+    /// The state as written in the definition, as JSON.
+    pub config: String,
+}
+
+/// An event a transition is taken on.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EventInfo {
+    /// The name of the `InternalEvent`, e.g. "String" or "OnPointerDown".
+    pub event_type: String,
+    /// The value, target, key, marker name, frame, direction, duration or interval of the
+    /// event. Empty when the event has none, or matches any.
+    pub value: String,
+}
+
+/// A transition of a state machine. The guards and actions are JSON arrays, as written in the
+/// definition.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransitionInfo {
+    pub from_state: String,
+    pub to_state: String,
+    pub event: EventInfo,
+    pub guards: String,
+    pub actions: String,
+}
+
+/// A listener of a state machine, the actions are a JSON array as written in the definition.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListenerInfo {
+    pub listener_type: String,
+    /// Empty when the listener has no target.
+    pub target: String,
+    pub actions: String,
+}
+
+/// A context variable of a state machine with its current value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextInfo {
+    pub key: String,
+    /// "Numeric", "String" or "Boolean".
+    pub context_type: String,
+    pub value: String,
+}
+
+impl From<&InternalEvent> for EventInfo {
+    fn from(event: &InternalEvent) -> Self {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+
+        let (event_type, value) = match event {
+            InternalEvent::Bool { value } => ("Bool", value.to_string()),
+            InternalEvent::String { value } => ("String", value.clone()),
+            InternalEvent::Numeric { value } => ("Numeric", value.to_string()),
+            InternalEvent::OnPointerDown { target } => ("OnPointerDown", optional(target)),
+            InternalEvent::OnPointerUp { target } => ("OnPointerUp", optional(target)),
+            InternalEvent::OnPointerMove { target } => ("OnPointerMove", optional(target)),
+            InternalEvent::OnPointerEnter { target } => ("OnPointerEnter", optional(target)),
+            InternalEvent::OnPointerExit { target } => ("OnPointerExit", optional(target)),
+            InternalEvent::OnComplete => ("OnComplete", String::new()),
+            InternalEvent::SetNumericContext { key, .. } => ("SetNumericContext", key.clone()),
+            InternalEvent::OnMarkerReached { name } => ("OnMarkerReached", name.clone()),
+            InternalEvent::OnFrameReached { frame } => ("OnFrameReached", frame.to_string()),
+            InternalEvent::OnLoopComplete => ("OnLoopComplete", String::new()),
+            InternalEvent::OnKeyDown { key } => ("OnKeyDown", optional(key)),
+            InternalEvent::OnKeyUp { key } => ("OnKeyUp", optional(key)),
+            InternalEvent::OnFocus => ("OnFocus", String::new()),
+            InternalEvent::OnBlur => ("OnBlur", String::new()),
+            InternalEvent::OnScroll => ("OnScroll", String::new()),
+            InternalEvent::OnLongPress { target } => ("OnLongPress", optional(target)),
+            InternalEvent::OnDoubleClick { target } => ("OnDoubleClick", optional(target)),
+            InternalEvent::OnSwipe { direction } => (
+                "OnSwipe",
+                direction
+                    .map(|direction| direction.to_string())
+                    .unwrap_or_default(),
+            ),
+            InternalEvent::After { duration } => ("After", duration.to_string()),
+            InternalEvent::Delay { duration } => ("Delay", duration.to_string()),
+            InternalEvent::Every { interval } => ("Every", interval.to_string()),
+        };
+
+        EventInfo {
+            event_type: event_type.to_string(),
+            value,
+        }
+    }
+}
+
+impl StateMachine {
+    /// All of the states, in the order of the definition.
+    pub fn describe_states(&self) -> Vec<StateInfo> {
+        self.states
+            .iter()
+            .enumerate()
+            .filter_map(|(index, state)| {
+                let state = state.read().ok()?;
+
+                let config = self
+                    .definition
+                    .as_ref()
+                    .and_then(|definition| definition.states.get(index))
+                    .and_then(|state| serialize_without_nulls(state).ok())
+                    .unwrap_or_default();
+
+                Some(StateInfo {
+                    name: state.get_name(),
+                    path: self.state_path(index),
+                    state_type: state.get_type(),
+                    active: self.active_states.contains(&index),
+                    config,
+                })
+            })
+            .collect()
+    }
+
+    /// All of the transitions, grouped by the state they leave from.
+    pub fn describe_transitions(&self) -> Vec<TransitionInfo> {
+        let mut transitions = Vec::new();
+
+        for (index, state) in self.states.iter().enumerate() {
+            let Ok(state) = state.read() else {
+                continue;
+            };
+
+            for transition in state.get_transitions() {
+                let Ok(transition) = transition.read() else {
+                    continue;
+                };
+
+                let guards: Vec<TransitionGuardJson> = transition
+                    .get_guards()
+                    .iter()
+                    .cloned()
+                    .map(Into::into)
+                    .collect();
+                let actions: Vec<ContextActionJson> = transition
+                    .get_actions()
+                    .iter()
+                    .cloned()
+                    .map(Into::into)
+                    .collect();
+
+                transitions.push(TransitionInfo {
+                    from_state: self.state_path(index),
+                    to_state: self.state_path(transition.get_target_state() as usize),
+                    event: EventInfo::from(&*transition.get_event().read().unwrap()),
+                    guards: serialize_without_nulls(&guards).unwrap_or_default(),
+                    actions: serialize_without_nulls(&actions).unwrap_or_default(),
+                });
+            }
+        }
+
+        transitions
+    }
+
+    pub fn describe_listeners(&self) -> Vec<ListenerInfo> {
+        self.listeners
+            .iter()
+            .filter_map(|listener| {
+                let listener = listener.read().ok()?;
+                let actions: Vec<ContextActionJson> = listener
+                    .get_actions()
+                    .iter()
+                    .cloned()
+                    .map(Into::into)
+                    .collect();
+
+                Some(ListenerInfo {
+                    listener_type: listener.get_type().to_string(),
+                    target: listener.get_target().unwrap_or_default(),
+                    actions: serialize_without_nulls(&actions).unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    /// All of the context variables with their current values, sorted by key.
+    pub fn describe_context(&self) -> Vec<ContextInfo> {
+        let numeric = self
+            .numeric_context
+            .iter()
+            .map(|(key, value)| (key, "Numeric", value.to_string()));
+        let string = self
+            .string_context
+            .iter()
+            .map(|(key, value)| (key, "String", value.clone()));
+        let bool = self
+            .bool_context
+            .iter()
+            .map(|(key, value)| (key, "Boolean", value.to_string()));
+
+        let mut context: Vec<ContextInfo> = numeric
+            .chain(string)
+            .chain(bool)
+            .map(|(key, context_type, value)| ContextInfo {
+                key: key.clone(),
+                context_type: context_type.to_string(),
+                value,
+            })
+            .collect();
+
+        context.sort_by(|a, b| a.key.cmp(&b.key));
+        context
+    }
+
+    /// The events that take a transition from the active states, or from the global state, with
+    /// the guards checked against the current context. Empty unless the state machine is
+    /// running. Pointer events are listed regardless of where the pointer is.
+    pub fn available_events(&self) -> Vec<EventInfo> {
+        if self.status != StateMachineStatus::Running || self.current_state.is_none() {
+            return Vec::new();
+        }
+
+        let global_index = self
+            .global_state
+            .as_ref()
+            .and_then(|state| self.index_of(state));
+        let mut sources: Vec<usize> = global_index.into_iter().collect();
+
+        for leaf in self.active_leaves() {
+            for index in std::iter::once(leaf).chain(self.ancestors(leaf)) {
+                if !sources.contains(&index) {
+                    sources.push(index);
+                }
+            }
+        }
+
+        let mut events = Vec::new();
+
+        for index in sources {
+            let Ok(state) = self.states[index].read() else {
+                continue;
+            };
+
+            for transition in state.get_transitions() {
+                let Ok(transition) = transition.read() else {
+                    continue;
+                };
+
+                if !self.verify_if_guards_are_met(transition.get_guards()) {
+                    continue;
+                }
+
+                let event = EventInfo::from(&*transition.get_event().read().unwrap());
+
+                if !events.contains(&event) {
+                    events.push(event);
+                }
+            }
+        }
+
+        events
+    }
+}
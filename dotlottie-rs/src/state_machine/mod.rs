@@ -1,37 +1,139 @@
-use std::collections::HashMap;
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 
+use serde::{Deserialize, Serialize};
+
+pub mod actions;
+pub mod builder;
+pub mod diagnostics;
 pub mod errors;
 pub mod events;
+pub mod introspection;
 pub mod listeners;
 pub mod parser;
 pub mod states;
 pub mod transitions;
 
-use crate::parser::StringNumberBool;
-use crate::state_machine::listeners::Listener;
-use crate::state_machine::states::StateTrait;
+use crate::parser::{ContextActionJson, StateActionJson, StringNumberBool};
+use crate::state_machine::listeners::{Listener, ListenerTrait, ListenerType};
+use crate::state_machine::states::{StateNode, StateTrait};
 use crate::state_machine::transitions::guard::Guard;
 use crate::state_machine::transitions::TransitionTrait;
-use crate::{Config, DotLottiePlayerContainer, InternalEvent, Layout, Mode, PointerEvent};
-
-use self::parser::{state_machine_parse, ContextJsonType};
-use self::{errors::StateMachineError, events::Event, states::State, transitions::Transition};
-
+use crate::{
+    AnimationTransition, Config, DotLottiePlayerContainer, Easing, InternalEvent, Layout, Mode,
+    PointerEvent, TransitionDirection, TransitionEffect,
+};
+use crate::{ContextAction, StateAction};
+
+use self::parser::{
+    state_machine_parse, state_machine_serialize, AnimationTransitionJson,
+    AnimationTransitionJsonType, ContextJsonType, EasingJson, StateMachineJson,
+    TransitionDirectionJson,
+};
+use self::{
+    errors::StateMachineError,
+    events::{Event, GestureRecognizer},
+    introspection::EventInfo,
+    states::State,
+    transitions::Transition,
+};
+
+/// States are reported by their path, the names of the state and of the states containing it
+/// separated by '/', e.g. "toggle/on/hover". The path of a state at the top level is its name.
 pub trait StateMachineObserver: Send + Sync {
     fn on_transition(&self, previous_state: String, new_state: String);
     fn on_state_entered(&self, entering_state: String);
     fn on_state_exit(&self, leaving_state: String);
+    /// An URL, sound or log action of a state being entered or exited, for the host to perform.
+    fn on_action(&self, action: StateAction) {
+        let _ = action;
+    }
+    /// A context variable was restored to its initial value by the `reset_context` of a state.
+    fn on_context_reset(&self, key: String) {
+        let _ = key;
+    }
+    /// A context variable was changed by the host, an action or a reset. The values are
+    /// formatted as in `ContextInfo`, the old value is None for a new variable.
+    fn on_context_changed(&self, key: String, old_value: Option<String>, new_value: String) {
+        let _ = (key, old_value, new_value);
+    }
+    /// An event matched transitions of the active states, but none of them were taken because
+    /// their guards weren't met. Reported once for each event of the rejected transitions.
+    fn on_event_rejected(&self, event: EventInfo) {
+        let _ = event;
+    }
+    /// A listener was triggered by an input event. The target is empty for listeners without
+    /// one.
+    fn on_listener_triggered(&self, listener_type: String, target: String) {
+        let _ = (listener_type, target);
+    }
+    /// A `FireCustomEvent` action of a state being entered or exited.
+    fn on_custom_event(&self, event: String) {
+        let _ = event;
+    }
+    /// Something went wrong while running the state machine, e.g. the animation of a state
+    /// couldn't be loaded.
+    fn on_error(&self, message: String) {
+        let _ = message;
+    }
 }
 
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum StateMachineStatus {
     Running,
     Paused,
     Stopped,
 }
 
+/// The runtime state of a state machine, to be restored into a state machine created from the
+/// same definition. States are identified by their path, so reordering the states of the
+/// definition doesn't break existing snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateMachineSnapshot {
+    /// The id of the descriptor of the definition.
+    pub id: String,
+    pub status: StateMachineStatus,
+    /// The paths of the active states, parents before their children.
+    pub active_states: Vec<String>,
+    pub current_state: Option<String>,
+    pub numeric_context: BTreeMap<String, f32>,
+    pub string_context: BTreeMap<String, String>,
+    pub bool_context: BTreeMap<String, bool>,
+}
+
+/// What handling events asked of the animation. Returned by `post_event`, `process_events` and
+/// `update`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventResult {
+    /// The events were handled, the animation carries on as it was.
+    Success,
+    /// The state machine isn't running, or the events didn't take any transition.
+    Failure,
+    /// The animation of the entered state was played.
+    Play,
+    /// The animation of the entered state was paused.
+    Pause,
+    /// The frame of a sync state was set, a new frame needs to be drawn.
+    DrawFrame,
+    /// The state machine is busy handling another event, the event is handled right after it.
+    Queued,
+}
+
+impl EventResult {
+    // The result of handling a sequence of events, the last one that did something
+    pub(crate) fn then(self, next: EventResult) -> EventResult {
+        match next {
+            EventResult::Failure => self,
+            _ => next,
+        }
+    }
+}
+
+// The indices of the source and target states of a matched transition, and the transition
+type MatchedTransition = (usize, usize, Option<Arc<RwLock<Transition>>>);
+
 pub struct StateMachine {
     pub global_state: Option<Arc<RwLock<State>>>,
     pub states: Vec<Arc<RwLock<State>>>,
@@ -44,7 +146,34 @@ pub struct StateMachine {
     string_context: HashMap<String, String>,
     bool_context: HashMap<String, bool>,
 
+    // the context_variables values of the definition, restored by the reset_context of a state
+    initial_numeric_context: HashMap<String, f32>,
+    initial_string_context: HashMap<String, String>,
+    initial_bool_context: HashMap<String, bool>,
+
+    // the animation transition of the last taken transition, applied when entering its target
+    animation_transition: Option<AnimationTransition>,
+
+    // the parent, children, initial child and parallel flag of each state, by index
+    hierarchy: Vec<StateNode>,
+    // the indices of the active states, parents before their children
+    active_states: Vec<usize>,
+
+    // player clock times in seconds at which each active state was entered, the last event was
+    // posted and the timed transitions were last evaluated
+    state_entered_at: HashMap<usize, f64>,
+    last_event_at: f64,
+    last_update_at: f64,
+
+    gestures: GestureRecognizer,
+
+    // the events posted and not handled yet, in the order they were posted
+    event_queue: VecDeque<Event>,
+
     observers: RwLock<Vec<Arc<dyn StateMachineObserver>>>,
+
+    // the definition the state machine was created from, exported by to_json
+    definition: Option<StateMachineJson>,
 }
 
 impl Default for StateMachine {
@@ -58,8 +187,20 @@ impl Default for StateMachine {
             numeric_context: HashMap::new(),
             string_context: HashMap::new(),
             bool_context: HashMap::new(),
+            initial_numeric_context: HashMap::new(),
+            initial_string_context: HashMap::new(),
+            initial_bool_context: HashMap::new(),
             status: StateMachineStatus::Stopped,
+            animation_transition: None,
+            hierarchy: Vec::new(),
+            active_states: Vec::new(),
+            state_entered_at: HashMap::new(),
+            last_event_at: 0.0,
+            last_update_at: 0.0,
+            gestures: GestureRecognizer::default(),
+            event_queue: VecDeque::new(),
             observers: RwLock::new(Vec::new()),
+            definition: None,
         }
     }
 }
@@ -78,8 +219,20 @@ impl StateMachine {
             numeric_context: HashMap::new(),
             string_context: HashMap::new(),
             bool_context: HashMap::new(),
+            initial_numeric_context: HashMap::new(),
+            initial_string_context: HashMap::new(),
+            initial_bool_context: HashMap::new(),
             status: StateMachineStatus::Stopped,
+            animation_transition: None,
+            hierarchy: Vec::new(),
+            active_states: Vec::new(),
+            state_entered_at: HashMap::new(),
+            last_event_at: 0.0,
+            last_update_at: 0.0,
+            gestures: GestureRecognizer::default(),
+            event_queue: VecDeque::new(),
             observers: RwLock::new(Vec::new()),
+            definition: None,
         };
 
         state_machine.create_state_machine(state_machine_definition, &player)
@@ -110,16 +263,49 @@ impl StateMachine {
     }
 
     pub fn set_numeric_context(&mut self, key: &str, value: f32) {
-        self.numeric_context.insert(key.to_string(), value);
+        let old_value = self.numeric_context.insert(key.to_string(), value);
+
+        if old_value != Some(value) {
+            self.notify_context_changed(
+                key,
+                old_value.map(|value| value.to_string()),
+                value.to_string(),
+            );
+        }
     }
 
     pub fn set_string_context(&mut self, key: &str, value: &str) {
-        self.string_context
+        let old_value = self
+            .string_context
             .insert(key.to_string(), value.to_string());
+
+        if old_value.as_deref() != Some(value) {
+            self.notify_context_changed(key, old_value, value.to_string());
+        }
     }
 
     pub fn set_bool_context(&mut self, key: &str, value: bool) {
-        self.bool_context.insert(key.to_string(), value);
+        let old_value = self.bool_context.insert(key.to_string(), value);
+
+        if old_value != Some(value) {
+            self.notify_context_changed(
+                key,
+                old_value.map(|value| value.to_string()),
+                value.to_string(),
+            );
+        }
+    }
+
+    fn notify_context_changed(&self, key: &str, old_value: Option<String>, new_value: String) {
+        self.observers.read().unwrap().iter().for_each(|observer| {
+            observer.on_context_changed(key.to_string(), old_value.clone(), new_value.clone());
+        });
+    }
+
+    fn notify_error(&self, message: String) {
+        self.observers.read().unwrap().iter().for_each(|observer| {
+            observer.on_error(message.clone());
+        });
     }
 
     // Parses the JSON of the state machine definition and creates the states and transitions
@@ -128,397 +314,501 @@ impl StateMachine {
         sm_definition: &str,
         player: &Rc<RwLock<DotLottiePlayerContainer>>,
     ) -> Result<StateMachine, StateMachineError> {
-        let parsed_state_machine = state_machine_parse(sm_definition);
+        let parsed_state_machine = state_machine_parse(sm_definition)?;
 
-        // todo somehow get the context json without having to parse it again
-        // self.json_context = Some(
-        //     state_machine_parse(sm_definition)
-        //         .unwrap()
-        //         .context_variables,
-        // );
+        self.create_from_definition(parsed_state_machine, player)
+    }
+
+    // Creates the states and transitions of a parsed state machine definition
+    pub(crate) fn create_from_definition(
+        &mut self,
+        parsed_state_machine: StateMachineJson,
+        player: &Rc<RwLock<DotLottiePlayerContainer>>,
+    ) -> Result<StateMachine, StateMachineError> {
+        let definition = parsed_state_machine.clone();
 
         let mut states: Vec<Arc<RwLock<State>>> = Vec::new();
         let mut global_state: Option<Arc<RwLock<State>>> = None;
         let mut listeners: Vec<Arc<RwLock<Listener>>> = Vec::new();
         let mut new_state_machine = StateMachine::default();
-
-        match parsed_state_machine {
-            Ok(parsed_state_machine) => {
-                // Loop through result json states and create objects for each
-                for state in parsed_state_machine.states {
-                    match state {
-                        parser::StateJson::PlaybackState {
-                            name,
-                            animation_id,
-                            r#loop,
-                            autoplay,
-                            mode,
-                            speed,
-                            segment,
-                            background_color,
-                            use_frame_interpolation,
-                            reset_context,
-                            marker,
-                            ..
-                        } => {
-                            let unwrapped_mode = mode.unwrap_or("Forward".to_string());
-                            let mode = {
-                                match unwrapped_mode.as_str() {
-                                    "Forward" => Mode::Forward,
-                                    "Reverse" => Mode::Reverse,
-                                    "Bounce" => Mode::Bounce,
-                                    "ReverseBounce" => Mode::ReverseBounce,
-                                    _ => Mode::Forward,
-                                }
-                            };
-
-                            let default_config = Config::default();
-
-                            // Fill out a config with the state's values, if absent use default config values
-                            let playback_config = Config {
-                                mode,
-                                loop_animation: r#loop.unwrap_or(default_config.loop_animation),
-                                speed: speed.unwrap_or(default_config.speed),
-                                use_frame_interpolation: use_frame_interpolation
-                                    .unwrap_or(default_config.use_frame_interpolation),
-                                autoplay: autoplay.unwrap_or(default_config.autoplay),
-                                segment: segment.unwrap_or(default_config.segment),
-                                background_color: background_color
-                                    .unwrap_or(default_config.background_color),
-                                layout: Layout::default(),
-                                marker: marker.unwrap_or(default_config.marker),
-                            };
-
-                            // Construct a State with the values we've gathered
-                            let new_playback_state = State::Playback {
-                                name,
-                                config: playback_config,
-                                reset_context: reset_context.unwrap_or("".to_string()),
-                                animation_id: animation_id.unwrap_or("".to_string()),
-                                transitions: Vec::new(),
-                            };
-
-                            states.push(Arc::new(RwLock::new(new_playback_state)));
-                        }
-                        parser::StateJson::SyncState {
-                            name,
-                            animation_id,
-                            background_color,
-                            reset_context,
-                            frame_context_key,
-                            segment,
-                            ..
-                        } => {
-                            let mut config = Config::default();
-
-                            config.background_color =
-                                background_color.unwrap_or(config.background_color);
-                            config.segment = segment.unwrap_or(config.segment);
-
-                            let new_sync_state = State::Sync {
-                                name,
-                                frame_context_key,
-                                reset_context: reset_context.unwrap_or("".to_string()),
-                                animation_id: animation_id.unwrap_or("".to_string()),
-                                transitions: Vec::new(),
-                                config,
-                            };
-
-                            states.push(Arc::new(RwLock::new(new_sync_state)));
+        // the parent, initial child and parallel flag of each state
+        let mut nesting: Vec<(Option<u32>, Option<u32>, bool)> = Vec::new();
+        let mut global_state_index = None;
+
+        let state_names: Vec<String> = parsed_state_machine
+            .states
+            .iter()
+            .map(|state| state.name().to_string())
+            .collect();
+
+        // Loop through result json states and create objects for each
+        for state in parsed_state_machine.states {
+            match state {
+                parser::StateJson::PlaybackState {
+                    name,
+                    animation_id,
+                    r#loop,
+                    autoplay,
+                    mode,
+                    speed,
+                    segment,
+                    background_color,
+                    use_frame_interpolation,
+                    reset_context,
+                    marker,
+                    entry_actions,
+                    exit_actions,
+                    parent,
+                    initial,
+                    parallel,
+                } => {
+                    let unwrapped_mode = mode.unwrap_or("Forward".to_string());
+                    let mode = {
+                        match unwrapped_mode.as_str() {
+                            "Forward" => Mode::Forward,
+                            "Reverse" => Mode::Reverse,
+                            "Bounce" => Mode::Bounce,
+                            "ReverseBounce" => Mode::ReverseBounce,
+                            _ => Mode::Forward,
                         }
-                        parser::StateJson::GlobalState {
-                            name,
-                            reset_context,
-                            entry_actions: _,
-                            exit_actions: _,
-                        } => {
-                            let new_global_state = State::Global {
-                                name,
-                                reset_context: reset_context.unwrap_or("".to_string()),
-                                transitions: Vec::new(),
-                            };
+                    };
+
+                    let default_config = Config::default();
+
+                    // Fill out a config with the state's values, if absent use default config values
+                    let playback_config = Config {
+                        mode,
+                        loop_animation: r#loop.unwrap_or(default_config.loop_animation),
+                        speed: speed.unwrap_or(default_config.speed),
+                        use_frame_interpolation: use_frame_interpolation
+                            .unwrap_or(default_config.use_frame_interpolation),
+                        autoplay: autoplay.unwrap_or(default_config.autoplay),
+                        segment: segment.unwrap_or(default_config.segment),
+                        background_color: background_color
+                            .unwrap_or(default_config.background_color),
+                        layout: Layout::default(),
+                        marker: marker.unwrap_or(default_config.marker),
+                    };
+
+                    // Construct a State with the values we've gathered
+                    let new_playback_state = State::Playback {
+                        name,
+                        config: playback_config,
+                        reset_context: reset_context.unwrap_or("".to_string()),
+                        animation_id: animation_id.unwrap_or("".to_string()),
+                        transitions: Vec::new(),
+                        entry_actions: state_actions(entry_actions),
+                        exit_actions: state_actions(exit_actions),
+                    };
+
+                    states.push(Arc::new(RwLock::new(new_playback_state)));
+                    nesting.push((
+                        parent
+                            .map(|parent| parent.resolve(&state_names))
+                            .transpose()?,
+                        initial
+                            .map(|initial| initial.resolve(&state_names))
+                            .transpose()?,
+                        parallel.unwrap_or(false),
+                    ));
+                }
+                parser::StateJson::SyncState {
+                    name,
+                    animation_id,
+                    background_color,
+                    reset_context,
+                    frame_context_key,
+                    segment,
+                    entry_actions,
+                    exit_actions,
+                    parent,
+                    initial,
+                    parallel,
+                } => {
+                    let mut config = Config::default();
+
+                    config.background_color = background_color.unwrap_or(config.background_color);
+                    config.segment = segment.unwrap_or(config.segment);
+
+                    let new_sync_state = State::Sync {
+                        name,
+                        frame_context_key,
+                        reset_context: reset_context.unwrap_or("".to_string()),
+                        animation_id: animation_id.unwrap_or("".to_string()),
+                        transitions: Vec::new(),
+                        entry_actions: state_actions(entry_actions),
+                        exit_actions: state_actions(exit_actions),
+                        config,
+                    };
+
+                    states.push(Arc::new(RwLock::new(new_sync_state)));
+                    nesting.push((
+                        parent
+                            .map(|parent| parent.resolve(&state_names))
+                            .transpose()?,
+                        initial
+                            .map(|initial| initial.resolve(&state_names))
+                            .transpose()?,
+                        parallel.unwrap_or(false),
+                    ));
+                }
+                parser::StateJson::GlobalState {
+                    name,
+                    reset_context,
+                    entry_actions,
+                    exit_actions,
+                } => {
+                    let new_global_state = State::Global {
+                        name,
+                        reset_context: reset_context.unwrap_or("".to_string()),
+                        transitions: Vec::new(),
+                        entry_actions: state_actions(entry_actions),
+                        exit_actions: state_actions(exit_actions),
+                    };
+
+                    let locked_global_state = Arc::new(RwLock::new(new_global_state));
+
+                    global_state = Some(locked_global_state.clone());
+                    global_state_index = Some(states.len());
+
+                    states.push(locked_global_state);
+                    nesting.push((None, None, false));
+                }
+            }
+        }
 
-                            let locked_global_state = Arc::new(RwLock::new(new_global_state));
+        let hierarchy = state_hierarchy(&nesting, global_state_index)?;
+
+        // Loop through result transitions and create objects for each
+        for transition in parsed_state_machine.transitions {
+            match transition {
+                parser::TransitionJson::Transition {
+                    from_state,
+                    to_state,
+                    guards,
+                    numeric_event,
+                    string_event,
+                    boolean_event,
+                    on_complete_event,
+                    on_pointer_down_event,
+                    on_pointer_up_event,
+                    on_pointer_enter_event,
+                    on_pointer_exit_event,
+                    on_pointer_move_event,
+                    on_key_down_event,
+                    on_key_up_event,
+                    on_focus_event,
+                    on_blur_event,
+                    on_scroll_event,
+                    on_long_press_event,
+                    on_double_click_event,
+                    on_swipe_event,
+                    on_marker_reached_event,
+                    on_frame_reached_event,
+                    on_loop_complete_event,
+                    after_event,
+                    delay_event,
+                    every_event,
+                    animation_transition,
+                    actions,
+                } => {
+                    let from_state = from_state.resolve(&state_names)?;
+                    let target_state_index = to_state.resolve(&state_names)?;
+                    let mut guards_for_transition: Vec<Guard> = Vec::new();
+
+                    // Use the provided index to get the state in the vec we've built
+                    if target_state_index >= states.len() as u32 {
+                        return Err(StateMachineError::ParsingError {
+                            reason: "Transition has an invalid target state index value!"
+                                .to_string(),
+                        });
+                    }
 
-                            global_state = Some(locked_global_state.clone());
+                    // Loop through transition guards and create equivalent Guard objects
+                    if guards.is_some() {
+                        let guards = guards.unwrap();
 
-                            states.push(locked_global_state);
+                        for guard in guards {
+                            guards_for_transition.push(Guard::try_from(guard)?);
                         }
                     }
-                }
-
-                // Loop through result transitions and create objects for each
-                for transition in parsed_state_machine.transitions {
-                    match transition {
-                        parser::TransitionJson::Transition {
-                            from_state,
-                            to_state,
-                            guards,
-                            numeric_event,
-                            string_event,
-                            boolean_event,
-                            on_complete_event,
-                            on_pointer_down_event,
-                            on_pointer_up_event,
-                            on_pointer_enter_event,
-                            on_pointer_exit_event,
-                            on_pointer_move_event,
-                        } => {
-                            let target_state_index = to_state;
-                            let mut guards_for_transition: Vec<Guard> = Vec::new();
-
-                            // Use the provided index to get the state in the vec we've built
-                            if target_state_index >= states.len() as u32 {
-                                return Err(StateMachineError::ParsingError {
-                                    reason: "Transition has an invalid target state index value!"
-                                        .to_string(),
-                                });
-                            }
-
-                            // Loop through transition guards and create equivalent Guard objects
-                            if guards.is_some() {
-                                let guards = guards.unwrap();
 
-                                for guard in guards {
-                                    let new_guard = Guard {
-                                        context_key: guard.context_key,
-                                        condition_type: guard.condition_type,
-                                        compare_to: guard.compare_to,
-                                    };
-
-                                    guards_for_transition.push(new_guard);
-                                }
-                            }
+                    // let mut new_transition: Option<Transition> = None;
+                    let mut state_to_attach_to: i32 = -1;
+                    let mut new_event: Option<InternalEvent> = None;
 
-                            // let mut new_transition: Option<Transition> = None;
-                            let mut state_to_attach_to: i32 = -1;
-                            let mut new_event: Option<InternalEvent> = None;
-
-                            // Capture which event this transition has
-                            if numeric_event.is_some() {
-                                let numeric_event = numeric_event.unwrap();
-                                new_event = Some(InternalEvent::Numeric {
-                                    value: numeric_event.value,
-                                });
-                                state_to_attach_to = from_state as i32;
-                            } else if string_event.is_some() {
-                                let string_event = string_event.unwrap();
-                                new_event = Some(InternalEvent::String {
-                                    value: string_event.value,
-                                });
-                                state_to_attach_to = from_state as i32;
-                            } else if boolean_event.is_some() {
-                                let boolean_event = boolean_event.unwrap();
-                                new_event = Some(InternalEvent::Bool {
-                                    value: boolean_event.value,
-                                });
-                                state_to_attach_to = from_state as i32;
-                            } else if on_complete_event.is_some() {
-                                new_event = Some(InternalEvent::OnComplete);
-                                state_to_attach_to = from_state as i32;
-                            } else if on_pointer_down_event.is_some() {
-                                // Default to 0.0 0.0 coordinates
-                                let pointer_down_event = on_pointer_down_event.unwrap();
-
-                                if pointer_down_event.target.is_some() {
-                                    new_event = Some(InternalEvent::OnPointerDown {
-                                        target: pointer_down_event.target,
-                                    });
-                                } else {
-                                    new_event = Some(InternalEvent::OnPointerDown { target: None });
-                                }
+                    // Capture which event this transition has
+                    if numeric_event.is_some() {
+                        let numeric_event = numeric_event.unwrap();
+                        new_event = Some(InternalEvent::Numeric {
+                            value: numeric_event.value,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if string_event.is_some() {
+                        let string_event = string_event.unwrap();
+                        new_event = Some(InternalEvent::String {
+                            value: string_event.value,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if boolean_event.is_some() {
+                        let boolean_event = boolean_event.unwrap();
+                        new_event = Some(InternalEvent::Bool {
+                            value: boolean_event.value,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if on_complete_event.is_some() {
+                        new_event = Some(InternalEvent::OnComplete);
+                        state_to_attach_to = from_state as i32;
+                    } else if on_pointer_down_event.is_some() {
+                        // Default to 0.0 0.0 coordinates
+                        let pointer_down_event = on_pointer_down_event.unwrap();
+
+                        if pointer_down_event.target.is_some() {
+                            new_event = Some(InternalEvent::OnPointerDown {
+                                target: pointer_down_event.target,
+                            });
+                        } else {
+                            new_event = Some(InternalEvent::OnPointerDown { target: None });
+                        }
 
-                                state_to_attach_to = from_state as i32;
-                            } else if on_pointer_up_event.is_some() {
-                                // Default to 0.0 0.0 coordinates
+                        state_to_attach_to = from_state as i32;
+                    } else if on_pointer_up_event.is_some() {
+                        // Default to 0.0 0.0 coordinates
 
-                                let pointer_up_event = on_pointer_up_event.unwrap();
+                        let pointer_up_event = on_pointer_up_event.unwrap();
 
-                                if pointer_up_event.target.is_some() {
-                                    new_event = Some(InternalEvent::OnPointerUp {
-                                        target: pointer_up_event.target,
-                                    });
-                                } else {
-                                    new_event = Some(InternalEvent::OnPointerUp { target: None });
-                                }
+                        if pointer_up_event.target.is_some() {
+                            new_event = Some(InternalEvent::OnPointerUp {
+                                target: pointer_up_event.target,
+                            });
+                        } else {
+                            new_event = Some(InternalEvent::OnPointerUp { target: None });
+                        }
 
-                                state_to_attach_to = from_state as i32;
-                            } else if on_pointer_enter_event.is_some() {
-                                // Default to 0.0 0.0 coordinates
-                                let pointer_enter_event = on_pointer_enter_event.unwrap();
+                        state_to_attach_to = from_state as i32;
+                    } else if on_pointer_enter_event.is_some() {
+                        // Default to 0.0 0.0 coordinates
+                        let pointer_enter_event = on_pointer_enter_event.unwrap();
 
-                                if pointer_enter_event.target.is_some() {
-                                    new_event = Some(InternalEvent::OnPointerEnter {
-                                        target: pointer_enter_event.target,
-                                    });
-                                } else {
-                                    new_event =
-                                        Some(InternalEvent::OnPointerEnter { target: None });
-                                }
+                        if pointer_enter_event.target.is_some() {
+                            new_event = Some(InternalEvent::OnPointerEnter {
+                                target: pointer_enter_event.target,
+                            });
+                        } else {
+                            new_event = Some(InternalEvent::OnPointerEnter { target: None });
+                        }
 
-                                state_to_attach_to = from_state as i32;
-                            } else if on_pointer_exit_event.is_some() {
-                                // Default to 0.0 0.0 coordinates
-                                let pointer_exit_event = on_pointer_exit_event.unwrap();
+                        state_to_attach_to = from_state as i32;
+                    } else if on_pointer_exit_event.is_some() {
+                        // Default to 0.0 0.0 coordinates
+                        let pointer_exit_event = on_pointer_exit_event.unwrap();
 
-                                if pointer_exit_event.target.is_some() {
-                                    new_event = Some(InternalEvent::OnPointerExit {
-                                        target: pointer_exit_event.target,
-                                    });
-                                } else {
-                                    new_event = Some(InternalEvent::OnPointerExit { target: None });
-                                }
+                        if pointer_exit_event.target.is_some() {
+                            new_event = Some(InternalEvent::OnPointerExit {
+                                target: pointer_exit_event.target,
+                            });
+                        } else {
+                            new_event = Some(InternalEvent::OnPointerExit { target: None });
+                        }
 
-                                state_to_attach_to = from_state as i32;
-                            } else if on_pointer_move_event.is_some() {
-                                // Default to 0.0 0.0 coordinates
-                                let pointer_move_event = on_pointer_move_event.unwrap();
+                        state_to_attach_to = from_state as i32;
+                    } else if on_pointer_move_event.is_some() {
+                        // Default to 0.0 0.0 coordinates
+                        let pointer_move_event = on_pointer_move_event.unwrap();
 
-                                if pointer_move_event.target.is_some() {
-                                    new_event = Some(InternalEvent::OnPointerMove {
-                                        target: pointer_move_event.target,
-                                    });
-                                } else {
-                                    new_event = Some(InternalEvent::OnPointerMove { target: None });
-                                }
+                        if pointer_move_event.target.is_some() {
+                            new_event = Some(InternalEvent::OnPointerMove {
+                                target: pointer_move_event.target,
+                            });
+                        } else {
+                            new_event = Some(InternalEvent::OnPointerMove { target: None });
+                        }
 
-                                state_to_attach_to = from_state as i32;
-                            }
-                            if let Some(event) = new_event {
-                                let new_transition = Transition::Transition {
-                                    target_state: target_state_index,
-                                    event: Arc::new(RwLock::new(event)),
-                                    guards: guards_for_transition,
-                                };
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(key_down_event) = on_key_down_event {
+                        new_event = Some(InternalEvent::OnKeyDown {
+                            key: key_down_event.key,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(key_up_event) = on_key_up_event {
+                        new_event = Some(InternalEvent::OnKeyUp {
+                            key: key_up_event.key,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if on_focus_event.is_some() {
+                        new_event = Some(InternalEvent::OnFocus);
+                        state_to_attach_to = from_state as i32;
+                    } else if on_blur_event.is_some() {
+                        new_event = Some(InternalEvent::OnBlur);
+                        state_to_attach_to = from_state as i32;
+                    } else if on_scroll_event.is_some() {
+                        new_event = Some(InternalEvent::OnScroll);
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(long_press_event) = on_long_press_event {
+                        new_event = Some(InternalEvent::OnLongPress {
+                            target: long_press_event.target,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(double_click_event) = on_double_click_event {
+                        new_event = Some(InternalEvent::OnDoubleClick {
+                            target: double_click_event.target,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(swipe_event) = on_swipe_event {
+                        new_event = Some(InternalEvent::OnSwipe {
+                            direction: swipe_event.direction,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(marker_event) = on_marker_reached_event {
+                        new_event = Some(InternalEvent::OnMarkerReached {
+                            name: marker_event.name,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(frame_event) = on_frame_reached_event {
+                        new_event = Some(InternalEvent::OnFrameReached {
+                            frame: frame_event.frame,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if on_loop_complete_event.is_some() {
+                        new_event = Some(InternalEvent::OnLoopComplete);
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(after_event) = after_event {
+                        new_event = Some(InternalEvent::After {
+                            duration: after_event.duration,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(delay_event) = delay_event {
+                        new_event = Some(InternalEvent::Delay {
+                            duration: delay_event.duration,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    } else if let Some(every_event) = every_event {
+                        new_event = Some(InternalEvent::Every {
+                            interval: every_event.interval,
+                        });
+                        state_to_attach_to = from_state as i32;
+                    }
+                    if let Some(event) = new_event {
+                        let new_transition = Transition::Transition {
+                            target_state: target_state_index,
+                            event: Arc::new(RwLock::new(event)),
+                            guards: guards_for_transition,
+                            animation_transition: animation_transition
+                                .as_ref()
+                                .map(animation_transition_from_json),
+                            actions: context_actions(actions),
+                        };
 
-                                // Since the target is valid and transition created, we attach it to the state
-                                if state_to_attach_to < states.len() as i32 {
-                                    let try_write_state =
-                                        states[state_to_attach_to as usize].try_write();
+                        // Since the target is valid and transition created, we attach it to the state
+                        if state_to_attach_to < states.len() as i32 {
+                            let try_write_state = states[state_to_attach_to as usize].try_write();
 
-                                    try_write_state
-                                        .map_err(|_| StateMachineError::ParsingError {
-                                            reason: "Failed to write to state".to_string(),
-                                        })?
-                                        .add_transition(new_transition);
-                                }
-                            }
+                            try_write_state
+                                .map_err(|_| StateMachineError::ParsingError {
+                                    reason: "Failed to write to state".to_string(),
+                                })?
+                                .add_transition(new_transition);
                         }
                     }
                 }
+            }
+        }
 
-                for listener in parsed_state_machine.listeners {
-                    match listener.r#type {
-                        parser::ListenerJsonType::PointerUp => {
-                            let new_listener = Listener::PointerUp {
-                                r#type: listeners::ListenerType::PointerUp,
-                                target: listener.target,
-                                action: listener.action,
-                                value: listener.value,
-                                context_key: listener.context_key,
-                            };
-
-                            listeners.push(Arc::new(RwLock::new(new_listener)));
-                        }
-                        parser::ListenerJsonType::PointerDown => {
-                            let new_listener = Listener::PointerDown {
-                                r#type: listeners::ListenerType::PointerDown,
-                                target: listener.target,
-                                action: listener.action,
-                                value: listener.value,
-                                context_key: listener.context_key,
-                            };
-
-                            listeners.push(Arc::new(RwLock::new(new_listener)));
-                        }
-                        parser::ListenerJsonType::PointerEnter => {
-                            let new_listener = Listener::PointerEnter {
-                                r#type: listeners::ListenerType::PointerEnter,
-                                target: listener.target,
-                                action: listener.action,
-                                value: listener.value,
-                                context_key: listener.context_key,
-                            };
-
-                            listeners.push(Arc::new(RwLock::new(new_listener)));
-                        }
-                        parser::ListenerJsonType::PointerExit => {
-                            let new_listener = Listener::PointerExit {
-                                r#type: listeners::ListenerType::PointerExit,
-                                target: listener.target,
-                                action: listener.action,
-                                value: listener.value,
-                                context_key: listener.context_key,
-                            };
-
-                            listeners.push(Arc::new(RwLock::new(new_listener)));
-                        }
-                        parser::ListenerJsonType::PointerMove => {
-                            let new_listener = Listener::PointerMove {
-                                r#type: listeners::ListenerType::PointerMove,
-                                target: listener.target,
-                                action: listener.action,
-                                value: listener.value,
-                                context_key: listener.context_key,
-                            };
+        for listener in parsed_state_machine.listeners {
+            let new_listener = Listener {
+                r#type: listener.r#type.into(),
+                target: listener.target,
+                action: listener.action,
+                value: listener.value,
+                context_key: listener.context_key,
+                actions: context_actions(listener.actions),
+            };
+
+            listeners.push(Arc::new(RwLock::new(new_listener)));
+        }
 
-                            listeners.push(Arc::new(RwLock::new(new_listener)));
-                        }
+        // Since value can either be a string, int or bool, we need to check the type and set the context accordingly
+        for variable in parsed_state_machine.context_variables {
+            match variable.r#type {
+                ContextJsonType::Numeric => {
+                    if let StringNumberBool::F32(value) = variable.value {
+                        new_state_machine.set_numeric_context(&variable.key, value);
                     }
                 }
-
-                // Since value can either be a string, int or bool, we need to check the type and set the context accordingly
-                for variable in parsed_state_machine.context_variables {
-                    match variable.r#type {
-                        ContextJsonType::Numeric => {
-                            if let StringNumberBool::F32(value) = variable.value {
-                                new_state_machine.set_numeric_context(&variable.key, value);
-                            }
-                        }
-                        ContextJsonType::String => {
-                            if let StringNumberBool::String(value) = variable.value {
-                                new_state_machine.set_string_context(&variable.key, value.as_str());
-                            }
-                        }
-                        ContextJsonType::Boolean => {
-                            if let StringNumberBool::Bool(value) = variable.value {
-                                new_state_machine.set_bool_context(&variable.key, value);
-                            }
-                        }
+                ContextJsonType::String => {
+                    if let StringNumberBool::String(value) = variable.value {
+                        new_state_machine.set_string_context(&variable.key, value.as_str());
                     }
                 }
+                ContextJsonType::Boolean => {
+                    if let StringNumberBool::Bool(value) = variable.value {
+                        new_state_machine.set_bool_context(&variable.key, value);
+                    }
+                }
+            }
+        }
 
-                let mut initial_state = None;
-
-                // All states and transitions have been created, we can set the state machine's initial state
-                let initial_state_index = parsed_state_machine.descriptor.initial;
+        let mut initial_state = None;
 
-                if initial_state_index < states.len() as u32 {
-                    initial_state = Some(states[initial_state_index as usize].clone());
-                }
+        // All states and transitions have been created, we can set the state machine's initial state
+        let initial_state_index = parsed_state_machine
+            .descriptor
+            .initial
+            .resolve(&state_names)?;
 
-                new_state_machine = StateMachine {
-                    global_state,
-                    states,
-                    listeners,
-                    current_state: initial_state,
-                    player: Some(player.clone()),
-                    numeric_context: new_state_machine.numeric_context,
-                    string_context: new_state_machine.string_context,
-                    bool_context: new_state_machine.bool_context,
-                    status: StateMachineStatus::Stopped,
-                    observers: RwLock::new(Vec::new()),
-                };
-
-                Ok(new_state_machine)
-            }
-            Err(error) => Err(error),
+        if initial_state_index < states.len() as u32 {
+            initial_state = Some(states[initial_state_index as usize].clone());
         }
+
+        new_state_machine = StateMachine {
+            global_state,
+            states,
+            listeners,
+            current_state: initial_state,
+            player: Some(player.clone()),
+            hierarchy,
+            initial_numeric_context: new_state_machine.numeric_context.clone(),
+            initial_string_context: new_state_machine.string_context.clone(),
+            initial_bool_context: new_state_machine.bool_context.clone(),
+            numeric_context: new_state_machine.numeric_context,
+            string_context: new_state_machine.string_context,
+            bool_context: new_state_machine.bool_context,
+            status: StateMachineStatus::Stopped,
+            animation_transition: None,
+            active_states: Vec::new(),
+            state_entered_at: HashMap::new(),
+            last_event_at: 0.0,
+            last_update_at: 0.0,
+            gestures: GestureRecognizer::default(),
+            event_queue: VecDeque::new(),
+            observers: RwLock::new(Vec::new()),
+            definition: Some(definition),
+        };
+
+        Ok(new_state_machine)
     }
 
     pub fn start(&mut self) {
         self.status = StateMachineStatus::Running;
-        self.execute_current_state();
+
+        let initial_state = self
+            .current_state
+            .as_ref()
+            .and_then(|state| self.index_of(state));
+
+        match initial_state {
+            Some(index) => {
+                self.active_states.clear();
+                self.state_entered_at.clear();
+
+                let path = self.path_to(index, None);
+                self.enter_path(&path, false);
+                self.current_state = self.leaf_state();
+            }
+            None => {
+                self.execute_current_state();
+            }
+        }
     }
 
     pub fn pause(&mut self) {
@@ -529,6 +819,121 @@ impl StateMachine {
         self.status = StateMachineStatus::Stopped;
     }
 
+    /// The definition the state machine was created from, as JSON. States added or removed
+    /// afterwards aren't part of it.
+    pub fn to_json(&self) -> Result<String, StateMachineError> {
+        match &self.definition {
+            Some(definition) => state_machine_serialize(definition),
+            None => Err(StateMachineError::ParsingError {
+                reason: "The state machine wasn't created from a definition".to_string(),
+            }),
+        }
+    }
+
+    pub fn snapshot(&self) -> StateMachineSnapshot {
+        StateMachineSnapshot {
+            id: self
+                .definition
+                .as_ref()
+                .map(|definition| definition.descriptor.id.clone())
+                .unwrap_or_default(),
+            status: self.status,
+            active_states: self
+                .active_states
+                .iter()
+                .map(|index| self.state_path(*index))
+                .collect(),
+            current_state: self.get_current_state_path(),
+            numeric_context: self.numeric_context.clone().into_iter().collect(),
+            string_context: self.string_context.clone().into_iter().collect(),
+            bool_context: self.bool_context.clone().into_iter().collect(),
+        }
+    }
+
+    /// Puts the state machine back into the state of a snapshot, without notifying the
+    /// observers or performing entry actions. The playback settings of the active states are
+    /// applied to the player unless the snapshot was taken while stopped.
+    pub fn restore(&mut self, snapshot: &StateMachineSnapshot) -> Result<(), StateMachineError> {
+        let id = self
+            .definition
+            .as_ref()
+            .map(|definition| definition.descriptor.id.as_str());
+
+        if id != Some(snapshot.id.as_str()) {
+            return Err(StateMachineError::SnapshotError {
+                reason: format!(
+                    "The snapshot was taken from the state machine '{}'!",
+                    snapshot.id
+                ),
+            });
+        }
+
+        // States sharing a name can't be told apart by their path
+        let index_of_path = |path: &str| {
+            let mut matches =
+                (0..self.states.len()).filter(|index| self.state_path(*index) == path);
+
+            match (matches.next(), matches.next()) {
+                (Some(index), None) => Ok(index),
+                (None, _) => Err(StateMachineError::SnapshotError {
+                    reason: format!("There is no state '{}'!", path),
+                }),
+                (Some(_), Some(_)) => Err(StateMachineError::SnapshotError {
+                    reason: format!("Several states are at '{}'!", path),
+                }),
+            }
+        };
+
+        let active_states = snapshot
+            .active_states
+            .iter()
+            .map(|path| index_of_path(path))
+            .collect::<Result<Vec<usize>, StateMachineError>>()?;
+        let current_state = snapshot
+            .current_state
+            .as_deref()
+            .map(index_of_path)
+            .transpose()?;
+
+        self.numeric_context = snapshot.numeric_context.clone().into_iter().collect();
+        self.string_context = snapshot.string_context.clone().into_iter().collect();
+        self.bool_context = snapshot.bool_context.clone().into_iter().collect();
+
+        self.status = snapshot.status;
+        self.active_states = active_states;
+        self.current_state = current_state.map(|index| self.states[index].clone());
+        self.animation_transition = None;
+        self.gestures = GestureRecognizer::default();
+        self.state_entered_at.clear();
+
+        // Timed transitions start over from the restore
+        if let Some(now) = self.clock_time() {
+            for index in &self.active_states {
+                self.state_entered_at.insert(*index, now);
+            }
+
+            self.last_event_at = now;
+            self.last_update_at = now;
+        }
+
+        if self.status != StateMachineStatus::Stopped {
+            let leaves = if self.active_states.is_empty() {
+                self.current_state.clone().into_iter().collect()
+            } else {
+                self.active_leaves()
+                    .into_iter()
+                    .map(|index| self.states[index].clone())
+                    .collect::<Vec<_>>()
+            };
+
+            for state in leaves {
+                self.execute_state(&state);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn set_initial_state(&mut self, state: Arc<RwLock<State>>) {
         self.current_state = Some(state);
     }
@@ -537,6 +942,37 @@ impl StateMachine {
         self.current_state.clone()
     }
 
+    /// The names of the current state and of the states containing it, from the top level and
+    /// separated by '/', e.g. "toggle/on/hover".
+    pub fn get_current_state_path(&self) -> Option<String> {
+        let state = self.current_state.as_ref()?;
+
+        match self.index_of(state) {
+            Some(index) => Some(self.state_path(index)),
+            None => state.read().ok().map(|state| state.get_name()),
+        }
+    }
+
+    /// The paths of the innermost active states, one for each active parallel region.
+    pub fn get_active_state_paths(&self) -> Vec<String> {
+        self.active_leaves()
+            .into_iter()
+            .map(|index| self.state_path(index))
+            .collect()
+    }
+
+    pub fn get_state_node(&self, index: usize) -> Option<&StateNode> {
+        self.hierarchy.get(index)
+    }
+
+    /// The state with the given name, the first one if several states share it.
+    pub fn get_state_by_name(&self, name: &str) -> Option<Arc<RwLock<State>>> {
+        self.states
+            .iter()
+            .find(|state| state.read().is_ok_and(|state| state.get_name() == name))
+            .cloned()
+    }
+
     pub fn add_state(&mut self, state: Arc<RwLock<State>>) {
         self.states.push(state);
     }
@@ -545,78 +981,542 @@ impl StateMachine {
         &self.listeners
     }
 
-    // Return codes
-    // 0: Success
-    // 1: Failure
-    // 2: Play animation
-    // 3: Pause animation
-    // 4: Request and draw a new single frame of the animation (needed for sync state)
-    pub fn execute_current_state(&mut self) -> i32 {
-        if self.current_state.is_none() {
-            return 1;
+    pub fn execute_current_state(&mut self) -> EventResult {
+        match self.current_state.clone() {
+            Some(state) => self.execute_state(&state),
+            None => EventResult::Failure,
         }
+    }
 
-        // Check if current_state is not None and execute the state
-        if let Some(ref state) = self.current_state {
-            let unwrapped_state = state.read().unwrap();
-            let reset_key = unwrapped_state.get_reset_context_key();
-
-            if !reset_key.is_empty() {
-                if reset_key == "*" {
-                    // Todo dont clear reset to their original values from file
-                    // self.numeric_context.clear();
-                    // self.string_context.clear();
-                    // self.bool_context.clear();
-                } else {
-                    if self.numeric_context.contains_key(reset_key) {
-                        // self.numeric_context.remove(reset_key);
-                    }
+    fn execute_state(&mut self, state: &Arc<RwLock<State>>) -> EventResult {
+        let unwrapped_state = state.read().unwrap();
 
-                    if self.string_context.contains_key(reset_key) {
-                        // self.string_context.remove(reset_key);
-                    }
+        let Some(player) = self.player.clone() else {
+            return EventResult::Failure;
+        };
 
-                    if self.bool_context.contains_key(reset_key) {
-                        // self.bool_context.remove(reset_key);
-                    }
-                }
+        let result = unwrapped_state.execute(
+            &player,
+            &self.string_context,
+            &self.bool_context,
+            &self.numeric_context,
+            self.animation_transition.take().as_ref(),
+        );
+
+        // The active animation is cleared when loading the animation of the state fails
+        if let Some(animation_id) = unwrapped_state.get_animation_id() {
+            let loaded = player
+                .try_read()
+                .map_or(true, |player| player.active_animation_id() == *animation_id);
+
+            if !animation_id.is_empty() && !loaded {
+                let path = self
+                    .index_of(state)
+                    .map(|index| self.state_path(index))
+                    .unwrap_or_else(|| unwrapped_state.get_name());
+
+                self.notify_error(format!(
+                    "Failed to load animation '{}' of state '{}'",
+                    animation_id, path
+                ));
             }
+        }
+
+        result
+    }
+
+    // Enter the first state of the path and go on with the rest of it, then into the initial
+    // children of its last state. Every region of a parallel state is entered, in order, so the
+    // last one drives the player
+    fn enter_path(&mut self, path: &[usize], notify: bool) -> EventResult {
+        let Some((&index, rest)) = path.split_first() else {
+            return EventResult::Failure;
+        };
+
+        let mut result = self.enter_state(index, notify);
+        let node = self.hierarchy.get(index).cloned().unwrap_or_default();
 
-            if self.player.is_some() {
-                return unwrapped_state.execute(
-                    self.player.as_mut().unwrap(),
-                    &self.string_context,
-                    &self.bool_context,
-                    &self.numeric_context,
-                );
+        let children = if node.parallel {
+            node.children
+        } else if let Some(next) = rest.first() {
+            vec![*next]
+        } else {
+            node.initial
+                .or(node.children.first().copied())
+                .into_iter()
+                .collect()
+        };
+
+        for child in children {
+            let child_result = if rest.first() == Some(&child) {
+                self.enter_path(rest, notify)
             } else {
-                return 1;
+                self.enter_path(&[child], notify)
+            };
+
+            result = result.then(child_result);
+        }
+
+        result
+    }
+
+    // Reset the context of a state, execute it unless it's a compound state whose children play
+    // instead, then run its entry actions
+    fn enter_state(&mut self, index: usize, notify: bool) -> EventResult {
+        let state = self.states[index].clone();
+
+        self.active_states.push(index);
+
+        if notify {
+            let path = self.state_path(index);
+
+            self.observers.read().unwrap().iter().for_each(|observer| {
+                observer.on_state_entered(path.clone());
+            });
+        }
+
+        let reset_key = state
+            .read()
+            .map(|state| state.get_reset_context_key().clone())
+            .unwrap_or_default();
+
+        if !reset_key.is_empty() {
+            self.reset_context(&reset_key);
+        }
+
+        if let Some(now) = self.clock_time() {
+            self.state_entered_at.insert(index, now);
+            self.last_event_at = now;
+            self.last_update_at = now;
+        }
+
+        let result = if self.is_compound(index) {
+            EventResult::Failure
+        } else {
+            self.execute_state(&state)
+        };
+
+        if let Ok(state) = state.read() {
+            self.perform_actions(state.get_entry_actions());
+        }
+
+        result
+    }
+
+    fn exit_state(&mut self, index: usize) {
+        let path = self.state_path(index);
+
+        self.observers.read().unwrap().iter().for_each(|observer| {
+            observer.on_state_exit(path.clone());
+        });
+
+        if let Ok(state) = self.states[index].read() {
+            self.perform_actions(state.get_exit_actions());
+        }
+
+        self.active_states.retain(|active| *active != index);
+        self.state_entered_at.remove(&index);
+    }
+
+    fn index_of(&self, state: &Arc<RwLock<State>>) -> Option<usize> {
+        self.states
+            .iter()
+            .position(|other| Arc::ptr_eq(other, state))
+    }
+
+    fn parent_of(&self, index: usize) -> Option<usize> {
+        self.hierarchy.get(index).and_then(|node| node.parent)
+    }
+
+    // The states containing a state, from its parent up
+    fn ancestors(&self, index: usize) -> Vec<usize> {
+        let mut ancestors = Vec::new();
+        let mut parent = self.parent_of(index);
+
+        while let Some(index) = parent {
+            ancestors.push(index);
+            parent = self.parent_of(index);
+        }
+
+        ancestors
+    }
+
+    fn is_descendant(&self, index: usize, ancestor: usize) -> bool {
+        self.ancestors(index).contains(&ancestor)
+    }
+
+    fn is_compound(&self, index: usize) -> bool {
+        self.hierarchy
+            .get(index)
+            .map_or(false, |node| !node.children.is_empty())
+    }
+
+    fn state_path(&self, index: usize) -> String {
+        let mut path: Vec<usize> = self.ancestors(index);
+        path.reverse();
+        path.push(index);
+
+        path.iter()
+            .map(|index| {
+                self.states[*index]
+                    .read()
+                    .map(|state| state.get_name())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+
+    // The states to enter for reaching the target from the domain, which isn't entered again
+    fn path_to(&self, target: usize, domain: Option<usize>) -> Vec<usize> {
+        let mut path: Vec<usize> = self
+            .ancestors(target)
+            .into_iter()
+            .take_while(|ancestor| Some(*ancestor) != domain)
+            .collect();
+
+        path.reverse();
+        path.push(target);
+
+        path
+    }
+
+    // The innermost compound state containing both the source and the target, the top level when
+    // None. A parallel state is left and entered again as a whole
+    fn transition_domain(&self, source: Option<usize>, target: usize) -> Option<usize> {
+        source.and_then(|source| {
+            self.ancestors(source).into_iter().find(|ancestor| {
+                self.is_descendant(target, *ancestor) && !self.hierarchy[*ancestor].parallel
+            })
+        })
+    }
+
+    // Whether a state was left by a transition with one of the domains
+    fn is_within_domains(&self, index: usize, domains: &[Option<usize>]) -> bool {
+        domains.iter().any(|domain| match domain {
+            Some(domain) => self.is_descendant(index, *domain),
+            None => true,
+        })
+    }
+
+    // The active states without children, region by region
+    fn active_leaves(&self) -> Vec<usize> {
+        let mut leaves = Vec::new();
+
+        for index in &self.active_states {
+            if self.parent_of(*index).is_none() {
+                self.collect_active_leaves(*index, &mut leaves);
+            }
+        }
+
+        leaves
+    }
+
+    fn collect_active_leaves(&self, index: usize, leaves: &mut Vec<usize>) {
+        if !self.is_compound(index) {
+            leaves.push(index);
+            return;
+        }
+
+        for child in &self.hierarchy[index].children {
+            if self.active_states.contains(child) {
+                self.collect_active_leaves(*child, leaves);
             }
         }
+    }
+
+    // The innermost active state entered last, the one driving the player when there are
+    // parallel regions
+    fn leaf_state(&self) -> Option<Arc<RwLock<State>>> {
+        let leaves = self.active_leaves();
+
+        self.active_states
+            .iter()
+            .rev()
+            .find(|index| leaves.contains(index))
+            .map(|index| self.states[*index].clone())
+    }
+
+    // The first state from the leaf up with a matching transition, with the index of its target
+    // and the matched transition. Compound states pass their transitions down to their children
+    fn find_transition<F>(&self, leaf: usize, mut evaluate: F) -> Option<MatchedTransition>
+    where
+        F: FnMut(usize, &[Arc<RwLock<Transition>>]) -> (i32, Option<Arc<RwLock<Transition>>>),
+    {
+        std::iter::once(leaf)
+            .chain(self.ancestors(leaf))
+            .find_map(|index| {
+                let state = self.states.get(index)?.read().ok()?;
+                let (target, matched_transition) = evaluate(index, state.get_transitions());
+
+                (target > -1).then_some((index, target as usize, matched_transition))
+            })
+    }
+
+    // Restore a context variable, or all of them for "*", to its value in the definition
+    fn reset_context(&mut self, reset_key: &str) {
+        let is_reset = |key: &String| reset_key == "*" || reset_key == key;
+        let mut changed_keys = Vec::new();
+
+        let numeric: Vec<(String, f32)> = self
+            .initial_numeric_context
+            .iter()
+            .filter(|(key, _)| is_reset(key))
+            .map(|(key, value)| (key.clone(), *value))
+            .collect();
+
+        for (key, value) in numeric {
+            if self.numeric_context.get(&key) != Some(&value) {
+                self.set_numeric_context(&key, value);
+                changed_keys.push(key);
+            }
+        }
+
+        let string: Vec<(String, String)> = self
+            .initial_string_context
+            .iter()
+            .filter(|(key, _)| is_reset(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        for (key, value) in string {
+            if self.string_context.get(&key) != Some(&value) {
+                self.set_string_context(&key, &value);
+                changed_keys.push(key);
+            }
+        }
+
+        let bool: Vec<(String, bool)> = self
+            .initial_bool_context
+            .iter()
+            .filter(|(key, _)| is_reset(key))
+            .map(|(key, value)| (key.clone(), *value))
+            .collect();
+
+        for (key, value) in bool {
+            if self.bool_context.get(&key) != Some(&value) {
+                self.set_bool_context(&key, value);
+                changed_keys.push(key);
+            }
+        }
+
+        let observers = self.observers.read().unwrap();
+
+        for key in changed_keys {
+            observers.iter().for_each(|observer| {
+                observer.on_context_reset(key.clone());
+            });
+        }
+    }
+
+    fn current_state_is_sync(&self) -> bool {
+        match &self.current_state {
+            Some(state) => matches!(state.read().as_deref(), Ok(State::Sync { .. })),
+            None => false,
+        }
+    }
+
+    // Take a transition of the source state, or of the global state when None: leave the active
+    // states within the transition's domain, innermost first, then enter the target state
+    fn transition_to(
+        &mut self,
+        source: Option<usize>,
+        target: usize,
+        matched_transition: Option<Arc<RwLock<Transition>>>,
+    ) -> EventResult {
+        let domain = self.transition_domain(source, target);
+
+        let exited: Vec<usize> = self
+            .active_states
+            .iter()
+            .copied()
+            .filter(|index| domain.map_or(true, |domain| self.is_descendant(*index, domain)))
+            .collect();
+
+        let previous_state = match exited.iter().find(|index| !self.is_compound(**index)) {
+            Some(index) => self.state_path(*index),
+            None => self.get_current_state_path().unwrap_or_default(),
+        };
+        let new_state = self.state_path(target);
+
+        // Emit transtion occured event
+        self.observers.read().unwrap().iter().for_each(|observer| {
+            observer.on_transition(previous_state.clone(), new_state.clone());
+        });
+
+        for index in exited.into_iter().rev() {
+            self.exit_state(index);
+        }
+
+        self.take_transition(matched_transition);
+
+        let path = self.path_to(target, domain);
+        let result = self.enter_path(&path, true);
+
+        self.current_state = self
+            .leaf_state()
+            .or_else(|| Some(self.states[target].clone()));
 
-        0
+        result
     }
 
-    fn verify_if_guards_are_met(&self, guard: &Guard) -> bool {
-        match guard.compare_to {
-            StringNumberBool::String(_) => {
-                if guard.string_context_is_satisfied(&self.string_context) {
-                    return true;
+    // Run the context actions of the taken transition and keep its animation transition for
+    // entering the target state
+    fn take_transition(&mut self, transition: Option<Arc<RwLock<Transition>>>) {
+        if let Some(transition) = transition {
+            if let Ok(transition) = transition.read() {
+                self.perform_context_actions(transition.get_actions());
+                self.animation_transition = transition.get_animation_transition().cloned();
+            }
+        }
+    }
+
+    // Run the context actions of the listeners triggered by an input event, returns true if
+    // any listener was triggered
+    fn trigger_listeners(&mut self, event: &Event) -> bool {
+        let mut actions = Vec::new();
+        let mut triggered_listeners = Vec::new();
+
+        for listener in &self.listeners {
+            let Ok(listener) = listener.read() else {
+                continue;
+            };
+
+            let triggered = match (listener.get_type(), event) {
+                (ListenerType::PointerDown, Event::OnPointerDown { x, y })
+                | (ListenerType::PointerUp, Event::OnPointerUp { x, y })
+                | (ListenerType::PointerMove, Event::OnPointerMove { x, y })
+                | (ListenerType::PointerEnter, Event::OnPointerEnter { x, y }) => {
+                    match listener.get_target() {
+                        Some(target) => self.perform_hit_check(&target, *x, *y),
+                        None => true,
+                    }
+                }
+                (ListenerType::PointerExit, Event::OnPointerExit { x, y }) => {
+                    match listener.get_target() {
+                        Some(target) => !self.perform_hit_check(&target, *x, *y),
+                        None => true,
+                    }
                 }
+                (ListenerType::LongPress, Event::OnLongPress { x, y })
+                | (ListenerType::DoubleClick, Event::OnDoubleClick { x, y }) => {
+                    match listener.get_target() {
+                        Some(target) => self.perform_hit_check(&target, *x, *y),
+                        None => true,
+                    }
+                }
+                // The target of keyboard listeners is the key, of swipe listeners the direction
+                (ListenerType::KeyDown, Event::OnKeyDown { key })
+                | (ListenerType::KeyUp, Event::OnKeyUp { key }) => {
+                    listener.get_target().map_or(true, |target| target == *key)
+                }
+                (ListenerType::Swipe, Event::OnSwipe { direction }) => listener
+                    .get_target()
+                    .map_or(true, |target| target == direction.to_string()),
+                (ListenerType::Focus, Event::OnFocus)
+                | (ListenerType::Blur, Event::OnBlur)
+                | (ListenerType::Scroll, Event::OnScroll { .. }) => true,
+                _ => false,
+            };
+
+            if triggered {
+                actions.extend(listener.get_actions().iter().cloned());
+                triggered_listeners.push((
+                    listener.get_type().to_string(),
+                    listener.get_target().unwrap_or_default(),
+                ));
             }
-            StringNumberBool::F32(_) => {
-                if guard.numeric_context_is_satisfied(&self.numeric_context) {
-                    return true;
+        }
+
+        for (listener_type, target) in triggered_listeners {
+            self.observers.read().unwrap().iter().for_each(|observer| {
+                observer.on_listener_triggered(listener_type.clone(), target.clone());
+            });
+        }
+
+        self.perform_context_actions(&actions);
+
+        !actions.is_empty()
+    }
+
+    fn perform_context_actions(&mut self, actions: &[ContextAction]) {
+        for action in actions {
+            match action {
+                ContextAction::Set { context_key, value } => match value {
+                    StringNumberBool::F32(value) => self.set_numeric_context(context_key, *value),
+                    StringNumberBool::String(value) => self.set_string_context(context_key, value),
+                    StringNumberBool::Bool(value) => self.set_bool_context(context_key, *value),
+                },
+                ContextAction::Increment { context_key, value } => {
+                    if let Some(current) = self.get_numeric_context(context_key) {
+                        self.set_numeric_context(context_key, current + value);
+                    }
+                }
+                ContextAction::Decrement { context_key, value } => {
+                    if let Some(current) = self.get_numeric_context(context_key) {
+                        self.set_numeric_context(context_key, current - value);
+                    }
+                }
+                ContextAction::Toggle { context_key } => {
+                    if let Some(current) = self.get_bool_context(context_key) {
+                        self.set_bool_context(context_key, !current);
+                    }
+                }
+                ContextAction::Clamp {
+                    context_key,
+                    min,
+                    max,
+                } => {
+                    if let Some(current) = self.get_numeric_context(context_key) {
+                        self.set_numeric_context(context_key, current.max(*min).min(*max));
+                    }
+                }
+                ContextAction::Append { context_key, value } => {
+                    if let Some(mut current) = self.get_string_context(context_key) {
+                        current.push_str(value);
+                        self.set_string_context(context_key, &current);
+                    }
                 }
             }
-            StringNumberBool::Bool(_) => {
-                if guard.bool_context_is_satisfied(&self.bool_context) {
-                    return true;
+        }
+    }
+
+    // Apply the theme actions and pass the other actions to the observers
+    fn perform_actions(&self, actions: &[StateAction]) {
+        for action in actions {
+            match action {
+                StateAction::Theme { theme_id, .. } => {
+                    if let Some(player) = &self.player {
+                        if let Ok(player) = player.try_read() {
+                            if !player.load_theme(theme_id) {
+                                self.notify_error(format!("Failed to load theme '{}'", theme_id));
+                            }
+                        }
+                    }
+                }
+                StateAction::FireCustomEvent { event } => {
+                    self.observers.read().unwrap().iter().for_each(|observer| {
+                        observer.on_custom_event(event.clone());
+                    });
+                }
+                _ => {
+                    self.observers.read().unwrap().iter().for_each(|observer| {
+                        observer.on_action(action.clone());
+                    });
                 }
             }
         }
+    }
 
-        false
+    // The guards of a transition are met when all of them are
+    fn verify_if_guards_are_met(&self, guards: &[Guard]) -> bool {
+        guards.iter().all(|guard| {
+            guard.is_satisfied(
+                &self.numeric_context,
+                &self.string_context,
+                &self.bool_context,
+            )
+        })
     }
 
     fn perform_hit_check(&self, target: &str, x: f32, y: f32) -> bool {
@@ -642,18 +1542,33 @@ impl StateMachine {
         }
     }
 
-    fn evaluate_transition(&self, transitions: &[Arc<RwLock<Transition>>], event: &Event) -> i32 {
+    // Returns the index of the target state, -1 if no transition matched, and the matched
+    // transition. The events of the transitions whose guards weren't met are added to rejected
+    fn evaluate_transition(
+        &self,
+        transitions: &[Arc<RwLock<Transition>>],
+        event: &Event,
+        rejected: &mut Vec<InternalEvent>,
+    ) -> (i32, Option<Arc<RwLock<Transition>>>) {
         let mut tmp_state: i32 = -1;
+        let mut matched_transition = None;
         let iter = transitions.iter();
 
-        for transition in iter {
-            let unwrapped_transition = transition.read().unwrap();
+        for locked_transition in iter {
+            let unwrapped_transition = locked_transition.read().unwrap();
             let target_state = unwrapped_transition.get_target_state();
             let transition = &*unwrapped_transition;
             let event_lock = transition.get_event();
             let event_data = event_lock.read().unwrap();
             let transition_event = &*event_data;
             let transition_guards = transition.get_guards();
+            let mut matched = false;
+            let guards_failed = Cell::new(false);
+            let guards_met = || {
+                let met = self.verify_if_guards_are_met(transition_guards);
+                guards_failed.set(!met);
+                met
+            };
 
             // Match the transition's event type and compare it to the received event
             match transition_event {
@@ -662,15 +1577,13 @@ impl StateMachine {
 
                     if let Event::Bool { value } = event {
                         if *value == *bool_value {
-                            // If there are guards loop over them and check if theyre verified
+                            // If there are guards check that they're all verified
                             if !transition_guards.is_empty() {
-                                for guard in transition_guards {
-                                    if self.verify_if_guards_are_met(guard) {
-                                        tmp_state = target_state as i32;
-                                    }
+                                if guards_met() {
+                                    matched = true;
                                 }
                             } else {
-                                tmp_state = target_state as i32;
+                                matched = true;
                             }
                         }
                     }
@@ -680,15 +1593,13 @@ impl StateMachine {
 
                     if let Event::String { value } = event {
                         if string_value == value {
-                            // If there are guards loop over them and check if theyre verified
+                            // If there are guards check that they're all verified
                             if !transition_guards.is_empty() {
-                                for guard in transition_guards {
-                                    if self.verify_if_guards_are_met(guard) {
-                                        tmp_state = target_state as i32;
-                                    }
+                                if guards_met() {
+                                    matched = true;
                                 }
                             } else {
-                                tmp_state = target_state as i32;
+                                matched = true;
                             }
                         }
                     }
@@ -698,85 +1609,75 @@ impl StateMachine {
 
                     if let Event::Numeric { value } = event {
                         if *value == *num_value {
-                            // If there are guards loop over them and check if theyre verified
+                            // If there are guards check that they're all verified
                             if !transition_guards.is_empty() {
-                                for guard in transition_guards {
-                                    if self.verify_if_guards_are_met(guard) {
-                                        tmp_state = target_state as i32;
-                                    }
+                                if guards_met() {
+                                    matched = true;
                                 }
                             } else {
-                                tmp_state = target_state as i32;
+                                matched = true;
                             }
                         }
                     }
                 }
                 InternalEvent::OnComplete => {
                     if let Event::OnComplete = event {
-                        // If there are guards loop over them and check if theyre verified
+                        // If there are guards check that they're all verified
                         if !transition_guards.is_empty() {
-                            for guard in transition_guards {
-                                if self.verify_if_guards_are_met(guard) {
-                                    tmp_state = target_state as i32;
-                                }
+                            if guards_met() {
+                                matched = true;
                             }
                         } else {
-                            tmp_state = target_state as i32;
+                            matched = true;
                         }
                     }
                 }
                 // This is checking the state machine's event, not the passed event
                 InternalEvent::OnPointerDown { target } => {
                     if let Event::OnPointerDown { x, y } = event {
-                        // If there are guards loop over them and check if theyre verified
+                        // If there are guards check that they're all verified
                         if !transition_guards.is_empty() {
-                            for guard in transition_guards {
-                                if self.verify_if_guards_are_met(guard) {
-                                    tmp_state = target_state as i32;
-                                }
+                            if guards_met() {
+                                matched = true;
                             }
                         } else if target.is_some() && self.player.is_some() {
                             if self.perform_hit_check(target.as_ref().unwrap(), *x, *y) {
-                                tmp_state = target_state as i32;
+                                matched = true;
                             }
                         } else {
-                            tmp_state = target_state as i32;
+                            matched = true;
                         }
                     }
                 }
                 InternalEvent::OnPointerUp { target } => {
                     if let Event::OnPointerUp { x, y } = event {
-                        // If there are guards loop over them and check if theyre verified
+                        // If there are guards check that they're all verified
                         if !transition_guards.is_empty() {
-                            for guard in transition_guards {
-                                if self.verify_if_guards_are_met(guard) {
-                                    tmp_state = target_state as i32;
-                                }
+                            if guards_met() {
+                                matched = true;
                             }
                         } else if target.is_some() && self.player.is_some() {
                             if self.perform_hit_check(target.as_ref().unwrap(), *x, *y) {
-                                tmp_state = target_state as i32;
+                                matched = true;
                             }
                         } else {
-                            tmp_state = target_state as i32;
+                            matched = true;
                         }
                     }
                 }
                 InternalEvent::OnPointerMove { target } => {
                     if let Event::OnPointerMove { x, y } = event {
-                        // If there are guards loop over them and check if theyre verified
+                        // If there are guards check that they're all verified
                         if !transition_guards.is_empty() {
-                            for guard in transition_guards {
-                                if self.verify_if_guards_are_met(guard) {
-                                    tmp_state = target_state as i32;
-                                }
+                            if guards_met() {
+                                matched = true;
                             }
                         } else if target.is_some() && self.player.is_some() {
                             if self.perform_hit_check(target.as_ref().unwrap(), *x, *y) {
-                                tmp_state = target_state as i32;
+                                matched = true;
                             }
                         } else {
-                            tmp_state = target_state as i32;
+                            matched = true;
                         }
                     }
                 }
@@ -793,12 +1694,10 @@ impl StateMachine {
                         _ => {}
                     }
 
-                    // If there are guards loop over them and check if theyre verified
+                    // If there are guards check that they're all verified
                     if !transition_guards.is_empty() {
-                        for guard in transition_guards {
-                            if self.verify_if_guards_are_met(guard) {
-                                tmp_state = target_state as i32;
-                            }
+                        if guards_met() {
+                            matched = true;
                         }
                     } else if target.is_some() && self.player.is_some() {
                         if self.perform_hit_check(
@@ -811,10 +1710,10 @@ impl StateMachine {
                                     if let Ok(state) = current_state.read() {
                                         state.get_name()
                                     } else {
-                                        return -1;
+                                        return (-1, None);
                                     }
                                 } else {
-                                    return -1;
+                                    return (-1, None);
                                 };
 
                             let target_state_name = if let Some(target_state) =
@@ -823,19 +1722,19 @@ impl StateMachine {
                                 if let Ok(state) = target_state.read() {
                                     state.get_name()
                                 } else {
-                                    return 1; // Handle read lock error
+                                    return (1, None); // Handle read lock error
                                 }
                             } else {
-                                return 1; // Handle invalid index
+                                return (1, None); // Handle invalid index
                             };
 
                             // This prevent the state from transitioning to itself over and over again
                             if current_state_name != target_state_name {
-                                tmp_state = target_state as i32;
+                                matched = true;
                             }
                         }
                     } else {
-                        tmp_state = target_state as i32;
+                        matched = true;
                     }
                 }
                 InternalEvent::OnPointerExit { target } => {
@@ -851,12 +1750,10 @@ impl StateMachine {
                         _ => {}
                     }
 
-                    // If there are guards loop over them and check if theyre verified
+                    // If there are guards check that they're all verified
                     if !transition_guards.is_empty() {
-                        for guard in transition_guards {
-                            if self.verify_if_guards_are_met(guard) {
-                                tmp_state = target_state as i32;
-                            }
+                        if guards_met() {
+                            matched = true;
                         }
                     } else if target.is_some() && self.player.is_some() {
                         // Check if current state is the target state
@@ -864,10 +1761,10 @@ impl StateMachine {
                             if let Ok(state) = current_state.read() {
                                 state.get_name()
                             } else {
-                                return -1;
+                                return (-1, None);
                             }
                         } else {
-                            return -1;
+                            return (-1, None);
                         };
 
                         if current_state_name == *target.as_ref().unwrap()
@@ -877,7 +1774,7 @@ impl StateMachine {
                                 received_event_values.y(),
                             )
                         {
-                            tmp_state = target_state as i32;
+                            matched = true;
                         }
                     } else {
                         // Check if coordinates are outside of the player
@@ -888,30 +1785,337 @@ impl StateMachine {
                             || received_event_values.y() < 0.0
                             || received_event_values.y() > height as f32
                         {
-                            tmp_state = target_state as i32;
+                            matched = true;
                         }
                     }
                 }
                 InternalEvent::SetNumericContext { key: _, value: _ } => {}
+                InternalEvent::OnKeyDown { key } => {
+                    if let Event::OnKeyDown { key: pressed } = event {
+                        if key.as_ref().map_or(true, |key| key == pressed) && guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnKeyUp { key } => {
+                    if let Event::OnKeyUp { key: released } = event {
+                        if key.as_ref().map_or(true, |key| key == released) && guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnFocus => {
+                    if let Event::OnFocus = event {
+                        if guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnBlur => {
+                    if let Event::OnBlur = event {
+                        if guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnScroll => {
+                    if let Event::OnScroll { .. } = event {
+                        if guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnLongPress { target } => {
+                    if let Event::OnLongPress { x, y } = event {
+                        let hit = match target {
+                            Some(target) => self.perform_hit_check(target, *x, *y),
+                            None => true,
+                        };
+
+                        if hit && guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnDoubleClick { target } => {
+                    if let Event::OnDoubleClick { x, y } = event {
+                        let hit = match target {
+                            Some(target) => self.perform_hit_check(target, *x, *y),
+                            None => true,
+                        };
+
+                        if hit && guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnSwipe { direction } => {
+                    if let Event::OnSwipe { direction: swiped } = event {
+                        if direction.map_or(true, |direction| direction == *swiped) && guards_met()
+                        {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnMarkerReached { name } => {
+                    if let Event::OnMarkerReached { name: reached } = event {
+                        if name == reached && guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnFrameReached { frame } => {
+                    if let Event::OnFrameReached { frame: reached } = event {
+                        if frame == reached && guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                InternalEvent::OnLoopComplete => {
+                    if let Event::OnLoopComplete = event {
+                        if guards_met() {
+                            matched = true;
+                        }
+                    }
+                }
+                // Timed transitions are evaluated by update
+                InternalEvent::After { .. }
+                | InternalEvent::Delay { .. }
+                | InternalEvent::Every { .. } => {}
+            }
+
+            if matched {
+                tmp_state = target_state as i32;
+                matched_transition = Some(locked_transition.clone());
+            }
+
+            if guards_failed.get() {
+                rejected.push(transition_event.clone());
+            }
+        }
+
+        (tmp_state, matched_transition)
+    }
+
+    /// Take the after, delay and every transitions that are due at the player's clock time.
+    ///
+    /// Called by the player when rendering.
+    pub fn update(&mut self) -> EventResult {
+        if self.status != StateMachineStatus::Running || self.current_state.is_none() {
+            return EventResult::Failure;
+        }
+
+        let Some(now) = self.clock_time() else {
+            return EventResult::Failure;
+        };
+
+        // The clock was replaced by one reading an earlier time, keep the elapsed times
+        if now < self.last_update_at {
+            let shift = now - self.last_update_at;
+
+            self.state_entered_at
+                .values_mut()
+                .for_each(|entered_at| *entered_at += shift);
+            self.last_event_at += shift;
+            self.last_update_at = now;
+        }
+
+        let previous_update = self.last_update_at;
+        let idle = now - self.last_event_at;
+
+        self.last_update_at = now;
+
+        if let Some(long_press) = self.due_long_press() {
+            let result = self.handle_event(&long_press);
+
+            if result != EventResult::Failure {
+                return result;
+            }
+        }
+
+        // As with events, the transitions of the global state are evaluated first. Its timers
+        // count from entering the current state
+        if let Some(global_state) = self.global_state.clone() {
+            let current_state = self.active_leaves().first().copied();
+            let (elapsed, previous_elapsed) =
+                self.elapsed_in_state(current_state, now, previous_update);
+
+            let (tmp_state, matched_transition) = match global_state.read() {
+                Ok(state) => {
+                    self.evaluate_timers(state.get_transitions(), elapsed, previous_elapsed, idle)
+                }
+                Err(_) => (-1, None),
+            };
+
+            if tmp_state > -1 {
+                return self.transition_to(None, tmp_state as usize, matched_transition);
+            }
+        }
+
+        let mut result = EventResult::Success;
+        let mut domains = Vec::new();
+
+        for leaf in self.active_leaves() {
+            // Left by the transition of another region
+            if self.is_within_domains(leaf, &domains) {
+                continue;
+            }
+
+            let matched = self.find_transition(leaf, |index, transitions| {
+                let (elapsed, previous_elapsed) =
+                    self.elapsed_in_state(Some(index), now, previous_update);
+
+                self.evaluate_timers(transitions, elapsed, previous_elapsed, idle)
+            });
+
+            if let Some((source, target, matched_transition)) = matched {
+                domains.push(self.transition_domain(Some(source), target));
+                result = self.transition_to(Some(source), target, matched_transition);
+            }
+        }
+
+        result
+    }
+
+    // The seconds spent in a state now and at the previous update
+    fn elapsed_in_state(&self, index: Option<usize>, now: f64, previous_update: f64) -> (f64, f64) {
+        let entered_at = index
+            .and_then(|index| self.state_entered_at.get(&index))
+            .copied()
+            .unwrap_or(now);
+
+        (now - entered_at, previous_update - entered_at)
+    }
+
+    // Returns the index of the target state, -1 if no timed transition is due, and the due
+    // transition
+    fn evaluate_timers(
+        &self,
+        transitions: &[Arc<RwLock<Transition>>],
+        elapsed: f64,
+        previous_elapsed: f64,
+        idle: f64,
+    ) -> (i32, Option<Arc<RwLock<Transition>>>) {
+        let mut tmp_state: i32 = -1;
+        let mut matched_transition = None;
+
+        for locked_transition in transitions {
+            let transition = locked_transition.read().unwrap();
+            let event = transition.get_event();
+            let event = event.read().unwrap();
+
+            let due = match &*event {
+                InternalEvent::After { duration } => idle >= *duration as f64,
+                InternalEvent::Delay { duration } => elapsed >= *duration as f64,
+                // Every time a multiple of the interval was reached since the last update
+                InternalEvent::Every { interval } => {
+                    let interval = *interval as f64;
+
+                    interval > 0.0
+                        && (elapsed / interval).floor() > (previous_elapsed / interval).floor()
+                }
+                _ => false,
+            };
+
+            if due && self.verify_if_guards_are_met(transition.get_guards()) {
+                tmp_state = transition.get_target_state() as i32;
+                matched_transition = Some(locked_transition.clone());
+            }
+        }
+
+        (tmp_state, matched_transition)
+    }
+
+    // The time of the player's clock in seconds
+    fn clock_time(&self) -> Option<f64> {
+        self.player
+            .as_ref()
+            .and_then(|player| player.try_read().ok())
+            .and_then(|player| player.clock_time())
+            .map(|time| time.as_secs_f64())
+    }
+
+    /// Queue an event and handle every queued event, see `process_events`.
+    pub fn post_event(&mut self, event: &Event) -> EventResult {
+        self.queue_event(event.clone());
+        self.process_events()
+    }
+
+    /// Queue an event, to be handled by the next call to `process_events`.
+    pub fn queue_event(&mut self, event: Event) {
+        self.event_queue.push_back(event);
+    }
+
+    /// Handle the queued events in the order they were posted. Each event runs to completion,
+    /// its transition taken and the entered states executed, before the next one is handled.
+    ///
+    /// A long press held since the previous event is handled first, so that hosts which stop
+    /// rendering while the animation is paused still get it.
+    ///
+    /// Returns the result of the last event that did something, Failure if none did.
+    pub fn process_events(&mut self) -> EventResult {
+        let mut result = EventResult::Failure;
+
+        loop {
+            if let Some(long_press) = self.due_long_press() {
+                result = result.then(self.handle_event(&long_press));
+            }
+
+            let Some(event) = self.event_queue.pop_front() else {
+                break;
+            };
+
+            result = result.then(self.handle_event(&event));
+
+            // The gestures completed by a pointer event are handled right after it
+            let gestures = match self.clock_time() {
+                Some(now) if self.status == StateMachineStatus::Running => {
+                    self.gestures.pointer_event(&event, now)
+                }
+                _ => Vec::new(),
+            };
+
+            for gesture in gestures {
+                result = result.then(self.handle_event(&gesture));
             }
         }
 
-        tmp_state
+        result
+    }
+
+    // The long press of the pointer held down in place until now, once per press
+    fn due_long_press(&mut self) -> Option<Event> {
+        if self.status != StateMachineStatus::Running {
+            return None;
+        }
+
+        let now = self.clock_time()?;
+
+        self.gestures.update(now)
     }
 
-    // Return codes
-    // 0: Success
-    // 1: Failure
-    // 2: Play animation
-    // 3: Pause animation
-    // 4: Request and draw a new single frame of the animation (needed for sync state)
-    pub fn post_event(&mut self, event: &Event) -> i32 {
+    fn handle_event(&mut self, event: &Event) -> EventResult {
         if self.status == StateMachineStatus::Stopped || self.status == StateMachineStatus::Paused {
-            return 1;
+            return EventResult::Failure;
         }
 
         if self.current_state.is_none() {
-            return 1;
+            return EventResult::Failure;
+        }
+
+        // Only the events posted by the host restart the idle time of after transitions
+        let is_playback_event = matches!(
+            event,
+            Event::OnComplete
+                | Event::OnMarkerReached { .. }
+                | Event::OnFrameReached { .. }
+                | Event::OnLoopComplete
+        );
+
+        if !is_playback_event {
+            if let Some(now) = self.clock_time() {
+                self.last_event_at = now;
+            }
         }
 
         // Only match with setNumericContext as if this is the case we return early
@@ -934,9 +2138,17 @@ impl StateMachine {
                 }
             }
 
-            return 0;
+            return EventResult::Success;
+        }
+
+        // Listeners update the context before the transitions are evaluated, so that their
+        // guards see the new values
+        if self.trigger_listeners(event) && self.current_state_is_sync() {
+            self.execute_current_state();
         }
 
+        let mut rejected = Vec::new();
+
         // Firstly check if we have a global state within the state machine.
         if self.global_state.is_some() {
             let global_state = self.global_state.clone().unwrap();
@@ -944,117 +2156,162 @@ impl StateMachine {
 
             if global_state_value.is_ok() {
                 let state_value = global_state_value.unwrap();
-                let tmp_state = self.evaluate_transition(state_value.get_transitions(), event);
+                let (tmp_state, matched_transition) =
+                    self.evaluate_transition(state_value.get_transitions(), event, &mut rejected);
 
                 if tmp_state > -1 {
-                    let next_state = self.states.get(tmp_state as usize).unwrap();
+                    return self.transition_to(None, tmp_state as usize, matched_transition);
+                }
+            }
+        }
 
-                    // Emit transtion occured event
-                    self.observers.read().unwrap().iter().for_each(|observer| {
-                        observer.on_transition(
-                            (*self
-                                .current_state
-                                .as_ref()
-                                .unwrap()
-                                .read()
-                                .unwrap()
-                                .get_name())
-                            .to_string(),
-                            (*next_state.read().unwrap().get_name()).to_string(),
-                        )
-                    });
+        // Otherwise we evaluate the transitions of the active states, from the innermost ones up.
+        // Each parallel region can take a transition
+        let mut result = EventResult::Failure;
+        let mut domains = Vec::new();
 
-                    // Emit leaving current state event
-                    if self.current_state.is_some() {
-                        self.observers.read().unwrap().iter().for_each(|observer| {
-                            observer.on_state_exit(
-                                (*self
-                                    .current_state
-                                    .as_ref()
-                                    .unwrap()
-                                    .read()
-                                    .unwrap()
-                                    .get_name())
-                                .to_string(),
-                            );
-                        });
-                    }
+        for leaf in self.active_leaves() {
+            // Left by the transition of another region
+            if self.is_within_domains(leaf, &domains) {
+                continue;
+            }
 
-                    self.current_state = Some(next_state.clone());
+            let matched = self.find_transition(leaf, |_, transitions| {
+                self.evaluate_transition(transitions, event, &mut rejected)
+            });
 
-                    // Emit entering a new state
-                    self.observers.read().unwrap().iter().for_each(|observer| {
-                        observer
-                            .on_state_entered((*next_state.read().unwrap().get_name()).to_string());
-                    });
+            if let Some((source, target, matched_transition)) = matched {
+                domains.push(self.transition_domain(Some(source), target));
+                result = self.transition_to(Some(source), target, matched_transition);
+            }
+        }
 
-                    return self.execute_current_state();
+        // The event is only rejected when it didn't take any transition
+        if domains.is_empty() {
+            let mut events: Vec<EventInfo> = Vec::new();
+
+            for event in rejected.iter().map(EventInfo::from) {
+                if !events.contains(&event) {
+                    events.push(event);
                 }
             }
+
+            self.observers.read().unwrap().iter().for_each(|observer| {
+                events
+                    .iter()
+                    .for_each(|event| observer.on_event_rejected(event.clone()));
+            });
         }
 
-        // Otherwise we evaluate the transitions of the current state
-        let curr_state = self.current_state.clone().unwrap();
+        result
+    }
+
+    pub fn remove_state(&mut self, state: Arc<RwLock<State>>) {
+        let _ = state;
+        // self.states.remove(state);
+    }
+}
 
-        let state_value_result = curr_state.read();
+// Build the nested states from the parent, initial child and parallel flag of each state
+fn state_hierarchy(
+    nesting: &[(Option<u32>, Option<u32>, bool)],
+    global_state: Option<usize>,
+) -> Result<Vec<StateNode>, StateMachineError> {
+    let mut hierarchy = vec![StateNode::default(); nesting.len()];
 
-        if state_value_result.is_ok() {
-            let state_value = state_value_result.unwrap();
+    for (index, (parent, _, parallel)) in nesting.iter().enumerate() {
+        hierarchy[index].parallel = *parallel;
 
-            let tmp_state = self.evaluate_transition(state_value.get_transitions(), event);
+        if let Some(parent) = parent {
+            let parent = *parent as usize;
 
-            if tmp_state > -1 {
-                let next_state = self.states.get(tmp_state as usize).unwrap();
-
-                // Emit transtion occured event
-                self.observers.read().unwrap().iter().for_each(|observer| {
-                    observer.on_transition(
-                        (*self
-                            .current_state
-                            .as_ref()
-                            .unwrap()
-                            .read()
-                            .unwrap()
-                            .get_name())
-                        .to_string(),
-                        (*next_state.read().unwrap().get_name()).to_string(),
-                    )
+            if parent >= nesting.len() || parent == index || Some(parent) == global_state {
+                return Err(StateMachineError::ParsingError {
+                    reason: format!("State {} has an invalid parent state index value!", index),
                 });
+            }
 
-                // Emit leaving current state event
-                if self.current_state.is_some() {
-                    self.observers.read().unwrap().iter().for_each(|observer| {
-                        observer.on_state_exit(
-                            (*self
-                                .current_state
-                                .as_ref()
-                                .unwrap()
-                                .read()
-                                .unwrap()
-                                .get_name())
-                            .to_string(),
-                        );
-                    });
-                }
+            hierarchy[index].parent = Some(parent);
+            hierarchy[parent].children.push(index);
+        }
+    }
 
-                self.current_state = Some(next_state.clone());
+    // Going up from any state has to reach the top level
+    for index in 0..hierarchy.len() {
+        let mut parent = hierarchy[index].parent;
+        let mut depth = 0;
 
-                // Emit entering a new state
-                self.observers.read().unwrap().iter().for_each(|observer| {
-                    observer.on_state_entered((*next_state.read().unwrap().get_name()).to_string());
-                });
+        while let Some(index) = parent {
+            depth += 1;
 
-                return self.execute_current_state();
+            if depth > hierarchy.len() {
+                return Err(StateMachineError::ParsingError {
+                    reason: format!("State {} is nested within itself!", index),
+                });
             }
-        }
 
-        1
+            parent = hierarchy[index].parent;
+        }
     }
 
-    pub fn remove_state(&mut self, state: Arc<RwLock<State>>) {
-        let _ = state;
-        // self.states.remove(state);
+    for (index, (_, initial, _)) in nesting.iter().enumerate() {
+        if let Some(initial) = initial {
+            let initial = *initial as usize;
+
+            if !hierarchy[index].children.contains(&initial) {
+                return Err(StateMachineError::ParsingError {
+                    reason: format!(
+                        "The initial state of state {} isn't one of its children!",
+                        index
+                    ),
+                });
+            }
+
+            hierarchy[index].initial = Some(initial);
+        }
     }
+
+    Ok(hierarchy)
+}
+
+fn context_actions(actions: Option<Vec<ContextActionJson>>) -> Vec<ContextAction> {
+    actions
+        .unwrap_or_default()
+        .into_iter()
+        .map(ContextAction::from)
+        .collect()
+}
+
+fn state_actions(actions: Option<Vec<StateActionJson>>) -> Vec<StateAction> {
+    actions
+        .unwrap_or_default()
+        .into_iter()
+        .map(StateAction::from)
+        .collect()
+}
+
+fn animation_transition_from_json(json: &AnimationTransitionJson) -> AnimationTransition {
+    let direction = match json.direction {
+        Some(TransitionDirectionJson::Right) => TransitionDirection::Right,
+        Some(TransitionDirectionJson::Up) => TransitionDirection::Up,
+        Some(TransitionDirectionJson::Down) => TransitionDirection::Down,
+        Some(TransitionDirectionJson::Left) | None => TransitionDirection::Left,
+    };
+
+    let effect = match json.r#type {
+        AnimationTransitionJsonType::Crossfade => TransitionEffect::Crossfade,
+        AnimationTransitionJsonType::Slide => TransitionEffect::Slide { direction },
+        AnimationTransitionJsonType::Wipe => TransitionEffect::Wipe { direction },
+    };
+
+    let easing = match json.easing {
+        Some(EasingJson::EaseIn) => Easing::EaseIn,
+        Some(EasingJson::EaseOut) => Easing::EaseOut,
+        Some(EasingJson::EaseInOut) => Easing::EaseInOut,
+        Some(EasingJson::Linear) | None => Easing::Linear,
+    };
+
+    AnimationTransition::new(effect, json.duration, easing)
 }
 
 unsafe impl Send for StateMachine {}
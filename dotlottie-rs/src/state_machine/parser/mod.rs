@@ -1,16 +1,18 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::errors::StateMachineError;
+use crate::SwipeDirection;
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub enum StateActionType {
     URLAction,
     ThemeAction,
     SoundAction,
     LogAction,
+    FireCustomEvent,
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum StringNumberBool {
     String(String),
@@ -18,36 +20,47 @@ pub enum StringNumberBool {
     Bool(bool),
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub enum TransitionGuardType {
     Numeric,
     String,
     Boolean,
+    And,
+    Or,
+    Not,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
 pub enum ContextJsonType {
     Numeric,
     String,
     Boolean,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum TransitionJsonType {
     Transition,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub enum ListenerJsonType {
     PointerUp,
     PointerDown,
     PointerEnter,
     PointerExit,
     PointerMove,
+    KeyDown,
+    KeyUp,
+    Focus,
+    Blur,
+    Scroll,
+    LongPress,
+    DoubleClick,
+    Swipe,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
 pub enum TransitionGuardConditionType {
     GreaterThan,
     GreaterThanOrEqual,
@@ -55,15 +68,117 @@ pub enum TransitionGuardConditionType {
     LessThanOrEqual,
     Equal,
     NotEqual,
+    Contains,
+    StartsWith,
+    EndsWith,
+    // '*' matches any run of characters, '?' a single one
+    Matches,
+    // between min and max, inclusive
+    InRange,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum AnimationTransitionJsonType {
+    Crossfade,
+    Slide,
+    Wipe,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum TransitionDirectionJson {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub enum EasingJson {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+// States are referenced by their name, or by their index in the states of the definition
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum StateReferenceJson {
+    Index(u32),
+    Name(String),
+}
+
+impl StateReferenceJson {
+    /// Returns the index of the referenced state, given the names of all of the states. Indices
+    /// are returned as is, it's up to the caller to check that they're in range.
+    pub fn resolve(&self, state_names: &[String]) -> Result<u32, StateMachineError> {
+        let name = match self {
+            StateReferenceJson::Index(index) => return Ok(*index),
+            StateReferenceJson::Name(name) => name,
+        };
+
+        let mut matches = state_names
+            .iter()
+            .enumerate()
+            .filter(|(_, state_name)| *state_name == name);
+
+        match (matches.next(), matches.next()) {
+            (Some((index, _)), None) => Ok(index as u32),
+            (None, _) => Err(StateMachineError::ParsingError {
+                reason: format!("There is no state named '{}'!", name),
+            }),
+            (Some(_), Some(_)) => Err(StateMachineError::ParsingError {
+                reason: format!("Several states are named '{}'!", name),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for StateReferenceJson {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StateReferenceJson::Index(index) => write!(f, "{}", index),
+            StateReferenceJson::Name(name) => write!(f, "'{}'", name),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct DescriptorJson {
     pub id: String,
-    pub initial: u32,
+    pub initial: StateReferenceJson,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ContextActionJson {
+    Set {
+        context_key: String,
+        value: StringNumberBool,
+    },
+    Increment {
+        context_key: String,
+        value: Option<f32>,
+    },
+    Decrement {
+        context_key: String,
+        value: Option<f32>,
+    },
+    Toggle {
+        context_key: String,
+    },
+    Clamp {
+        context_key: String,
+        min: f32,
+        max: f32,
+    },
+    Append {
+        context_key: String,
+        value: String,
+    },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum StateActionJson {
     URLAction {
@@ -81,11 +196,18 @@ pub enum StateActionJson {
     LogAction {
         message: String,
     },
+    // passed to the on_custom_event callback of the observers
+    FireCustomEvent {
+        event: String,
+    },
 }
 
+// States are nested by setting the index of their parent. A compound state enters its initial
+// child, the first one when unset, or all of its children if they are parallel regions
+
 // Type is the actual "type" declared in the state machine State json
 // This allows serde to determine which struct to deserialize the json into
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[serde(tag = "type")]
 pub enum StateJson {
     PlaybackState {
@@ -102,6 +224,9 @@ pub enum StateJson {
         entry_actions: Option<Vec<StateActionJson>>,
         exit_actions: Option<Vec<StateActionJson>>,
         reset_context: Option<String>,
+        parent: Option<StateReferenceJson>,
+        initial: Option<StateReferenceJson>,
+        parallel: Option<bool>,
     },
     SyncState {
         name: String,
@@ -112,6 +237,9 @@ pub enum StateJson {
         entry_actions: Option<Vec<StateActionJson>>,
         exit_actions: Option<Vec<StateActionJson>>,
         reset_context: Option<String>,
+        parent: Option<StateReferenceJson>,
+        initial: Option<StateReferenceJson>,
+        parallel: Option<bool>,
     },
     GlobalState {
         name: String,
@@ -121,63 +249,152 @@ pub enum StateJson {
     },
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+impl StateJson {
+    pub fn name(&self) -> &str {
+        match self {
+            StateJson::PlaybackState { name, .. }
+            | StateJson::SyncState { name, .. }
+            | StateJson::GlobalState { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct TransitionGuardJson {
     pub r#type: TransitionGuardType,
+    #[serde(default)]
     pub context_key: String,
-    pub condition_type: TransitionGuardConditionType,
-    pub compare_to: StringNumberBool,
+    pub condition_type: Option<TransitionGuardConditionType>,
+    pub compare_to: Option<StringNumberBool>,
+    // compare to the value of another context variable instead of compare_to
+    pub compare_to_key: Option<String>,
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+    // the nested guards of And, Or and Not guards
+    pub guards: Option<Vec<TransitionGuardJson>>,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct NumericEventJson {
     pub value: f32,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct StringEventJson {
     pub value: String,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct BooleanEventJson {
     pub value: bool,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct OnCompleteEventJson {}
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnMarkerReachedEventJson {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnFrameReachedEventJson {
+    pub frame: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnLoopCompleteEventJson {}
+
+// Durations and intervals are in seconds of the player's clock
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AfterEventJson {
+    pub duration: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct DelayEventJson {
+    pub duration: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct EveryEventJson {
+    pub interval: f32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct OnPointerDownEventJson {
     pub target: Option<String>,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct OnPointerUpEventJson {
     pub target: Option<String>,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct OnPointerEnterEventJson {
     pub target: Option<String>,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct OnPointerExitEventJson {
     pub target: Option<String>,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct OnPointerMoveEventJson {
     pub target: Option<String>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnKeyDownEventJson {
+    pub key: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnKeyUpEventJson {
+    pub key: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnFocusEventJson {}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnBlurEventJson {}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnScrollEventJson {}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnLongPressEventJson {
+    pub target: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnDoubleClickEventJson {
+    pub target: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OnSwipeEventJson {
+    pub direction: Option<SwipeDirection>,
+}
+
+// Blends the animations of the source and target states, the duration is in seconds
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct AnimationTransitionJson {
+    pub r#type: AnimationTransitionJsonType,
+    pub direction: Option<TransitionDirectionJson>,
+    pub duration: f32,
+    pub easing: Option<EasingJson>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum TransitionJson {
     Transition {
-        from_state: u32,
-        to_state: u32,
+        from_state: StateReferenceJson,
+        to_state: StateReferenceJson,
         guards: Option<Vec<TransitionGuardJson>>,
         numeric_event: Option<NumericEventJson>,
         string_event: Option<StringEventJson>,
@@ -188,27 +405,47 @@ pub enum TransitionJson {
         on_pointer_enter_event: Option<OnPointerEnterEventJson>,
         on_pointer_exit_event: Option<OnPointerExitEventJson>,
         on_pointer_move_event: Option<OnPointerMoveEventJson>,
+        on_key_down_event: Option<OnKeyDownEventJson>,
+        on_key_up_event: Option<OnKeyUpEventJson>,
+        on_focus_event: Option<OnFocusEventJson>,
+        on_blur_event: Option<OnBlurEventJson>,
+        on_scroll_event: Option<OnScrollEventJson>,
+        on_long_press_event: Option<OnLongPressEventJson>,
+        on_double_click_event: Option<OnDoubleClickEventJson>,
+        on_swipe_event: Option<OnSwipeEventJson>,
+        on_marker_reached_event: Option<OnMarkerReachedEventJson>,
+        on_frame_reached_event: Option<OnFrameReachedEventJson>,
+        on_loop_complete_event: Option<OnLoopCompleteEventJson>,
+        after_event: Option<AfterEventJson>,
+        delay_event: Option<DelayEventJson>,
+        every_event: Option<EveryEventJson>,
+        animation_transition: Option<AnimationTransitionJson>,
+        actions: Option<Vec<ContextActionJson>>,
     },
 }
 
-#[derive(Deserialize, Debug)]
+// The target of pointer, long press and double click listeners is a layer name, of keyboard
+// listeners a key name and of swipe listeners a direction. Focus, blur and scroll listeners have
+// no target
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ListenerJson {
     pub r#type: ListenerJsonType,
     pub target: Option<String>,
     pub action: Option<String>,
     pub value: Option<StringNumberBool>,
     pub context_key: Option<String>,
+    pub actions: Option<Vec<ContextActionJson>>,
 }
 
 // todo move to enum and add #[serde(tag = "type")]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ContextJson {
     pub r#type: ContextJsonType,
     pub key: String,
     pub value: StringNumberBool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct StateMachineJson {
     pub descriptor: DescriptorJson,
     pub states: Vec<StateJson>,
@@ -227,3 +464,33 @@ pub fn state_machine_parse(json: &str) -> Result<StateMachineJson, StateMachineE
         }),
     }
 }
+
+// Unset optional values are left out instead of being written as null
+fn without_nulls(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(object) => object
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(key, value)| (key, without_nulls(value)))
+            .collect(),
+        serde_json::Value::Array(array) => array.into_iter().map(without_nulls).collect(),
+        value => value,
+    }
+}
+
+pub fn state_machine_serialize(
+    state_machine: &StateMachineJson,
+) -> Result<String, StateMachineError> {
+    serialize_without_nulls(state_machine)
+}
+
+// Serializes a part of a definition, e.g. a state or the guards of a transition
+pub(crate) fn serialize_without_nulls<T: Serialize>(
+    value: &T,
+) -> Result<String, StateMachineError> {
+    serde_json::to_value(value)
+        .and_then(|value| serde_json::to_string(&without_nulls(value)))
+        .map_err(|err| StateMachineError::ParsingError {
+            reason: err.to_string(),
+        })
+}
@@ -4,9 +4,9 @@ use std::{
     sync::{Arc, RwLock},
 };
 
-use crate::{Config, DotLottiePlayerContainer};
+use crate::{AnimationTransition, Config, DotLottiePlayerContainer};
 
-use super::transitions::Transition;
+use super::{actions::StateAction, transitions::Transition, EventResult};
 
 pub trait StateTrait {
     fn execute(
@@ -15,7 +15,8 @@ pub trait StateTrait {
         string_context: &HashMap<String, String>,
         bool_context: &HashMap<String, bool>,
         numeric_context: &HashMap<String, f32>,
-    ) -> i32;
+        animation_transition: Option<&AnimationTransition>,
+    ) -> EventResult;
     fn get_reset_context_key(&self) -> &String;
     fn get_animation_id(&self) -> Option<&String>;
     fn get_transitions(&self) -> &Vec<Arc<RwLock<Transition>>>;
@@ -25,12 +26,24 @@ pub trait StateTrait {
     fn get_type(&self) -> String;
     // fn set_reset_context(&mut self, reset_context: bool);
 
-    // fn add_entry_action(&mut self, action: String);
-    // fn add_exit_action(&mut self, action: String);
-    // fn remove_entry_action(&mut self, action: String);
-    // fn remove_exit_action(&mut self, action: String);
-    // fn get_entry_actions(&self) -> Vec<String>;
-    // fn get_exit_actions(&self) -> Vec<String>;
+    fn add_entry_action(&mut self, action: StateAction);
+    fn add_exit_action(&mut self, action: StateAction);
+    fn get_entry_actions(&self) -> &Vec<StateAction>;
+    fn get_exit_actions(&self) -> &Vec<StateAction>;
+}
+
+/// The place of a state among nested states, states without a parent are at the top level.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StateNode {
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    /// The child entered with a compound state, the first child when unset.
+    pub initial: Option<usize>,
+    /// Whether the children are parallel regions, all active at the same time. The regions share
+    /// the player, so the state entered last sets its config: the last region when entering the
+    /// parallel state, then the region that took the last transition. That state is the
+    /// current state of the state machine.
+    pub parallel: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -41,6 +54,8 @@ pub enum State {
         reset_context: String,
         animation_id: String,
         transitions: Vec<Arc<RwLock<Transition>>>,
+        entry_actions: Vec<StateAction>,
+        exit_actions: Vec<StateAction>,
     },
     Sync {
         name: String,
@@ -49,11 +64,15 @@ pub enum State {
         reset_context: String,
         animation_id: String,
         transitions: Vec<Arc<RwLock<Transition>>>,
+        entry_actions: Vec<StateAction>,
+        exit_actions: Vec<StateAction>,
     },
     Global {
         name: String,
         reset_context: String,
         transitions: Vec<Arc<RwLock<Transition>>>,
+        entry_actions: Vec<StateAction>,
+        exit_actions: Vec<StateAction>,
     },
 }
 
@@ -68,19 +87,14 @@ impl State {
 }
 
 impl StateTrait for State {
-    // Return codes
-    // 0: Success
-    // 1: Failure
-    // 2: Play animation
-    // 3: Pause animation
-    // 4: Request and draw a new single frame of the animation (needed for sync state)
     fn execute(
         &self,
         player: &Rc<RwLock<DotLottiePlayerContainer>>,
         _: &HashMap<String, String>,
         _: &HashMap<String, bool>,
         numeric_context: &HashMap<String, f32>,
-    ) -> i32 {
+        animation_transition: Option<&AnimationTransition>,
+    ) -> EventResult {
         match self {
             State::Playback {
                 config,
@@ -93,9 +107,24 @@ impl StateTrait for State {
                 if let Ok(player_read) = player.try_read() {
                     let size = player_read.size();
 
-                    // Tell player to load new animation
+                    // Tell player to load new animation, blended with the one on screen
+                    // when the transition into this state asks for it
                     if !animation_id.is_empty() {
-                        player_read.load_animation(animation_id, size.0, size.1);
+                        match animation_transition {
+                            Some(transition)
+                                if player_read.active_animation_id() != *animation_id =>
+                            {
+                                player_read.load_animation_with_transition(
+                                    animation_id,
+                                    size.0,
+                                    size.1,
+                                    *transition,
+                                );
+                            }
+                            _ => {
+                                player_read.load_animation(animation_id, size.0, size.1);
+                            }
+                        }
                     }
 
                     player_read.set_config(config);
@@ -103,14 +132,14 @@ impl StateTrait for State {
                     if autoplay {
                         player_read.play();
 
-                        return 2;
+                        return EventResult::Play;
                     } else {
                         player_read.pause();
 
-                        return 3;
+                        return EventResult::Pause;
                     }
                 } else {
-                    return 1;
+                    return EventResult::Failure;
                 }
             }
             State::Sync {
@@ -134,7 +163,7 @@ impl StateTrait for State {
                         let ret = player_read.set_frame(*frame_value);
 
                         if ret {
-                            return 4;
+                            return EventResult::DrawFrame;
                         }
                     }
                 }
@@ -142,7 +171,7 @@ impl StateTrait for State {
             State::Global { .. } => {}
         }
 
-        0
+        EventResult::Success
     }
 
     fn get_reset_context_key(&self) -> &String {
@@ -210,4 +239,36 @@ impl StateTrait for State {
     // fn set_reset_context(&mut self, reset_context: bool) {
     //     todo!()
     // }
+
+    fn add_entry_action(&mut self, action: StateAction) {
+        match self {
+            State::Playback { entry_actions, .. } => entry_actions.push(action),
+            State::Sync { entry_actions, .. } => entry_actions.push(action),
+            State::Global { entry_actions, .. } => entry_actions.push(action),
+        }
+    }
+
+    fn add_exit_action(&mut self, action: StateAction) {
+        match self {
+            State::Playback { exit_actions, .. } => exit_actions.push(action),
+            State::Sync { exit_actions, .. } => exit_actions.push(action),
+            State::Global { exit_actions, .. } => exit_actions.push(action),
+        }
+    }
+
+    fn get_entry_actions(&self) -> &Vec<StateAction> {
+        match self {
+            State::Playback { entry_actions, .. } => entry_actions,
+            State::Sync { entry_actions, .. } => entry_actions,
+            State::Global { entry_actions, .. } => entry_actions,
+        }
+    }
+
+    fn get_exit_actions(&self) -> &Vec<StateAction> {
+        match self {
+            State::Playback { exit_actions, .. } => exit_actions,
+            State::Sync { exit_actions, .. } => exit_actions,
+            State::Global { exit_actions, .. } => exit_actions,
+        }
+    }
 }
@@ -1,89 +1,440 @@
 use std::collections::HashMap;
 
-use crate::parser::{StringNumberBool, TransitionGuardConditionType};
+use crate::errors::StateMachineError;
+use crate::parser::{
+    StringNumberBool, TransitionGuardConditionType, TransitionGuardJson, TransitionGuardType,
+};
 
-#[derive(Debug)]
-pub struct Guard {
-    pub context_key: String,
-    pub condition_type: TransitionGuardConditionType,
-    pub compare_to: StringNumberBool,
+/// What a context variable is compared to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardOperand {
+    /// A literal value.
+    Value(StringNumberBool),
+    /// The value of another context variable of the same type.
+    ContextKey(String),
+    /// An inclusive numeric range, used by `InRange`.
+    Range { min: f32, max: f32 },
 }
 
-impl Guard {
-    pub fn new(
+/// A condition that has to be met for a transition to be taken.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Guard {
+    Numeric {
         context_key: String,
         condition_type: TransitionGuardConditionType,
-        compare_to: StringNumberBool,
-    ) -> Self {
-        Self {
-            context_key,
-            condition_type,
-            compare_to,
-        }
-    }
+        compare_to: GuardOperand,
+    },
+    String {
+        context_key: String,
+        condition_type: TransitionGuardConditionType,
+        compare_to: GuardOperand,
+    },
+    Boolean {
+        context_key: String,
+        condition_type: TransitionGuardConditionType,
+        compare_to: GuardOperand,
+    },
+    /// Met when all of the guards are met.
+    And { guards: Vec<Guard> },
+    /// Met when at least one of the guards is met.
+    Or { guards: Vec<Guard> },
+    /// Met when the guard is not met.
+    Not { guard: Box<Guard> },
+}
 
-    pub fn string_context_is_satisfied(&self, context: &HashMap<String, String>) -> bool {
-        let context_value = context.get(&self.context_key);
+impl Guard {
+    pub fn is_satisfied(
+        &self,
+        numeric_context: &HashMap<String, f32>,
+        string_context: &HashMap<String, String>,
+        bool_context: &HashMap<String, bool>,
+    ) -> bool {
+        match self {
+            Guard::Numeric {
+                context_key,
+                condition_type,
+                compare_to,
+            } => {
+                let Some(value) = numeric_context.get(context_key) else {
+                    return false;
+                };
 
-        if context_value.is_none() {
-            return false;
+                match compare_to {
+                    GuardOperand::Value(StringNumberBool::F32(compare_to)) => {
+                        compare_numbers(*value, condition_type, *compare_to)
+                    }
+                    GuardOperand::ContextKey(key) => match numeric_context.get(key) {
+                        Some(compare_to) => compare_numbers(*value, condition_type, *compare_to),
+                        None => false,
+                    },
+                    GuardOperand::Range { min, max } => {
+                        *condition_type == TransitionGuardConditionType::InRange
+                            && *min <= *value
+                            && *value <= *max
+                    }
+                    GuardOperand::Value(_) => false,
+                }
+            }
+            Guard::String {
+                context_key,
+                condition_type,
+                compare_to,
+            } => {
+                let Some(value) = string_context.get(context_key) else {
+                    return false;
+                };
+
+                match compare_to {
+                    GuardOperand::Value(StringNumberBool::String(compare_to)) => {
+                        compare_strings(value, condition_type, compare_to)
+                    }
+                    GuardOperand::ContextKey(key) => match string_context.get(key) {
+                        Some(compare_to) => compare_strings(value, condition_type, compare_to),
+                        None => false,
+                    },
+                    _ => false,
+                }
+            }
+            Guard::Boolean {
+                context_key,
+                condition_type,
+                compare_to,
+            } => {
+                let Some(value) = bool_context.get(context_key) else {
+                    return false;
+                };
+
+                let compare_to = match compare_to {
+                    GuardOperand::Value(StringNumberBool::Bool(compare_to)) => *compare_to,
+                    GuardOperand::ContextKey(key) => match bool_context.get(key) {
+                        Some(compare_to) => *compare_to,
+                        None => return false,
+                    },
+                    _ => return false,
+                };
+
+                match condition_type {
+                    TransitionGuardConditionType::Equal => *value == compare_to,
+                    TransitionGuardConditionType::NotEqual => *value != compare_to,
+                    _ => false,
+                }
+            }
+            Guard::And { guards } => guards
+                .iter()
+                .all(|guard| guard.is_satisfied(numeric_context, string_context, bool_context)),
+            Guard::Or { guards } => guards
+                .iter()
+                .any(|guard| guard.is_satisfied(numeric_context, string_context, bool_context)),
+            Guard::Not { guard } => {
+                !guard.is_satisfied(numeric_context, string_context, bool_context)
+            }
         }
+    }
+}
 
-        match &self.compare_to {
-            StringNumberBool::String(compare_to) => match self.condition_type {
-                TransitionGuardConditionType::Equal => return context_value == Some(compare_to),
-                TransitionGuardConditionType::NotEqual => return context_value != Some(compare_to),
-                _ => return false,
-            },
-            StringNumberBool::F32(_) => false,
-            StringNumberBool::Bool(_) => false,
-        };
+fn compare_numbers(
+    value: f32,
+    condition_type: &TransitionGuardConditionType,
+    compare_to: f32,
+) -> bool {
+    match condition_type {
+        TransitionGuardConditionType::Equal => value == compare_to,
+        TransitionGuardConditionType::NotEqual => value != compare_to,
+        TransitionGuardConditionType::GreaterThan => value > compare_to,
+        TransitionGuardConditionType::LessThan => value < compare_to,
+        TransitionGuardConditionType::GreaterThanOrEqual => value >= compare_to,
+        TransitionGuardConditionType::LessThanOrEqual => value <= compare_to,
+        _ => false,
+    }
+}
 
-        false
+fn compare_strings(
+    value: &str,
+    condition_type: &TransitionGuardConditionType,
+    compare_to: &str,
+) -> bool {
+    match condition_type {
+        TransitionGuardConditionType::Equal => value == compare_to,
+        TransitionGuardConditionType::NotEqual => value != compare_to,
+        TransitionGuardConditionType::Contains => value.contains(compare_to),
+        TransitionGuardConditionType::StartsWith => value.starts_with(compare_to),
+        TransitionGuardConditionType::EndsWith => value.ends_with(compare_to),
+        TransitionGuardConditionType::Matches => glob_matches(value, compare_to),
+        _ => false,
     }
+}
 
-    pub fn bool_context_is_satisfied(&self, context: &HashMap<String, bool>) -> bool {
-        let context_value = context.get(&self.context_key);
+// Matches a value against a pattern where '*' matches any run of characters and '?' matches
+// exactly one character
+fn glob_matches(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
 
-        if context_value.is_none() {
+    let (mut v, mut p) = (0, 0);
+    // position of the last '*' in the pattern and of the value when it was reached
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            v += 1;
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, v));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // let the last '*' swallow one more character
+            backtrack = Some((star, matched + 1));
+            p = star + 1;
+            v = matched + 1;
+        } else {
             return false;
         }
+    }
 
-        match &self.compare_to {
-            StringNumberBool::Bool(compare_to) => match self.condition_type {
-                TransitionGuardConditionType::Equal => {
-                    return context_value == Some(compare_to);
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+fn nested_guards(
+    guards: Option<Vec<TransitionGuardJson>>,
+) -> Result<Vec<Guard>, StateMachineError> {
+    guards
+        .unwrap_or_default()
+        .into_iter()
+        .map(Guard::try_from)
+        .collect()
+}
+
+impl TryFrom<TransitionGuardJson> for Guard {
+    type Error = StateMachineError;
+
+    fn try_from(guard: TransitionGuardJson) -> Result<Self, Self::Error> {
+        let guard_type = match guard.r#type {
+            TransitionGuardType::And => {
+                return Ok(Guard::And {
+                    guards: nested_guards(guard.guards)?,
+                })
+            }
+            TransitionGuardType::Or => {
+                return Ok(Guard::Or {
+                    guards: nested_guards(guard.guards)?,
+                })
+            }
+            TransitionGuardType::Not => {
+                let mut guards = nested_guards(guard.guards)?;
+
+                if guards.len() != 1 {
+                    return Err(StateMachineError::ParsingError {
+                        reason: "A Not guard must contain exactly one guard!".to_string(),
+                    });
                 }
-                TransitionGuardConditionType::NotEqual => return context_value != Some(compare_to),
-                _ => return false,
-            },
-            StringNumberBool::String(_) => false,
-            StringNumberBool::F32(_) => false,
+
+                return Ok(Guard::Not {
+                    guard: Box::new(guards.remove(0)),
+                });
+            }
+            guard_type => guard_type,
         };
 
-        false
+        let Some(condition_type) = guard.condition_type else {
+            return Err(StateMachineError::ParsingError {
+                reason: format!(
+                    "Guard on context key '{}' is missing its condition type!",
+                    guard.context_key
+                ),
+            });
+        };
+
+        let compare_to = if condition_type == TransitionGuardConditionType::InRange {
+            match (guard.min, guard.max) {
+                (Some(min), Some(max)) => GuardOperand::Range { min, max },
+                _ => {
+                    return Err(StateMachineError::ParsingError {
+                        reason: format!(
+                            "InRange guard on context key '{}' needs a min and a max!",
+                            guard.context_key
+                        ),
+                    })
+                }
+            }
+        } else if let Some(compare_to_key) = guard.compare_to_key {
+            GuardOperand::ContextKey(compare_to_key)
+        } else if let Some(compare_to) = guard.compare_to {
+            GuardOperand::Value(compare_to)
+        } else {
+            return Err(StateMachineError::ParsingError {
+                reason: format!(
+                    "Guard on context key '{}' has nothing to compare to!",
+                    guard.context_key
+                ),
+            });
+        };
+
+        let context_key = guard.context_key;
+
+        Ok(match guard_type {
+            TransitionGuardType::String => Guard::String {
+                context_key,
+                condition_type,
+                compare_to,
+            },
+            TransitionGuardType::Boolean => Guard::Boolean {
+                context_key,
+                condition_type,
+                compare_to,
+            },
+            _ => Guard::Numeric {
+                context_key,
+                condition_type,
+                compare_to,
+            },
+        })
     }
+}
 
-    pub fn numeric_context_is_satisfied(&self, context: &HashMap<String, f32>) -> bool {
-        let context_value = context.get(&self.context_key);
+impl From<Guard> for TransitionGuardJson {
+    fn from(guard: Guard) -> Self {
+        let mut json = TransitionGuardJson {
+            r#type: TransitionGuardType::Numeric,
+            context_key: String::new(),
+            condition_type: None,
+            compare_to: None,
+            compare_to_key: None,
+            min: None,
+            max: None,
+            guards: None,
+        };
 
-        if context_value.is_none() {
-            return false;
+        let (context_key, condition_type, compare_to) = match guard {
+            Guard::Numeric {
+                context_key,
+                condition_type,
+                compare_to,
+            } => (context_key, condition_type, compare_to),
+            Guard::String {
+                context_key,
+                condition_type,
+                compare_to,
+            } => {
+                json.r#type = TransitionGuardType::String;
+                (context_key, condition_type, compare_to)
+            }
+            Guard::Boolean {
+                context_key,
+                condition_type,
+                compare_to,
+            } => {
+                json.r#type = TransitionGuardType::Boolean;
+                (context_key, condition_type, compare_to)
+            }
+            Guard::And { guards } => {
+                json.r#type = TransitionGuardType::And;
+                json.guards = Some(guards.into_iter().map(Into::into).collect());
+                return json;
+            }
+            Guard::Or { guards } => {
+                json.r#type = TransitionGuardType::Or;
+                json.guards = Some(guards.into_iter().map(Into::into).collect());
+                return json;
+            }
+            Guard::Not { guard } => {
+                json.r#type = TransitionGuardType::Not;
+                json.guards = Some(vec![(*guard).into()]);
+                return json;
+            }
+        };
+
+        json.context_key = context_key;
+        json.condition_type = Some(condition_type);
+
+        match compare_to {
+            GuardOperand::Value(value) => json.compare_to = Some(value),
+            GuardOperand::ContextKey(key) => json.compare_to_key = Some(key),
+            GuardOperand::Range { min, max } => {
+                json.min = Some(min);
+                json.max = Some(max);
+            }
         }
 
-        match &self.compare_to {
-            StringNumberBool::F32(compare_to) => match self.condition_type {
-                TransitionGuardConditionType::Equal => context_value == Some(compare_to),
-                TransitionGuardConditionType::NotEqual => context_value != Some(compare_to),
-                TransitionGuardConditionType::GreaterThan => context_value > Some(compare_to),
-                TransitionGuardConditionType::LessThan => context_value < Some(compare_to),
-                TransitionGuardConditionType::GreaterThanOrEqual => {
-                    context_value >= Some(compare_to)
-                }
-                TransitionGuardConditionType::LessThanOrEqual => context_value <= Some(compare_to),
-            },
-            StringNumberBool::String(_) => false,
-            StringNumberBool::Bool(_) => false,
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric(key: &str, condition_type: TransitionGuardConditionType, compare_to: f32) -> Guard {
+        Guard::Numeric {
+            context_key: key.to_string(),
+            condition_type,
+            compare_to: GuardOperand::Value(StringNumberBool::F32(compare_to)),
         }
     }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("star_3", "star_*"));
+        assert!(glob_matches("star_3", "star_?"));
+        assert!(glob_matches("star_3", "*"));
+        assert!(glob_matches("", "*"));
+        assert!(glob_matches("abcbcd", "a*bcd"));
+        assert!(!glob_matches("star_10", "star_?"));
+        assert!(!glob_matches("moon_3", "star_*"));
+        assert!(!glob_matches("star", "star_*"));
+    }
+
+    #[test]
+    fn test_compound_guards() {
+        let numeric_context = HashMap::from([("a".to_string(), 3.0), ("b".to_string(), 5.0)]);
+        let string_context = HashMap::from([("name".to_string(), "star_3".to_string())]);
+        let bool_context = HashMap::from([("on".to_string(), true)]);
+
+        let is_satisfied =
+            |guard: &Guard| guard.is_satisfied(&numeric_context, &string_context, &bool_context);
+
+        let a_gt_1 = numeric("a", TransitionGuardConditionType::GreaterThan, 1.0);
+        let a_gt_4 = numeric("a", TransitionGuardConditionType::GreaterThan, 4.0);
+
+        assert!(is_satisfied(&Guard::And {
+            guards: vec![a_gt_1.clone()]
+        }));
+        assert!(!is_satisfied(&Guard::And {
+            guards: vec![a_gt_1.clone(), a_gt_4.clone()]
+        }));
+        assert!(is_satisfied(&Guard::Or {
+            guards: vec![a_gt_4.clone(), a_gt_1.clone()]
+        }));
+        assert!(is_satisfied(&Guard::Not {
+            guard: Box::new(a_gt_4)
+        }));
+
+        // a < b
+        assert!(is_satisfied(&Guard::Numeric {
+            context_key: "a".to_string(),
+            condition_type: TransitionGuardConditionType::LessThan,
+            compare_to: GuardOperand::ContextKey("b".to_string()),
+        }));
+
+        // a is within [1, 3], b is not
+        let in_range = |key: &str| Guard::Numeric {
+            context_key: key.to_string(),
+            condition_type: TransitionGuardConditionType::InRange,
+            compare_to: GuardOperand::Range { min: 1.0, max: 3.0 },
+        };
+        assert!(is_satisfied(&in_range("a")));
+        assert!(!is_satisfied(&in_range("b")));
+
+        assert!(is_satisfied(&Guard::String {
+            context_key: "name".to_string(),
+            condition_type: TransitionGuardConditionType::StartsWith,
+            compare_to: GuardOperand::Value(StringNumberBool::String("star".to_string())),
+        }));
+
+        // Comparing with a missing context key never succeeds
+        assert!(!is_satisfied(&Guard::Boolean {
+            context_key: "on".to_string(),
+            condition_type: TransitionGuardConditionType::Equal,
+            compare_to: GuardOperand::ContextKey("missing".to_string()),
+        }));
+    }
 }
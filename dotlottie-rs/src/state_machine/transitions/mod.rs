@@ -3,6 +3,7 @@ pub mod guard;
 use std::sync::{Arc, RwLock};
 
 use crate::state_machine::events::InternalEvent;
+use crate::{AnimationTransition, ContextAction};
 
 use self::guard::Guard;
 
@@ -13,6 +14,8 @@ pub trait TransitionTrait {
     fn get_target_state(&self) -> u32;
     fn get_guards(&self) -> &Vec<Guard>;
     fn get_event(&self) -> Arc<RwLock<InternalEvent>>;
+    fn get_animation_transition(&self) -> Option<&AnimationTransition>;
+    fn get_actions(&self) -> &Vec<ContextAction>;
 }
 
 #[derive(Debug)]
@@ -21,6 +24,8 @@ pub enum Transition {
         target_state: u32,
         event: Arc<RwLock<InternalEvent>>,
         guards: Vec<Guard>,
+        animation_transition: Option<AnimationTransition>,
+        actions: Vec<ContextAction>,
     },
 }
 
@@ -58,4 +63,19 @@ impl TransitionTrait for Transition {
             Transition::Transition { guards, .. } => guards,
         }
     }
+
+    fn get_animation_transition(&self) -> Option<&AnimationTransition> {
+        match self {
+            Transition::Transition {
+                animation_transition,
+                ..
+            } => animation_transition.as_ref(),
+        }
+    }
+
+    fn get_actions(&self) -> &Vec<ContextAction> {
+        match self {
+            Transition::Transition { actions, .. } => actions,
+        }
+    }
 }
@@ -7,7 +7,7 @@ use thiserror::Error;
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum TvgError {
     #[error("Invalid argument provided in {function_name}")]
     InvalidArgument { function_name: String },
@@ -33,6 +33,7 @@ pub enum TvgEngine {
     TvgEngineGl,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TvgColorspace {
     ABGR8888,
     ABGR8888S,
@@ -314,6 +315,44 @@ impl Animation {
         convert_tvg_result(result, "tvg_paint_translate")
     }
 
+    pub fn rotate(&mut self, degree: f32) -> Result<(), TvgError> {
+        let result = unsafe { tvg_paint_rotate(self.raw_paint, degree) };
+
+        convert_tvg_result(result, "tvg_paint_rotate")
+    }
+
+    pub fn set_opacity(&mut self, opacity: u8) -> Result<(), TvgError> {
+        let result = unsafe { tvg_paint_set_opacity(self.raw_paint, opacity) };
+
+        convert_tvg_result(result, "tvg_paint_set_opacity")
+    }
+
+    /// Only draw the parts of the animation inside the clip shape, the shape is owned and freed
+    /// by ThorVG from now on.
+    pub fn set_clip(&mut self, clip: Shape) -> Result<(), TvgError> {
+        let result = unsafe {
+            tvg_paint_set_composite_method(
+                self.raw_paint,
+                clip.raw_shape,
+                Tvg_Composite_Method_TVG_COMPOSITE_METHOD_CLIP_PATH,
+            )
+        };
+
+        convert_tvg_result(result, "tvg_paint_set_composite_method")
+    }
+
+    pub fn clear_clip(&mut self) -> Result<(), TvgError> {
+        let result = unsafe {
+            tvg_paint_set_composite_method(
+                self.raw_paint,
+                ptr::null_mut(),
+                Tvg_Composite_Method_TVG_COMPOSITE_METHOD_NONE,
+            )
+        };
+
+        convert_tvg_result(result, "tvg_paint_set_composite_method")
+    }
+
     pub fn get_total_frame(&self) -> Result<f32, TvgError> {
         let mut total_frame: f32 = 0.0;
 
@@ -0,0 +1,123 @@
+/// The direction the animations move in during a slide or wipe transition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl TransitionDirection {
+    // unit vector of the movement, in canvas coordinates
+    pub(crate) fn vector(&self) -> (f32, f32) {
+        match self {
+            TransitionDirection::Left => (-1.0, 0.0),
+            TransitionDirection::Right => (1.0, 0.0),
+            TransitionDirection::Up => (0.0, -1.0),
+            TransitionDirection::Down => (0.0, 1.0),
+        }
+    }
+}
+
+/// How the outgoing animation is replaced by the incoming one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransitionEffect {
+    /// The outgoing animation fades out while the incoming one fades in.
+    Crossfade,
+    /// The incoming animation pushes the outgoing one off the canvas.
+    Slide { direction: TransitionDirection },
+    /// The incoming animation is revealed over the outgoing one by a moving edge.
+    Wipe { direction: TransitionDirection },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map the linear progress of a transition, from 0 to 1, to its eased progress.
+    pub fn apply(&self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// A blended transition between the animation on screen and the next loaded one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationTransition {
+    pub effect: TransitionEffect,
+    /// Duration in seconds.
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl AnimationTransition {
+    pub fn new(effect: TransitionEffect, duration: f32, easing: Easing) -> Self {
+        AnimationTransition {
+            effect,
+            duration,
+            easing,
+        }
+    }
+
+    /// The eased progress of the transition after the elapsed time in seconds, from 0 to 1.
+    pub fn progress(&self, elapsed: f32) -> f32 {
+        if self.duration <= 0.0 || !self.duration.is_finite() {
+            return 1.0;
+        }
+
+        self.easing.apply(elapsed / self.duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easing() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+        }
+
+        assert_eq!(Easing::Linear.apply(0.25), 0.25);
+        assert!(Easing::EaseIn.apply(0.5) < 0.5);
+        assert!(Easing::EaseOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_progress() {
+        let transition = AnimationTransition::new(TransitionEffect::Crossfade, 0.5, Easing::Linear);
+
+        assert_eq!(transition.progress(0.0), 0.0);
+        assert_eq!(transition.progress(0.25), 0.5);
+        assert_eq!(transition.progress(1.0), 1.0);
+
+        let instant = AnimationTransition::new(TransitionEffect::Crossfade, 0.0, Easing::Linear);
+        assert_eq!(instant.progress(0.0), 1.0);
+    }
+}
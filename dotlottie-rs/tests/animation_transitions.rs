@@ -0,0 +1,131 @@
+mod test_utils;
+
+use crate::test_utils::{HEIGHT, WIDTH};
+use dotlottie_rs::{
+    AnimationTransition, Config, DotLottiePlayer, Easing, TransitionDirection, TransitionEffect,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emoji_player() -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.load_dotlottie_data(include_bytes!("fixtures/emoji.lottie"), WIDTH, HEIGHT));
+        // Drive the transitions with a manual clock
+        player.tick(0.0);
+
+        player
+    }
+
+    #[test]
+    fn test_transition_effects() {
+        let effects = [
+            TransitionEffect::Crossfade,
+            TransitionEffect::Slide {
+                direction: TransitionDirection::Left,
+            },
+            TransitionEffect::Wipe {
+                direction: TransitionDirection::Down,
+            },
+        ];
+
+        for effect in effects {
+            let player = emoji_player();
+            let transition = AnimationTransition::new(effect, 0.5, Easing::EaseInOut);
+
+            assert!(player.load_animation_with_transition("wink", WIDTH, HEIGHT, transition));
+            assert_eq!(player.active_animation_id(), "wink");
+            assert!(player.is_transitioning());
+
+            player.tick(250.0);
+            assert!(player.render());
+            assert!(player.is_transitioning(), "{:?}", effect);
+
+            player.tick(250.0);
+            assert!(player.render());
+            assert!(!player.is_transitioning(), "{:?}", effect);
+        }
+    }
+
+    #[test]
+    fn test_transition_without_animation_on_screen() {
+        let player = DotLottiePlayer::new(Config::default());
+        let transition = AnimationTransition::new(TransitionEffect::Crossfade, 0.5, Easing::Linear);
+
+        assert!(!player.load_animation_with_transition("wink", WIDTH, HEIGHT, transition));
+        assert!(!player.is_transitioning());
+    }
+
+    #[test]
+    fn test_load_ends_transition() {
+        let player = emoji_player();
+        let transition = AnimationTransition::new(TransitionEffect::Crossfade, 1.0, Easing::Linear);
+
+        assert!(player.load_animation_with_transition("wink", WIDTH, HEIGHT, transition));
+        assert!(player.is_transitioning());
+
+        // A load without transition cuts to the new animation
+        assert!(player.load_animation("anger", WIDTH, HEIGHT));
+        assert!(!player.is_transitioning());
+
+        // Failing to load the next animation cuts the transition short
+        assert!(player.load_animation_with_transition("wink", WIDTH, HEIGHT, transition));
+        assert!(!player.load_animation_with_transition("missing", WIDTH, HEIGHT, transition));
+        assert!(!player.is_transitioning());
+    }
+
+    #[test]
+    fn test_state_machine_transition() {
+        let player = emoji_player();
+
+        let state_machine = r#"{
+            "descriptor": { "id": "emoji", "initial": 0 },
+            "states": [
+                { "name": "anger", "type": "PlaybackState", "animation_id": "anger", "autoplay": true },
+                { "name": "wink", "type": "PlaybackState", "animation_id": "wink", "autoplay": true }
+            ],
+            "transitions": [
+                {
+                    "type": "Transition",
+                    "from_state": 0,
+                    "to_state": 1,
+                    "string_event": { "value": "wink" },
+                    "animation_transition": {
+                        "type": "Slide",
+                        "direction": "Up",
+                        "duration": 0.5,
+                        "easing": "EaseOut"
+                    }
+                },
+                {
+                    "type": "Transition",
+                    "from_state": 1,
+                    "to_state": 0,
+                    "string_event": { "value": "anger" }
+                }
+            ],
+            "listeners": [],
+            "context_variables": []
+        }"#;
+
+        assert!(player.load_state_machine_data(state_machine));
+        assert!(player.start_state_machine());
+        assert_eq!(player.active_animation_id(), "anger");
+        assert!(!player.is_transitioning());
+
+        player.post_string_event("wink");
+        assert_eq!(player.active_animation_id(), "wink");
+        assert!(player.is_transitioning());
+
+        player.tick(500.0);
+        assert!(player.render());
+        assert!(!player.is_transitioning());
+
+        // Transitions without animation_transition hard-cut
+        player.post_string_event("anger");
+        assert_eq!(player.active_animation_id(), "anger");
+        assert!(!player.is_transitioning());
+    }
+}
@@ -0,0 +1,138 @@
+mod test_utils;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::test_utils::{HEIGHT, WIDTH};
+use dotlottie_rs::{Config, DotLottiePlayer, ManualClock};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_frame(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "Expected frame {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_tick() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.load_animation_path("tests/fixtures/test.json", WIDTH, HEIGHT));
+        assert!(player.play());
+
+        let duration_ms = player.duration() as f64 * 1000.0;
+        let total_frames = player.total_frames();
+
+        assert_frame(player.tick(0.0), 0.0);
+        assert_frame(player.tick(duration_ms / 4.0), total_frames / 4.0);
+        assert_frame(player.tick(duration_ms / 4.0), total_frames / 2.0);
+
+        // Time doesn't move between ticks
+        assert_frame(player.request_frame(), total_frames / 2.0);
+
+        assert_frame(player.tick(duration_ms), total_frames);
+    }
+
+    #[test]
+    fn test_request_frame_at() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.load_animation_path("tests/fixtures/test.json", WIDTH, HEIGHT));
+        assert!(player.play());
+
+        let duration_ms = player.duration() as f64 * 1000.0;
+        let total_frames = player.total_frames();
+
+        // The first timestamp of the host clock is the start of the playback
+        assert_frame(player.request_frame_at(5000.0), 0.0);
+        assert_frame(
+            player.request_frame_at(5000.0 + duration_ms / 2.0),
+            total_frames / 2.0,
+        );
+    }
+
+    #[test]
+    fn test_seek_and_speed_with_manual_clock() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.load_animation_path("tests/fixtures/test.json", WIDTH, HEIGHT));
+        assert!(player.play());
+
+        let duration_ms = player.duration() as f64 * 1000.0;
+        let total_frames = player.total_frames();
+
+        player.tick(0.0);
+
+        assert!(player.seek(total_frames / 2.0));
+
+        let next_frame = player.tick(duration_ms / 4.0);
+        assert_frame(next_frame, total_frames * 3.0 / 4.0);
+        assert!(player.set_frame(next_frame));
+
+        player.set_config(Config {
+            speed: 2.0,
+            ..player.config()
+        });
+        assert_frame(player.tick(duration_ms / 8.0), total_frames);
+    }
+
+    #[test]
+    fn test_set_clock() {
+        let player = DotLottiePlayer::new(Config {
+            loop_animation: true,
+            use_frame_interpolation: false,
+            ..Config::default()
+        });
+
+        let clock = ManualClock::default();
+        player.set_clock(Arc::new(clock.clone()));
+
+        assert!(player.load_animation_path("tests/fixtures/test.json", WIDTH, HEIGHT));
+        assert!(player.play());
+
+        let duration = Duration::from_secs_f32(player.duration());
+        let total_frames = player.total_frames();
+
+        clock.advance(duration / 4);
+        assert_frame(player.request_frame(), (total_frames / 4.0).round());
+
+        clock.advance(duration);
+        assert_frame(player.request_frame(), total_frames);
+        assert_eq!(player.loop_count(), 1);
+
+        // The next loop starts from the request that completed the previous one
+        clock.advance(duration / 4);
+        assert_frame(player.request_frame(), (total_frames / 4.0).round());
+    }
+
+    #[test]
+    fn test_deterministic_playback() {
+        let frames = |player: &DotLottiePlayer| {
+            assert!(player.load_animation_path("tests/fixtures/test.json", WIDTH, HEIGHT));
+            assert!(player.play());
+
+            (0..30)
+                .map(|_| {
+                    let next_frame = player.tick(1000.0 / 60.0);
+
+                    if player.set_frame(next_frame) {
+                        player.render();
+                    }
+
+                    next_frame
+                })
+                .collect::<Vec<f32>>()
+        };
+
+        let first_player = DotLottiePlayer::new(Config::default());
+        let second_player = DotLottiePlayer::new(Config::default());
+
+        assert_eq!(frames(&first_player), frames(&second_player));
+    }
+}
@@ -0,0 +1,181 @@
+mod test_utils;
+
+use std::time::Duration;
+
+use crate::test_utils::{HEIGHT, WIDTH};
+use dotlottie_rs::{Composition, CompositionError, ItemConfig, Mode, Transform};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emoji_composition() -> Composition {
+        let mut composition = Composition::new(WIDTH, HEIGHT).unwrap();
+
+        composition
+            .load_dotlottie_data(include_bytes!("fixtures/emoji.lottie"))
+            .unwrap();
+
+        composition
+    }
+
+    #[test]
+    fn test_add_items() {
+        let mut composition = emoji_composition();
+
+        let background = composition
+            .add_animation(
+                "anger",
+                ItemConfig {
+                    loop_animation: true,
+                    ..ItemConfig::default()
+                },
+            )
+            .unwrap();
+        let overlay = composition
+            .add_animation_data(include_str!("fixtures/test.json"), ItemConfig::default())
+            .unwrap();
+
+        assert_ne!(background, overlay);
+        assert_eq!(composition.items(), vec![background, overlay]);
+
+        assert!(composition.render().is_ok());
+        assert_eq!(composition.buffer().len(), (WIDTH * HEIGHT) as usize);
+
+        assert!(matches!(
+            composition.add_animation("missing", ItemConfig::default()),
+            Err(CompositionError::DotLottieError(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_animation_without_dotlottie() {
+        let mut composition = Composition::new(WIDTH, HEIGHT).unwrap();
+
+        assert!(matches!(
+            composition.add_animation("anger", ItemConfig::default()),
+            Err(CompositionError::NoDotLottieLoaded)
+        ));
+    }
+
+    #[test]
+    fn test_independent_playback() {
+        let mut composition = emoji_composition();
+
+        let background = composition
+            .add_animation(
+                "anger",
+                ItemConfig {
+                    loop_animation: true,
+                    ..ItemConfig::default()
+                },
+            )
+            .unwrap();
+        let overlay = composition
+            .add_animation(
+                "wink",
+                ItemConfig {
+                    autoplay: false,
+                    mode: Mode::Reverse,
+                    ..ItemConfig::default()
+                },
+            )
+            .unwrap();
+
+        assert!(composition.is_playing(background).unwrap());
+        assert!(!composition.is_playing(overlay).unwrap());
+
+        let overlay_first_frame = composition.current_frame(overlay).unwrap();
+
+        assert!(composition.tick(Duration::from_millis(500)).unwrap());
+        assert!(composition.current_frame(background).unwrap() > 0.0);
+        assert_eq!(
+            composition.current_frame(overlay).unwrap(),
+            overlay_first_frame
+        );
+
+        // The one-shot overlay plays once from the time it is started
+        composition.play(overlay).unwrap();
+        composition.tick(Duration::from_secs(60)).unwrap();
+
+        assert!(composition.is_complete(overlay).unwrap());
+        assert_eq!(composition.current_frame(overlay).unwrap(), 0.0);
+
+        assert!(!composition.is_complete(background).unwrap());
+        assert!(composition.loop_count(background).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_pause_and_stop() {
+        let mut composition = emoji_composition();
+
+        let item = composition
+            .add_animation("melting", ItemConfig::default())
+            .unwrap();
+
+        composition.tick(Duration::from_millis(200)).unwrap();
+        composition.pause(item).unwrap();
+
+        let paused_frame = composition.current_frame(item).unwrap();
+        assert!(paused_frame > 0.0);
+
+        composition.tick(Duration::from_millis(200)).unwrap();
+        assert_eq!(composition.current_frame(item).unwrap(), paused_frame);
+
+        composition.play(item).unwrap();
+        composition.tick(Duration::from_millis(200)).unwrap();
+        assert!(composition.current_frame(item).unwrap() > paused_frame);
+
+        composition.stop(item).unwrap();
+        assert_eq!(composition.current_frame(item).unwrap(), 0.0);
+        assert!(!composition.is_playing(item).unwrap());
+    }
+
+    #[test]
+    fn test_z_order_transform_and_opacity() {
+        let mut composition = emoji_composition();
+
+        let first = composition
+            .add_animation("anger", ItemConfig::default())
+            .unwrap();
+        let second = composition
+            .add_animation(
+                "wink",
+                ItemConfig {
+                    z_index: -1,
+                    ..ItemConfig::default()
+                },
+            )
+            .unwrap();
+
+        assert_eq!(composition.items(), vec![second, first]);
+
+        composition.set_z_index(second, 1).unwrap();
+        assert_eq!(composition.items(), vec![first, second]);
+
+        let transform = Transform {
+            x: 10.0,
+            y: 20.0,
+            scale: 0.5,
+            rotation: 45.0,
+        };
+
+        composition.set_transform(second, transform).unwrap();
+        composition.set_opacity(second, 0.5).unwrap();
+
+        let config = composition.item_config(second).unwrap();
+        assert_eq!(config.transform, transform);
+        assert_eq!(config.opacity, 0.5);
+
+        assert!(composition.render().is_ok());
+
+        composition.remove_item(first).unwrap();
+        assert_eq!(composition.items(), vec![second]);
+        assert!(composition.render().is_ok());
+
+        assert!(matches!(
+            composition.set_opacity(first, 1.0),
+            Err(CompositionError::ItemNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+mod test_utils;
+use crate::test_utils::{HEIGHT, WIDTH};
+
+use dotlottie_rs::{
+    Config, DotLottieError, DotLottiePlayer, DotLottiePlayerError, LottieRendererError,
+};
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_load_animation_path_error() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        let result = player.try_load_animation_path("tests/fixtures/missing.json", WIDTH, HEIGHT);
+
+        assert!(matches!(
+            result,
+            Err(DotLottiePlayerError::FileReadError { .. })
+        ));
+        assert!(matches!(
+            player.last_error(),
+            Some(DotLottiePlayerError::FileReadError { .. })
+        ));
+        assert!(player
+            .last_error_message()
+            .contains("tests/fixtures/missing.json"));
+        assert!(!player.is_loaded());
+    }
+
+    #[test]
+    fn test_load_dotlottie_data_error() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(matches!(
+            player.try_load_dotlottie_data(&[0, 1, 2, 3], WIDTH, HEIGHT),
+            Err(DotLottiePlayerError::DotLottieError(
+                DotLottieError::ArchiveOpenError
+            ))
+        ));
+
+        assert!(!player.load_dotlottie_data(&[0, 1, 2, 3], WIDTH, HEIGHT));
+        assert!(matches!(
+            player.last_error(),
+            Some(DotLottiePlayerError::DotLottieError(
+                DotLottieError::ArchiveOpenError
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_load_animation_not_found() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.load_dotlottie_data(include_bytes!("fixtures/test.lottie"), WIDTH, HEIGHT));
+        assert!(player.last_error().is_none());
+
+        assert!(matches!(
+            player.try_load_animation("missing", WIDTH, HEIGHT),
+            Err(DotLottiePlayerError::DotLottieError(
+                DotLottieError::AnimationNotFound { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_load_theme_errors() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(matches!(
+            player.try_load_theme("test_theme"),
+            Err(DotLottiePlayerError::ThemeNotFound { .. })
+        ));
+
+        assert!(player.load_dotlottie_data(include_bytes!("fixtures/test.lottie"), WIDTH, HEIGHT));
+
+        assert!(matches!(
+            player.try_load_theme("invalid_theme"),
+            Err(DotLottiePlayerError::ThemeNotFound { .. })
+        ));
+
+        assert!(player.try_load_theme("test_theme").is_ok());
+        assert!(player.last_error().is_none());
+    }
+
+    #[test]
+    fn test_set_frame_and_resize_errors() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.load_animation_path("tests/fixtures/test.json", WIDTH, HEIGHT));
+
+        let total_frames = player.total_frames();
+
+        assert!(matches!(
+            player.try_set_frame(total_frames + 1.0),
+            Err(DotLottiePlayerError::FrameOutOfRange { .. })
+        ));
+        assert!(matches!(
+            player.try_seek(-1.0),
+            Err(DotLottiePlayerError::FrameOutOfRange { .. })
+        ));
+        assert!(player.try_set_frame(total_frames / 2.0).is_ok());
+
+        assert!(matches!(
+            player.try_resize(0, 0),
+            Err(DotLottiePlayerError::RendererError(
+                LottieRendererError::InvalidArgument(_)
+            ))
+        ));
+        assert!(!player.last_error_message().is_empty());
+    }
+
+    #[test]
+    fn test_load_state_machine_errors() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.load_dotlottie_data(include_bytes!("fixtures/test.lottie"), WIDTH, HEIGHT));
+
+        assert!(matches!(
+            player.try_load_state_machine_data("{ not json"),
+            Err(DotLottiePlayerError::StateMachineError(_))
+        ));
+        assert!(matches!(
+            player.try_load_state_machine("missing"),
+            Err(DotLottiePlayerError::DotLottieError(
+                DotLottieError::FileFindError { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,306 @@
+#![cfg(feature = "export")]
+
+mod test_utils;
+use crate::test_utils::{HEIGHT, WIDTH};
+
+use dotlottie_rs::{
+    AnimatedFormat, AnimationSource, Config, ExportConfig, ExportError, FrameExporter,
+    FrameSelection, LottieRenderer, Mode,
+};
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn config(frames: FrameSelection) -> ExportConfig {
+        ExportConfig {
+            width: Some(WIDTH),
+            height: Some(HEIGHT),
+            frames,
+            ..ExportConfig::default()
+        }
+    }
+
+    fn total_frames() -> f32 {
+        let mut renderer = LottieRenderer::new();
+
+        renderer
+            .load_data(include_str!("fixtures/test.json"), WIDTH, HEIGHT, true)
+            .unwrap();
+
+        renderer.total_frames().unwrap()
+    }
+
+    #[test]
+    fn test_export_single_frame() {
+        let mut exporter = FrameExporter::new(
+            AnimationSource::Json(include_str!("fixtures/test.json")),
+            config(FrameSelection::Frame(10.0)),
+        )
+        .unwrap();
+
+        let frames = exporter.render_frames().unwrap();
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].frame, 10.0);
+        assert_eq!((frames[0].width, frames[0].height), (WIDTH, HEIGHT));
+        assert_eq!(frames[0].data.len(), (WIDTH * HEIGHT * 4) as usize);
+        assert!(
+            frames[0].data.chunks(4).any(|pixel| pixel[3] != 0),
+            "Frame should not be empty"
+        );
+    }
+
+    #[test]
+    fn test_export_frame_selection() {
+        let last_frame = total_frames() - 1.0;
+
+        let exporter = FrameExporter::new(
+            AnimationSource::Json(include_str!("fixtures/test.json")),
+            config(FrameSelection::Range {
+                start: 5.0,
+                end: last_frame + 10.0,
+            }),
+        )
+        .unwrap();
+
+        let frames = exporter.frames().unwrap();
+        assert_eq!(frames.first(), Some(&5.0));
+        assert_eq!(frames.last(), Some(&last_frame));
+
+        let exporter = FrameExporter::new(
+            AnimationSource::Json(include_str!("fixtures/test.json")),
+            config(FrameSelection::Frame(last_frame + 1.0)),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            exporter.frames(),
+            Err(ExportError::InvalidFrameSelection(_))
+        ));
+
+        let exporter = FrameExporter::new(
+            AnimationSource::Json(include_str!("fixtures/test.json")),
+            config(FrameSelection::Fps(0.0)),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            exporter.frames(),
+            Err(ExportError::InvalidFrameSelection(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_dotlottie_png_sequence() {
+        let output_dir = std::env::temp_dir().join("dotlottie_export_png_sequence");
+        let _ = std::fs::remove_dir_all(&output_dir);
+
+        let mut exporter = FrameExporter::new(
+            AnimationSource::DotLottie(include_bytes!("fixtures/test.lottie")),
+            ExportConfig {
+                theme_id: "test_theme".to_string(),
+                background_color: 0xFFFFFFFF,
+                ..config(FrameSelection::Fps(5.0))
+            },
+        )
+        .unwrap();
+
+        let frames = exporter.frames().unwrap();
+        let paths = exporter.export_png_sequence(&output_dir).unwrap();
+
+        assert!(!paths.is_empty());
+        assert_eq!(paths.len(), frames.len());
+        assert!(paths[0].ends_with("frame_0000.png"));
+
+        let png_data = std::fs::read(&paths[0]).unwrap();
+        assert!(png_data.starts_with(b"\x89PNG"));
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+    }
+
+    #[test]
+    fn test_export_background_color() {
+        let mut exporter = FrameExporter::new(
+            AnimationSource::Json(include_str!("fixtures/test.json")),
+            ExportConfig {
+                width: Some(WIDTH * 2),
+                background_color: 0xFF0000FF,
+                ..config(FrameSelection::Frame(0.0))
+            },
+        )
+        .unwrap();
+
+        let frame = exporter.render_frame(0.0).unwrap();
+
+        // The square animation is centered in a wider canvas, the left edge only shows the background
+        assert_eq!(&frame.data[..4], &[255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_export_animation_not_found() {
+        assert!(matches!(
+            FrameExporter::new(
+                AnimationSource::DotLottie(include_bytes!("fixtures/test.lottie")),
+                ExportConfig {
+                    animation_id: "missing".to_string(),
+                    ..config(FrameSelection::Frame(0.0))
+                },
+            ),
+            Err(ExportError::DotLottieError(_))
+        ));
+    }
+
+    fn json_exporter() -> FrameExporter {
+        FrameExporter::new(
+            AnimationSource::Json(include_str!("fixtures/test.json")),
+            config(FrameSelection::Frame(0.0)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_playback_timeline() {
+        let exporter = json_exporter();
+        let last_frame = total_frames() - 1.0;
+
+        let timeline = exporter
+            .playback_timeline(&Config::default(), None)
+            .unwrap();
+
+        assert_eq!(timeline.frames.first(), Some(&0.0));
+        assert_eq!(timeline.frames.last(), Some(&last_frame));
+        assert!(!timeline.loop_animation);
+
+        // Twice the speed shows half of the frames
+        let fast_timeline = exporter
+            .playback_timeline(
+                &Config {
+                    speed: 2.0,
+                    ..Config::default()
+                },
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(fast_timeline.fps, timeline.fps);
+        assert_eq!(
+            fast_timeline.frames.len() - 1,
+            (timeline.frames.len() - 1) / 2
+        );
+    }
+
+    #[test]
+    fn test_playback_timeline_segment_and_mode() {
+        let exporter = json_exporter();
+
+        let timeline = exporter
+            .playback_timeline(
+                &Config {
+                    segment: vec![10.0, 20.0],
+                    mode: Mode::Reverse,
+                    use_frame_interpolation: false,
+                    ..Config::default()
+                },
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(timeline.frames.first(), Some(&20.0));
+        assert_eq!(timeline.frames.last(), Some(&10.0));
+        assert!(timeline.frames.iter().all(|frame| frame.fract() == 0.0));
+
+        let timeline = exporter
+            .playback_timeline(
+                &Config {
+                    segment: vec![10.0, 20.0],
+                    mode: Mode::Bounce,
+                    loop_animation: true,
+                    ..Config::default()
+                },
+                Some(10.0),
+            )
+            .unwrap();
+
+        let turn = timeline
+            .frames
+            .iter()
+            .position(|frame| *frame == 20.0)
+            .unwrap();
+
+        assert_eq!(timeline.frames.first(), Some(&10.0));
+        assert!(timeline.frames[turn + 1..]
+            .iter()
+            .all(|frame| *frame < 20.0));
+        // The loop restarts on the first frame, it isn't repeated at the end
+        assert!(timeline.frames.last() > Some(&10.0));
+        assert!(timeline.loop_animation);
+    }
+
+    #[test]
+    fn test_playback_timeline_loop_duration() {
+        let exporter = json_exporter();
+        let mut renderer = LottieRenderer::new();
+
+        renderer
+            .load_data(include_str!("fixtures/test.json"), WIDTH, HEIGHT, true)
+            .unwrap();
+
+        let last_frame = renderer.total_frames().unwrap() - 1.0;
+        let segment_duration = renderer.duration().unwrap() * 10.0 / last_frame;
+
+        for mode in [Mode::Forward, Mode::Reverse] {
+            let config = Config {
+                segment: vec![10.0, 20.0],
+                mode,
+                speed: 2.0,
+                loop_animation: true,
+                ..Config::default()
+            };
+            let timeline = exporter.playback_timeline(&config, Some(30.0)).unwrap();
+
+            // Each loop lasts as long as in the player, without repeating a frame at the seam
+            let loop_duration = timeline.frames.len() as f32 / timeline.fps;
+
+            assert!((loop_duration - segment_duration / config.speed).abs() < 0.5 / timeline.fps);
+            assert_ne!(timeline.frames.first(), timeline.frames.last());
+        }
+    }
+
+    #[test]
+    fn test_export_animated_gif() {
+        let path = std::env::temp_dir().join("dotlottie_export_animation.gif");
+
+        let mut exporter = FrameExporter::new(
+            AnimationSource::DotLottie(include_bytes!("fixtures/test.lottie")),
+            ExportConfig {
+                theme_id: "test_theme".to_string(),
+                ..config(FrameSelection::Frame(0.0))
+            },
+        )
+        .unwrap();
+
+        let playback = Config {
+            loop_animation: true,
+            ..Config::default()
+        };
+
+        exporter
+            .write_animation(&path, AnimatedFormat::Gif, &playback, Some(10.0))
+            .unwrap();
+
+        let gif_data = std::fs::read(&path).unwrap();
+        assert!(gif_data.starts_with(b"GIF89a"));
+
+        std::fs::remove_file(&path).unwrap();
+
+        let mut webp_data = vec![];
+        exporter
+            .export_animation(&mut webp_data, AnimatedFormat::WebP, &playback, Some(10.0))
+            .unwrap();
+
+        assert!(webp_data.starts_with(b"RIFF"));
+        assert_eq!(&webp_data[8..12], b"WEBP");
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+
+use dotlottie_rs::{compare_frames, ExportedFrame, FrameExporter};
+
+/// Color difference at which two pixels are counted as different, from 0 to 1.
+pub const THRESHOLD: f32 = 0.1;
+
+/// Share of pixels allowed to differ, to absorb antialiasing differences between platforms.
+pub const MAX_DIFF_RATIO: f32 = 0.001;
+
+/// Compares a frame rendered by the exporter with the reference image tests/snapshots/<name>.png.
+///
+/// Run the tests with UPDATE_SNAPSHOTS=1 to record missing references, or to replace the existing
+/// ones after an intended rendering change. On failure, the rendered frame and an image
+/// highlighting the differing pixels are written next to the test binaries.
+pub fn assert_snapshot(name: &str, exporter: &mut FrameExporter, frame: f32) {
+    let actual = exporter
+        .render_frame(frame)
+        .unwrap_or_else(|error| panic!("Snapshot {}: failed to render: {}", name, error));
+
+    let reference_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{}.png", name));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(reference_path.parent().unwrap()).unwrap();
+        actual.write_png(&reference_path).unwrap();
+
+        eprintln!("Snapshot {}: recorded {}", name, reference_path.display());
+
+        return;
+    }
+
+    let Ok(reference_data) = std::fs::read(&reference_path) else {
+        let actual_path = output_path(name, "actual");
+        actual.write_png(&actual_path).unwrap();
+
+        panic!(
+            "Snapshot {}: {} is missing, record it with UPDATE_SNAPSHOTS=1\nRendered frame: {}",
+            name,
+            reference_path.display(),
+            actual_path.display()
+        );
+    };
+    let expected = ExportedFrame::from_png(frame, &reference_data).unwrap();
+
+    let failure = match compare_frames(&expected, &actual, THRESHOLD) {
+        Ok(diff) if diff.diff_ratio() <= MAX_DIFF_RATIO => return,
+        Ok(diff) => {
+            let diff_path = output_path(name, "diff");
+            diff.diff_image.write_png(&diff_path).unwrap();
+
+            format!(
+                "{} of {} pixels differ ({:.3}%), see {}",
+                diff.diff_pixels,
+                diff.total_pixels,
+                diff.diff_ratio() * 100.0,
+                diff_path.display()
+            )
+        }
+        Err(error) => error.to_string(),
+    };
+
+    let actual_path = output_path(name, "actual");
+    actual.write_png(&actual_path).unwrap();
+
+    panic!(
+        "Snapshot {} does not match {}: {}\nRendered frame: {}",
+        name,
+        reference_path.display(),
+        failure,
+        actual_path.display()
+    );
+}
+
+fn output_path(name: &str, kind: &str) -> PathBuf {
+    let output_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("snapshots");
+    std::fs::create_dir_all(&output_dir).unwrap();
+
+    output_dir.join(format!("{}.{}.png", name, kind))
+}
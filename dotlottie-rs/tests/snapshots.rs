@@ -0,0 +1,121 @@
+#![cfg(feature = "export")]
+
+mod snapshot_utils;
+mod test_utils;
+use crate::snapshot_utils::assert_snapshot;
+use crate::test_utils::{HEIGHT, WIDTH};
+
+use dotlottie_rs::{AnimationSource, ExportConfig, Fit, FrameExporter, Layout};
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn json_exporter(config: ExportConfig) -> FrameExporter {
+        FrameExporter::new(
+            AnimationSource::Json(include_str!("fixtures/test.json")),
+            ExportConfig {
+                width: config.width.or(Some(WIDTH)),
+                height: config.height.or(Some(HEIGHT)),
+                ..config
+            },
+        )
+        .unwrap()
+    }
+
+    fn dotlottie_exporter(file_data: &[u8], config: ExportConfig) -> FrameExporter {
+        FrameExporter::new(
+            AnimationSource::DotLottie(file_data),
+            ExportConfig {
+                width: Some(WIDTH),
+                height: Some(HEIGHT),
+                ..config
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_frames_snapshots() {
+        let mut exporter = json_exporter(ExportConfig::default());
+
+        assert_snapshot("test_json_frame_0", &mut exporter, 0.0);
+        assert_snapshot("test_json_frame_20", &mut exporter, 20.0);
+        assert_snapshot("test_json_frame_41", &mut exporter, 41.0);
+        // Between two frames, as rendered with frame interpolation
+        assert_snapshot("test_json_frame_20_5", &mut exporter, 20.5);
+    }
+
+    #[test]
+    fn test_layout_snapshots() {
+        let layouts = [
+            ("contain", Layout::new(Fit::Contain, vec![0.5, 0.5])),
+            ("contain_start", Layout::new(Fit::Contain, vec![0.0, 0.0])),
+            ("fill", Layout::new(Fit::Fill, vec![0.5, 0.5])),
+            ("cover", Layout::new(Fit::Cover, vec![0.5, 0.5])),
+            ("fit_width", Layout::new(Fit::FitWidth, vec![0.5, 0.5])),
+            ("fit_height", Layout::new(Fit::FitHeight, vec![0.5, 0.5])),
+            ("none", Layout::new(Fit::None, vec![1.0, 1.0])),
+        ];
+
+        for (name, layout) in layouts {
+            // A wide canvas, so that every fit gives a different result
+            let mut exporter = json_exporter(ExportConfig {
+                width: Some(WIDTH * 2),
+                layout,
+                background_color: 0x00FF00FF,
+                ..ExportConfig::default()
+            });
+
+            assert_snapshot(&format!("test_json_layout_{}", name), &mut exporter, 20.0);
+        }
+    }
+
+    #[test]
+    fn test_theme_snapshots() {
+        let file_data = include_bytes!("fixtures/test.lottie");
+
+        let mut exporter = dotlottie_exporter(file_data, ExportConfig::default());
+        assert_snapshot("test_lottie_no_theme", &mut exporter, 20.0);
+
+        for theme_id in ["test_theme", "global_theme"] {
+            let mut exporter = dotlottie_exporter(
+                file_data,
+                ExportConfig {
+                    theme_id: theme_id.to_string(),
+                    ..ExportConfig::default()
+                },
+            );
+
+            assert_snapshot(&format!("test_lottie_{}", theme_id), &mut exporter, 20.0);
+        }
+    }
+
+    #[test]
+    fn test_background_color_snapshot() {
+        let mut exporter = json_exporter(ExportConfig {
+            background_color: 0x3366CCFF,
+            ..ExportConfig::default()
+        });
+
+        assert_snapshot("test_json_background", &mut exporter, 0.0);
+    }
+
+    #[test]
+    fn test_multi_animation_snapshots() {
+        let file_data = include_bytes!("fixtures/emoji.lottie");
+
+        for animation_id in ["anger", "wink", "melting"] {
+            let mut exporter = dotlottie_exporter(
+                file_data,
+                ExportConfig {
+                    animation_id: animation_id.to_string(),
+                    ..ExportConfig::default()
+                },
+            );
+
+            assert_snapshot(&format!("emoji_{}", animation_id), &mut exporter, 10.0);
+        }
+    }
+}
@@ -41,6 +41,8 @@ mod tests {
                 value: "explosion".to_string(),
             })),
             guards: Vec::new(),
+            animation_transition: None,
+            actions: Vec::new(),
         };
 
         let pigeon_transition_1 = Transition {
@@ -49,6 +51,8 @@ mod tests {
                 value: "complete".to_string(),
             })),
             guards: Vec::new(),
+            animation_transition: None,
+            actions: Vec::new(),
         };
 
         let pigeon_transition_2 = Transition {
@@ -57,6 +61,8 @@ mod tests {
                 value: "complete".to_string(),
             })),
             guards: Vec::new(),
+            animation_transition: None,
+            actions: Vec::new(),
         };
 
         let pigeon_state_0 = State::Playback {
@@ -74,6 +80,8 @@ mod tests {
             },
             reset_context: "".to_string(),
             animation_id: "".to_string(),
+            entry_actions: vec![],
+            exit_actions: vec![],
             transitions: vec![Arc::new(RwLock::new(pigeon_transition_0))],
         };
 
@@ -92,6 +100,8 @@ mod tests {
             },
             reset_context: "".to_string(),
             animation_id: "".to_string(),
+            entry_actions: vec![],
+            exit_actions: vec![],
             transitions: vec![Arc::new(RwLock::new(pigeon_transition_1))],
         };
 
@@ -110,6 +120,8 @@ mod tests {
             },
             reset_context: "".to_string(),
             animation_id: "".to_string(),
+            entry_actions: vec![],
+            exit_actions: vec![],
             transitions: vec![Arc::new(RwLock::new(pigeon_transition_2))],
         };
 
@@ -128,6 +140,8 @@ mod tests {
                     reset_context: _,
                     animation_id: _,
                     transitions: state_transitions,
+                    entry_actions: _,
+                    exit_actions: _,
                 } => match ps {
                     State::Playback {
                         name: _,
@@ -135,6 +149,8 @@ mod tests {
                         reset_context: _,
                         animation_id: _,
                         transitions,
+                        entry_actions: _,
+                        exit_actions: _,
                     } => {
                         let first_transition = &*state_transitions[0].read().unwrap();
                         let second_transition = &*transitions[0].read().unwrap();
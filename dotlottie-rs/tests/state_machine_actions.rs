@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use dotlottie_rs::{Config, DotLottiePlayer, StateAction, StateMachineObserver};
+
+    #[derive(Default)]
+    struct ActionObserver {
+        actions: RwLock<Vec<StateAction>>,
+    }
+
+    impl StateMachineObserver for ActionObserver {
+        fn on_transition(&self, _previous_state: String, _new_state: String) {}
+
+        fn on_state_entered(&self, _entering_state: String) {}
+
+        fn on_state_exit(&self, _leaving_state: String) {}
+
+        fn on_action(&self, action: StateAction) {
+            self.actions.write().unwrap().push(action);
+        }
+    }
+
+    fn howdy() -> StateAction {
+        StateAction::Log {
+            message: "Howdy partner!".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_entry_actions() {
+        let player = DotLottiePlayer::new(Config::default());
+        let observer = Arc::new(ActionObserver::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        assert!(player.load_state_machine_data(include_str!("fixtures/pigeon_fsm.json")));
+        assert!(player.state_machine_subscribe(observer.clone()));
+
+        // The initial state is entered when the state machine starts
+        assert!(player.start_state_machine());
+        assert_eq!(*observer.actions.read().unwrap(), vec![howdy()]);
+
+        // pigeon -> explosion -> feather, without actions
+        player.post_pointer_down_event(0.0, 0.0);
+        player.post_pointer_down_event(0.0, 0.0);
+        assert_eq!(*observer.actions.read().unwrap(), vec![howdy()]);
+
+        // feather -> pigeon
+        player.post_pointer_down_event(0.0, 0.0);
+        assert_eq!(*observer.actions.read().unwrap(), vec![howdy(), howdy()]);
+    }
+
+    #[test]
+    fn test_exit_actions() {
+        let player = DotLottiePlayer::new(Config::default());
+        let observer = Arc::new(ActionObserver::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        let state_machine = r#"{
+            "descriptor": { "id": "actions", "initial": 0 },
+            "states": [
+                {
+                    "name": "pigeon",
+                    "type": "PlaybackState",
+                    "marker": "bird",
+                    "exit_actions": [
+                        { "type": "URLAction", "url": "https://lottiefiles.com", "target": "_blank" },
+                        { "type": "SoundAction", "sound_id": "boom" }
+                    ]
+                },
+                {
+                    "name": "explosion",
+                    "type": "PlaybackState",
+                    "marker": "explosion",
+                    "entry_actions": [
+                        { "type": "LogAction", "message": "exploded" }
+                    ]
+                }
+            ],
+            "transitions": [
+                { "type": "Transition", "from_state": 0, "to_state": 1, "on_pointer_down_event": {} }
+            ],
+            "listeners": [],
+            "context_variables": []
+        }"#;
+
+        assert!(player.load_state_machine_data(state_machine));
+        assert!(player.state_machine_subscribe(observer.clone()));
+        assert!(player.start_state_machine());
+        assert!(observer.actions.read().unwrap().is_empty());
+
+        player.post_pointer_down_event(0.0, 0.0);
+
+        // Exit actions of the previous state run before the entry actions of the next one
+        assert_eq!(
+            *observer.actions.read().unwrap(),
+            vec![
+                StateAction::Url {
+                    url: "https://lottiefiles.com".to_string(),
+                    target: Some("_blank".to_string()),
+                },
+                StateAction::Sound {
+                    sound_id: "boom".to_string(),
+                    target: None,
+                },
+                StateAction::Log {
+                    message: "exploded".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_theme_actions() {
+        let player = DotLottiePlayer::new(Config::default());
+        let observer = Arc::new(ActionObserver::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/test.lottie"), 100, 100);
+
+        let state_machine = r#"{
+            "descriptor": { "id": "themes", "initial": 0 },
+            "states": [
+                {
+                    "name": "themed",
+                    "type": "PlaybackState",
+                    "entry_actions": [{ "type": "ThemeAction", "theme_id": "test_theme" }],
+                    "exit_actions": [{ "type": "ThemeAction", "theme_id": "global_theme" }]
+                },
+                {
+                    "name": "plain",
+                    "type": "PlaybackState"
+                }
+            ],
+            "transitions": [
+                { "type": "Transition", "from_state": 0, "to_state": 1, "string_event": { "value": "next" } }
+            ],
+            "listeners": [],
+            "context_variables": []
+        }"#;
+
+        assert!(player.load_state_machine_data(state_machine));
+        assert!(player.state_machine_subscribe(observer.clone()));
+        assert!(player.start_state_machine());
+
+        assert_eq!(player.active_theme_id(), "test_theme");
+
+        player.post_string_event("next");
+        assert_eq!(player.active_theme_id(), "global_theme");
+
+        // Theme actions are applied by the player, not passed to the observers
+        assert!(observer.actions.read().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::current_state_path;
+    use dotlottie_rs::listeners::ListenerType;
+    use dotlottie_rs::parser::{StringNumberBool, TransitionGuardConditionType};
+    use dotlottie_rs::transitions::guard::{Guard, GuardOperand};
+    use dotlottie_rs::{
+        Config, ContextAction, DotLottiePlayer, InternalEvent, PlaybackStateBuilder,
+        StateMachineBuilder,
+    };
+
+    fn load_player() -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        player
+    }
+
+    fn builder() -> StateMachineBuilder {
+        StateMachineBuilder::new("builder")
+            .initial("pigeon")
+            .state(
+                PlaybackStateBuilder::new("pigeon")
+                    .marker("bird")
+                    .autoplay(true),
+            )
+            .state(
+                PlaybackStateBuilder::new("explosion")
+                    .marker("explosion")
+                    .autoplay(true),
+            )
+            .state(
+                PlaybackStateBuilder::new("feather")
+                    .marker("feather")
+                    .looping(true),
+            )
+            .transition(
+                "pigeon",
+                "explosion",
+                InternalEvent::String {
+                    value: "explode".to_string(),
+                },
+            )
+            .guard(Guard::Boolean {
+                context_key: "armed".to_string(),
+                condition_type: TransitionGuardConditionType::Equal,
+                compare_to: GuardOperand::Value(StringNumberBool::Bool(true)),
+            })
+            .action(ContextAction::Increment {
+                context_key: "explosions".to_string(),
+                value: 1.0,
+            })
+            .transition(
+                "explosion",
+                "feather",
+                InternalEvent::String {
+                    value: "fall".to_string(),
+                },
+            )
+            .listener(ListenerType::PointerDown, None, Vec::new())
+            .numeric_context("explosions", 0.0)
+            .bool_context("armed", false)
+    }
+
+    #[test]
+    fn test_load_builder() {
+        let player = load_player();
+
+        assert!(player.load_state_machine_builder(builder()));
+        assert!(player.start_state_machine());
+        assert_eq!(current_state_path(&player), "pigeon");
+
+        // The guard isn't met yet
+        player.post_string_event("explode");
+        assert_eq!(current_state_path(&player), "pigeon");
+
+        assert!(player.set_state_machine_boolean_context("armed", true));
+        player.post_string_event("explode");
+        assert_eq!(current_state_path(&player), "explosion");
+
+        player.post_string_event("fall");
+        assert_eq!(current_state_path(&player), "feather");
+
+        let state_machine = player.get_state_machine();
+        let state_machine = state_machine.read().unwrap();
+
+        assert_eq!(
+            state_machine
+                .as_ref()
+                .unwrap()
+                .get_numeric_context("explosions"),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_builder_matches_parser() {
+        let built = load_player();
+        assert!(built.load_state_machine_builder(builder()));
+
+        let definition = built.state_machine_to_json();
+
+        let parsed = load_player();
+        assert!(parsed.load_state_machine_data(&definition));
+        assert_eq!(parsed.state_machine_to_json(), definition);
+    }
+
+    #[test]
+    fn test_invalid_builder() {
+        let player = load_player();
+
+        let builder = builder().transition("feather", "nest", InternalEvent::OnComplete);
+
+        assert!(!player.load_state_machine_builder(builder));
+        assert!(player
+            .last_error_message()
+            .contains("transitions[2].to_state"));
+        assert!(player.get_state_machine().read().unwrap().is_none());
+    }
+}
@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod tests {
+    use dotlottie_rs::listeners::ListenerTrait;
+    use dotlottie_rs::parser::StringNumberBool;
+    use dotlottie_rs::states::StateTrait;
+    use dotlottie_rs::transitions::TransitionTrait;
+    use dotlottie_rs::{events::Event, Config, ContextAction, DotLottiePlayer};
+
+    const STATE_MACHINE: &str = r#"{
+        "descriptor": { "id": "context_actions", "initial": 0 },
+        "states": [
+            { "name": "pigeon", "type": "PlaybackState", "marker": "bird" },
+            { "name": "explosion", "type": "PlaybackState", "marker": "explosion" },
+            { "name": "feather", "type": "PlaybackState", "marker": "feather" }
+        ],
+        "transitions": [
+            {
+                "type": "Transition",
+                "from_state": 0,
+                "to_state": 1,
+                "string_event": { "value": "explosion" },
+                "guards": [
+                    { "type": "Numeric", "context_key": "counter_0", "condition_type": "GreaterThanOrEqual", "compare_to": 3 }
+                ],
+                "actions": [
+                    { "type": "Set", "context_key": "status", "value": "exploded" },
+                    { "type": "Toggle", "context_key": "exploded" },
+                    { "type": "Decrement", "context_key": "counter_0", "value": 10 },
+                    { "type": "Clamp", "context_key": "counter_0", "min": 0, "max": 5 }
+                ]
+            },
+            {
+                "type": "Transition",
+                "from_state": 1,
+                "to_state": 2,
+                "string_event": { "value": "complete" },
+                "guards": [
+                    { "type": "Boolean", "context_key": "exploded", "condition_type": "Equal", "compare_to": true }
+                ],
+                "actions": [
+                    { "type": "Append", "context_key": "status", "value": "_twice" },
+                    { "type": "Increment", "context_key": "missing" }
+                ]
+            },
+            {
+                "type": "Transition",
+                "from_state": 2,
+                "to_state": 0,
+                "string_event": { "value": "done" }
+            }
+        ],
+        "listeners": [
+            {
+                "type": "PointerDown",
+                "actions": [
+                    { "type": "Increment", "context_key": "counter_0" }
+                ]
+            },
+            {
+                "type": "PointerUp",
+                "actions": [
+                    { "type": "Increment", "context_key": "counter_0", "value": 0.5 },
+                    { "type": "Set", "context_key": "released", "value": true }
+                ]
+            }
+        ],
+        "context_variables": [
+            { "type": "Numeric", "key": "counter_0", "value": 0 },
+            { "type": "String", "key": "status", "value": "idle" },
+            { "type": "Boolean", "key": "exploded", "value": false }
+        ]
+    }"#;
+
+    fn load_player() -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        assert!(player.load_state_machine_data(STATE_MACHINE));
+        player.start_state_machine();
+
+        player
+    }
+
+    // Helper function to get the current state's name
+    pub fn get_current_state_name(player: &DotLottiePlayer) -> String {
+        player
+            .get_state_machine()
+            .read()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .get_current_state()
+            .unwrap()
+            .read()
+            .unwrap()
+            .get_name()
+    }
+
+    #[test]
+    pub fn context_actions_loaded_correctly() {
+        let player = load_player();
+
+        let sm = player.get_state_machine();
+        let tmp_unwrap = sm.read().unwrap();
+        let unwrapped_sm = tmp_unwrap.as_ref().unwrap();
+
+        let first_state = unwrapped_sm.states[0].read().unwrap();
+        let first_transition = first_state.get_transitions()[0].read().unwrap();
+
+        assert_eq!(
+            *first_transition.get_actions(),
+            vec![
+                ContextAction::Set {
+                    context_key: "status".to_string(),
+                    value: StringNumberBool::String("exploded".to_string()),
+                },
+                ContextAction::Toggle {
+                    context_key: "exploded".to_string(),
+                },
+                ContextAction::Decrement {
+                    context_key: "counter_0".to_string(),
+                    value: 10.0,
+                },
+                ContextAction::Clamp {
+                    context_key: "counter_0".to_string(),
+                    min: 0.0,
+                    max: 5.0,
+                },
+            ]
+        );
+
+        let listener = unwrapped_sm.get_listeners()[0].read().unwrap();
+
+        // Increment and decrement default to a step of 1
+        assert_eq!(
+            *listener.get_actions(),
+            vec![ContextAction::Increment {
+                context_key: "counter_0".to_string(),
+                value: 1.0,
+            }]
+        );
+    }
+
+    #[test]
+    pub fn listener_actions_test() {
+        let player = load_player();
+
+        player.post_pointer_down_event(10.0, 10.0);
+        player.post_pointer_down_event(10.0, 10.0);
+        player.post_pointer_up_event(10.0, 10.0);
+
+        let sm = player.get_state_machine();
+        let tmp_unwrap = sm.read().unwrap();
+        let unwrapped_sm = tmp_unwrap.as_ref().unwrap();
+
+        assert_eq!(unwrapped_sm.get_numeric_context("counter_0"), Some(2.5));
+
+        // Set creates the variable in the context of its value's type
+        assert_eq!(unwrapped_sm.get_bool_context("released"), Some(true));
+    }
+
+    #[test]
+    pub fn transition_actions_test() {
+        let player = load_player();
+
+        let explosion = Event::String {
+            value: "explosion".to_string(),
+        };
+
+        // The guard isn't met, the actions don't run
+        player.post_event(&explosion);
+        assert_eq!(get_current_state_name(&player), "pigeon");
+
+        // Listener actions run before the guards are evaluated
+        player.set_state_machine_numeric_context("counter_0", 2.0);
+        player.post_pointer_down_event(10.0, 10.0);
+        player.post_event(&explosion);
+        assert_eq!(get_current_state_name(&player), "explosion");
+
+        {
+            let sm = player.get_state_machine();
+            let tmp_unwrap = sm.read().unwrap();
+            let unwrapped_sm = tmp_unwrap.as_ref().unwrap();
+
+            // 3 - 10 is clamped to 0
+            assert_eq!(unwrapped_sm.get_numeric_context("counter_0"), Some(0.0));
+            assert_eq!(
+                unwrapped_sm.get_string_context("status"),
+                Some("exploded".to_string())
+            );
+            assert_eq!(unwrapped_sm.get_bool_context("exploded"), Some(true));
+        }
+
+        // The toggled context satisfies the next transition's guard
+        player.post_event(&Event::String {
+            value: "complete".to_string(),
+        });
+        assert_eq!(get_current_state_name(&player), "feather");
+
+        let sm = player.get_state_machine();
+        let tmp_unwrap = sm.read().unwrap();
+        let unwrapped_sm = tmp_unwrap.as_ref().unwrap();
+
+        assert_eq!(
+            unwrapped_sm.get_string_context("status"),
+            Some("exploded_twice".to_string())
+        );
+
+        // Only Set creates missing variables
+        assert_eq!(unwrapped_sm.get_numeric_context("missing"), None);
+    }
+}
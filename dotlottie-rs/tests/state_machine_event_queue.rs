@@ -0,0 +1,136 @@
+mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::current_state_path;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::{Arc, RwLock};
+
+    use dotlottie_rs::{Config, DotLottiePlayer, Event, EventResult, StateMachineObserver};
+
+    thread_local! {
+        // Observers are Send + Sync, they reach the player of the test through this
+        static PLAYER: RefCell<Option<Rc<DotLottiePlayer>>> = const { RefCell::new(None) };
+    }
+
+    #[derive(Default)]
+    struct QueueObserver {
+        entered_states: RwLock<Vec<String>>,
+        posted_results: RwLock<Vec<EventResult>>,
+    }
+
+    impl StateMachineObserver for QueueObserver {
+        fn on_transition(&self, _previous_state: String, _new_state: String) {}
+
+        fn on_state_entered(&self, entering_state: String) {
+            // Entering the explosion posts the next event while the transition is still running
+            if entering_state == "explosion" {
+                let result = PLAYER.with(|player| {
+                    player
+                        .borrow()
+                        .as_ref()
+                        .map(|player| player.post_string_event("feathers"))
+                });
+
+                self.posted_results.write().unwrap().extend(result);
+            }
+
+            self.entered_states.write().unwrap().push(entering_state);
+        }
+
+        fn on_state_exit(&self, _leaving_state: String) {}
+    }
+
+    const STATE_MACHINE: &str = r#"{
+        "descriptor": { "id": "queue", "initial": "pigeon" },
+        "states": [
+            { "name": "pigeon", "type": "PlaybackState", "marker": "bird", "autoplay": true },
+            { "name": "explosion", "type": "PlaybackState", "marker": "explosion", "autoplay": true },
+            { "name": "feathers", "type": "PlaybackState", "marker": "feather" }
+        ],
+        "transitions": [
+            { "type": "Transition", "from_state": "pigeon", "to_state": "explosion", "string_event": { "value": "explode" } },
+            { "type": "Transition", "from_state": "explosion", "to_state": "feathers", "string_event": { "value": "feathers" } },
+            { "type": "Transition", "from_state": "feathers", "to_state": "pigeon", "string_event": { "value": "restart" } }
+        ],
+        "listeners": [],
+        "context_variables": []
+    }"#;
+
+    fn load_player() -> (Rc<DotLottiePlayer>, Arc<QueueObserver>) {
+        let player = Rc::new(DotLottiePlayer::new(Config::default()));
+        let observer = Arc::new(QueueObserver::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        assert!(player.load_state_machine_data(STATE_MACHINE));
+        assert!(player.state_machine_subscribe(observer.clone()));
+        assert!(player.start_state_machine());
+
+        (player, observer)
+    }
+
+    #[test]
+    fn test_queued_events_are_processed_in_order() {
+        let (player, observer) = load_player();
+
+        player.queue_event(&Event::String {
+            value: "explode".to_string(),
+        });
+        player.queue_event(&Event::String {
+            value: "feathers".to_string(),
+        });
+        player.queue_event(&Event::String {
+            value: "restart".to_string(),
+        });
+
+        // Nothing is handled until the queue is processed
+        assert_eq!(current_state_path(&player), "pigeon");
+        assert!(observer.entered_states.read().unwrap().is_empty());
+
+        assert_eq!(player.process_events(), EventResult::Pause);
+        assert_eq!(
+            *observer.entered_states.read().unwrap(),
+            vec!["explosion", "feathers", "pigeon"]
+        );
+        assert_eq!(current_state_path(&player), "pigeon");
+
+        // The queue is empty
+        assert_eq!(player.process_events(), EventResult::Failure);
+    }
+
+    #[test]
+    fn test_events_posted_by_observers_are_not_dropped() {
+        let (player, observer) = load_player();
+
+        PLAYER.with(|cell| *cell.borrow_mut() = Some(player.clone()));
+
+        // The observer posts "feathers" while "explode" is being handled, it's handled right after
+        assert_eq!(player.post_string_event("explode"), EventResult::Pause);
+
+        assert_eq!(
+            *observer.posted_results.read().unwrap(),
+            vec![EventResult::Queued]
+        );
+        assert_eq!(
+            *observer.entered_states.read().unwrap(),
+            vec!["explosion", "feathers"]
+        );
+        assert_eq!(current_state_path(&player), "feathers");
+
+        PLAYER.with(|cell| cell.borrow_mut().take());
+    }
+
+    #[test]
+    fn test_event_results() {
+        let (player, _) = load_player();
+
+        assert_eq!(player.post_string_event("explode"), EventResult::Play);
+        assert_eq!(player.post_string_event("unknown"), EventResult::Failure);
+        assert_eq!(player.post_string_event("feathers"), EventResult::Pause);
+
+        assert!(player.stop_state_machine());
+        assert_eq!(player.post_string_event("restart"), EventResult::Failure);
+    }
+}
@@ -3,7 +3,10 @@ mod tests {
     use dotlottie_rs::states::StateTrait;
     use dotlottie_rs::{
         parser::TransitionGuardConditionType,
-        transitions::{guard::Guard, TransitionTrait},
+        transitions::{
+            guard::{Guard, GuardOperand},
+            TransitionTrait,
+        },
     };
 
     use dotlottie_rs::DotLottiePlayer;
@@ -53,20 +56,20 @@ mod tests {
         let tmp_unwrap = sm.read().unwrap();
         let unwrapped_sm = tmp_unwrap.as_ref().unwrap();
 
-        let guard_0 = Guard {
+        let guard_0 = Guard::Numeric {
             context_key: "counter_0".to_string(),
             condition_type: TransitionGuardConditionType::GreaterThan,
-            compare_to: dotlottie_rs::parser::StringNumberBool::F32(5.0),
+            compare_to: GuardOperand::Value(dotlottie_rs::parser::StringNumberBool::F32(5.0)),
         };
-        let guard_1 = Guard {
+        let guard_1 = Guard::Numeric {
             context_key: "counter_0".to_string(),
             condition_type: TransitionGuardConditionType::GreaterThanOrEqual,
-            compare_to: dotlottie_rs::parser::StringNumberBool::F32(60.0),
+            compare_to: GuardOperand::Value(dotlottie_rs::parser::StringNumberBool::F32(60.0)),
         };
-        let guard_2 = Guard {
+        let guard_2 = Guard::Numeric {
             context_key: "counter_0".to_string(),
             condition_type: TransitionGuardConditionType::GreaterThanOrEqual,
-            compare_to: dotlottie_rs::parser::StringNumberBool::F32(65.0),
+            compare_to: GuardOperand::Value(dotlottie_rs::parser::StringNumberBool::F32(65.0)),
         };
 
         let guards = [guard_0, guard_1, guard_2];
@@ -81,24 +84,13 @@ mod tests {
                 reset_context: _,
                 animation_id: _,
                 transitions,
+                entry_actions: _,
+                exit_actions: _,
             } = unwrapped_state
             {
                 let first_transition = &*transitions[0].read().unwrap();
 
-                assert_eq!(
-                    first_transition.get_guards()[0].compare_to,
-                    guards[i].compare_to
-                );
-
-                assert_eq!(
-                    first_transition.get_guards()[0].condition_type,
-                    guards[i].condition_type
-                );
-
-                assert_eq!(
-                    first_transition.get_guards()[0].context_key,
-                    guards[i].context_key
-                );
+                assert_eq!(first_transition.get_guards()[0], guards[i]);
             }
         }
     }
@@ -544,4 +536,170 @@ mod tests {
         // Should go back to first stage
         assert_eq!(get_current_transition_event(&player), "explosion");
     }
+
+    #[test]
+    pub fn compound_guards_test() {
+        use dotlottie_rs::{events::Event, Config, DotLottiePlayer};
+
+        let player = DotLottiePlayer::new(Config::default());
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        let state_machine = r#"{
+            "descriptor": { "id": "compound_guards", "initial": 0 },
+            "states": [
+                { "name": "pigeon", "type": "PlaybackState", "marker": "bird" },
+                { "name": "explosion", "type": "PlaybackState", "marker": "explosion" },
+                { "name": "feather", "type": "PlaybackState", "marker": "feather" }
+            ],
+            "transitions": [
+                {
+                    "type": "Transition",
+                    "from_state": 0,
+                    "to_state": 1,
+                    "string_event": { "value": "explosion" },
+                    "guards": [
+                        {
+                            "type": "Or",
+                            "guards": [
+                                { "type": "Numeric", "context_key": "counter_0", "condition_type": "InRange", "min": 3, "max": 5 },
+                                { "type": "Boolean", "context_key": "forced", "condition_type": "Equal", "compare_to": true }
+                            ]
+                        },
+                        {
+                            "type": "Not",
+                            "guards": [
+                                { "type": "String", "context_key": "name", "condition_type": "Contains", "compare_to": "egg" }
+                            ]
+                        }
+                    ]
+                },
+                {
+                    "type": "Transition",
+                    "from_state": 1,
+                    "to_state": 2,
+                    "string_event": { "value": "complete" },
+                    "guards": [
+                        { "type": "Numeric", "context_key": "counter_0", "condition_type": "GreaterThan", "compare_to_key": "counter_1" }
+                    ]
+                },
+                {
+                    "type": "Transition",
+                    "from_state": 2,
+                    "to_state": 0,
+                    "string_event": { "value": "done" },
+                    "guards": [
+                        {
+                            "type": "And",
+                            "guards": [
+                                { "type": "String", "context_key": "name", "condition_type": "StartsWith", "compare_to": "pi" },
+                                { "type": "String", "context_key": "name", "condition_type": "Matches", "compare_to": "*_?" }
+                            ]
+                        }
+                    ]
+                }
+            ],
+            "listeners": [],
+            "context_variables": [
+                { "type": "Numeric", "key": "counter_0", "value": 0 },
+                { "type": "Numeric", "key": "counter_1", "value": 10 },
+                { "type": "String", "key": "name", "value": "egg" },
+                { "type": "Boolean", "key": "forced", "value": false }
+            ]
+        }"#;
+
+        assert!(player.load_state_machine_data(state_machine));
+        player.start_state_machine();
+
+        let explosion = Event::String {
+            value: "explosion".to_string(),
+        };
+
+        // Within the range, but the name contains "egg"
+        player.set_state_machine_numeric_context("counter_0", 4.0);
+        player.post_event(&explosion);
+        assert_eq!(get_current_transition_event(&player), "explosion");
+
+        // Out of the range and not forced
+        player.set_state_machine_string_context("name", "pigeon");
+        player.set_state_machine_numeric_context("counter_0", 6.0);
+        player.post_event(&explosion);
+        assert_eq!(get_current_transition_event(&player), "explosion");
+
+        // Out of the range but forced
+        player.set_state_machine_boolean_context("forced", true);
+        player.post_event(&explosion);
+        assert_eq!(get_current_transition_event(&player), "complete");
+
+        let complete = Event::String {
+            value: "complete".to_string(),
+        };
+
+        // counter_0 is compared to counter_1
+        player.post_event(&complete);
+        assert_eq!(get_current_transition_event(&player), "complete");
+
+        player.set_state_machine_numeric_context("counter_1", 5.0);
+        player.post_event(&complete);
+        assert_eq!(get_current_transition_event(&player), "done");
+
+        let done = Event::String {
+            value: "done".to_string(),
+        };
+
+        // Starts with "pi" but doesn't match "*_?"
+        player.post_event(&done);
+        assert_eq!(get_current_transition_event(&player), "done");
+
+        player.set_state_machine_string_context("name", "pigeon_2");
+        player.post_event(&done);
+        assert_eq!(get_current_transition_event(&player), "explosion");
+    }
+
+    #[test]
+    pub fn invalid_guards_test() {
+        use dotlottie_rs::{Config, DotLottiePlayer};
+
+        let player = DotLottiePlayer::new(Config::default());
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        let state_machine = r#"{
+            "descriptor": { "id": "invalid_guards", "initial": 0 },
+            "states": [
+                { "name": "pigeon", "type": "PlaybackState", "marker": "bird" },
+                { "name": "explosion", "type": "PlaybackState", "marker": "explosion" }
+            ],
+            "transitions": [
+                {
+                    "type": "Transition",
+                    "from_state": 0,
+                    "to_state": 1,
+                    "string_event": { "value": "explosion" },
+                    "guards": [GUARD]
+                }
+            ],
+            "listeners": [],
+            "context_variables": []
+        }"#;
+
+        // A range without a max
+        assert!(!player.load_state_machine_data(&state_machine.replace(
+            "GUARD",
+            r#"{ "type": "Numeric", "context_key": "counter_0", "condition_type": "InRange", "min": 1 }"#
+        )));
+
+        // Not with two guards
+        assert!(!player.load_state_machine_data(&state_machine.replace(
+            "GUARD",
+            r#"{ "type": "Not", "guards": [
+                { "type": "Boolean", "context_key": "a", "condition_type": "Equal", "compare_to": true },
+                { "type": "Boolean", "context_key": "b", "condition_type": "Equal", "compare_to": true }
+            ] }"#
+        )));
+
+        // Nothing to compare to
+        assert!(!player.load_state_machine_data(&state_machine.replace(
+            "GUARD",
+            r#"{ "type": "Numeric", "context_key": "counter_0", "condition_type": "Equal" }"#
+        )));
+    }
 }
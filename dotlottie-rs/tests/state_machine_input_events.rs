@@ -0,0 +1,168 @@
+mod test_utils;
+
+use std::{sync::Arc, time::Duration};
+
+use crate::test_utils::{advance, current_state_name, HEIGHT, WIDTH};
+use dotlottie_rs::{Config, DotLottiePlayer, ManualClock};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_player(clock: &ManualClock, transitions: &str, listeners: &str) -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+        player.set_clock(Arc::new(clock.clone()));
+
+        assert!(player.load_dotlottie_data(
+            include_bytes!("fixtures/exploding_pigeon.lottie"),
+            WIDTH,
+            HEIGHT
+        ));
+
+        let state_machine = format!(
+            r#"{{
+                "descriptor": {{ "id": "input", "initial": 0 }},
+                "states": [
+                    {{ "name": "pigeon", "type": "PlaybackState", "marker": "bird" }},
+                    {{ "name": "explosion", "type": "PlaybackState", "marker": "explosion" }},
+                    {{ "name": "feather", "type": "PlaybackState", "marker": "feather" }}
+                ],
+                "transitions": {},
+                "listeners": {},
+                "context_variables": [
+                    {{ "type": "Numeric", "key": "presses", "value": 0 }},
+                    {{ "type": "Boolean", "key": "focused", "value": false }}
+                ]
+            }}"#,
+            transitions, listeners
+        );
+
+        assert!(player.load_state_machine_data(&state_machine));
+        assert!(player.start_state_machine());
+
+        player
+    }
+
+    #[test]
+    fn test_keyboard_and_focus() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"[
+                { "type": "Transition", "from_state": 0, "to_state": 1, "on_key_down_event": { "key": "Enter" } },
+                { "type": "Transition", "from_state": 1, "to_state": 2, "on_key_up_event": {} }
+            ]"#,
+            r#"[
+                { "type": "KeyDown", "target": "Enter", "actions": [{ "type": "Increment", "context_key": "presses" }] },
+                { "type": "Focus", "actions": [{ "type": "Set", "context_key": "focused", "value": true }] },
+                { "type": "Blur", "actions": [{ "type": "Set", "context_key": "focused", "value": false }] }
+            ]"#,
+        );
+
+        assert_eq!(
+            player.state_machine_framework_setup(),
+            vec!["KeyDown", "Focus", "Blur"]
+        );
+
+        player.post_focus_event();
+        assert_eq!(
+            player
+                .get_state_machine()
+                .read()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .get_bool_context("focused"),
+            Some(true)
+        );
+
+        player.post_key_down_event("Space");
+        assert_eq!(current_state_name(&player), "pigeon");
+
+        player.post_key_down_event("Enter");
+        assert_eq!(current_state_name(&player), "explosion");
+
+        // Without a key, any key matches
+        player.post_key_up_event("Enter");
+        assert_eq!(current_state_name(&player), "feather");
+
+        player.post_blur_event();
+
+        let state_machine = player.get_state_machine();
+        let state_machine = state_machine.read().unwrap();
+        let state_machine = state_machine.as_ref().unwrap();
+        assert_eq!(state_machine.get_numeric_context("presses"), Some(1.0));
+        assert_eq!(state_machine.get_bool_context("focused"), Some(false));
+    }
+
+    #[test]
+    fn test_long_press() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"[{ "type": "Transition", "from_state": 0, "to_state": 1, "on_long_press_event": {} }]"#,
+            "[]",
+        );
+
+        player.post_pointer_down_event(10.0, 10.0);
+
+        advance(&player, &clock, 0.25);
+        assert_eq!(current_state_name(&player), "pigeon");
+
+        advance(&player, &clock, 0.5);
+        assert_eq!(current_state_name(&player), "explosion");
+    }
+
+    #[test]
+    fn test_long_press_without_rendering() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"[{ "type": "Transition", "from_state": 0, "to_state": 1, "on_long_press_event": {} }]"#,
+            "[]",
+        );
+
+        assert!(!player.is_playing());
+
+        // The host doesn't render, the long press is recognized with the release
+        player.post_pointer_down_event(10.0, 10.0);
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(current_state_name(&player), "pigeon");
+
+        player.post_pointer_up_event(10.0, 10.0);
+        assert_eq!(current_state_name(&player), "explosion");
+    }
+
+    #[test]
+    fn test_double_click_and_swipe() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"[
+                { "type": "Transition", "from_state": 0, "to_state": 1, "on_double_click_event": {} },
+                { "type": "Transition", "from_state": 1, "to_state": 2, "on_swipe_event": { "direction": "Left" } }
+            ]"#,
+            "[]",
+        );
+
+        player.post_pointer_down_event(10.0, 10.0);
+        player.post_pointer_up_event(10.0, 10.0);
+        assert_eq!(current_state_name(&player), "pigeon");
+
+        advance(&player, &clock, 0.125);
+        player.post_pointer_down_event(10.0, 10.0);
+        player.post_pointer_up_event(10.0, 10.0);
+        assert_eq!(current_state_name(&player), "explosion");
+
+        // Swiping in another direction doesn't match
+        player.post_pointer_down_event(50.0, 50.0);
+        advance(&player, &clock, 0.125);
+        player.post_pointer_up_event(50.0, 100.0);
+        assert_eq!(current_state_name(&player), "explosion");
+
+        player.post_pointer_down_event(100.0, 50.0);
+        advance(&player, &clock, 0.125);
+        player.post_pointer_up_event(20.0, 55.0);
+        assert_eq!(current_state_name(&player), "feather");
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use dotlottie_rs::{Config, DotLottiePlayer, EventInfo};
+
+    const STATE_MACHINE: &str = r#"{
+        "descriptor": { "id": "introspection", "initial": "pigeon" },
+        "states": [
+            { "name": "pigeon", "type": "PlaybackState", "marker": "bird", "autoplay": true },
+            { "name": "explosion", "type": "PlaybackState", "marker": "explosion" },
+            { "name": "feather", "type": "PlaybackState", "marker": "feather", "loop": true }
+        ],
+        "transitions": [
+            {
+                "type": "Transition",
+                "from_state": "pigeon",
+                "to_state": "explosion",
+                "string_event": { "value": "explode" },
+                "guards": [{ "type": "Boolean", "context_key": "armed", "condition_type": "Equal", "compare_to": true }]
+            },
+            { "type": "Transition", "from_state": "pigeon", "to_state": "feather", "on_pointer_down_event": {} },
+            { "type": "Transition", "from_state": "explosion", "to_state": "feather", "on_complete_event": {} }
+        ],
+        "listeners": [
+            {
+                "type": "PointerDown",
+                "target": "pigeon",
+                "actions": [{ "type": "Toggle", "context_key": "armed" }]
+            }
+        ],
+        "context_variables": [
+            { "type": "Boolean", "key": "armed", "value": false },
+            { "type": "Numeric", "key": "rating", "value": 3 },
+            { "type": "String", "key": "name", "value": "pigeon" }
+        ]
+    }"#;
+
+    fn load_player() -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+        assert!(player.load_state_machine_data(STATE_MACHINE));
+
+        player
+    }
+
+    fn event(event_type: &str, value: &str) -> EventInfo {
+        EventInfo {
+            event_type: event_type.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_describe_definition() {
+        let player = load_player();
+
+        let states = player.state_machine_states();
+        let names: Vec<_> = states.iter().map(|state| state.path.as_str()).collect();
+
+        assert_eq!(names, vec!["pigeon", "explosion", "feather"]);
+        assert_eq!(states[2].state_type, "PlaybackState");
+        assert!(states[2].config.contains(r#""loop":true"#));
+        assert!(!states.iter().any(|state| state.active));
+
+        let transitions = player.state_machine_transitions();
+
+        assert_eq!(transitions.len(), 3);
+        assert_eq!(transitions[0].from_state, "pigeon");
+        assert_eq!(transitions[0].to_state, "explosion");
+        assert_eq!(transitions[0].event, event("String", "explode"));
+        assert!(transitions[0].guards.contains(r#""context_key":"armed""#));
+        assert_eq!(transitions[1].event, event("OnPointerDown", ""));
+        assert_eq!(transitions[2].actions, "[]");
+
+        let listeners = player.state_machine_listeners();
+
+        assert_eq!(listeners.len(), 1);
+        assert_eq!(listeners[0].listener_type, "PointerDown");
+        assert_eq!(listeners[0].target, "pigeon");
+        assert!(listeners[0].actions.contains("Toggle"));
+
+        let context: Vec<_> = player
+            .state_machine_context()
+            .into_iter()
+            .map(|context| (context.key, context.context_type, context.value))
+            .collect();
+
+        assert_eq!(
+            context,
+            vec![
+                (
+                    "armed".to_string(),
+                    "Boolean".to_string(),
+                    "false".to_string()
+                ),
+                (
+                    "name".to_string(),
+                    "String".to_string(),
+                    "pigeon".to_string()
+                ),
+                ("rating".to_string(), "Numeric".to_string(), "3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_available_events() {
+        let player = load_player();
+
+        // Nothing can happen before the state machine is started
+        assert!(player.state_machine_available_events().is_empty());
+
+        assert!(player.start_state_machine());
+        assert!(player.state_machine_states()[0].active);
+
+        // The guard of the string event isn't met
+        assert_eq!(
+            player.state_machine_available_events(),
+            vec![event("OnPointerDown", "")]
+        );
+
+        assert!(player.set_state_machine_boolean_context("armed", true));
+        assert_eq!(
+            player.state_machine_available_events(),
+            vec![event("String", "explode"), event("OnPointerDown", "")]
+        );
+
+        player.post_string_event("explode");
+        assert_eq!(
+            player.state_machine_available_events(),
+            vec![event("OnComplete", "")]
+        );
+    }
+
+    #[test]
+    fn test_without_state_machine() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.state_machine_states().is_empty());
+        assert!(player.state_machine_transitions().is_empty());
+        assert!(player.state_machine_listeners().is_empty());
+        assert!(player.state_machine_context().is_empty());
+        assert!(player.state_machine_available_events().is_empty());
+    }
+}
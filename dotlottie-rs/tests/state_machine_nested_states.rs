@@ -0,0 +1,237 @@
+mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::current_state_path;
+    use std::sync::{Arc, RwLock};
+
+    use dotlottie_rs::{Config, DotLottiePlayer, StateMachineObserver};
+
+    #[derive(Default)]
+    struct PathObserver {
+        events: RwLock<Vec<String>>,
+    }
+
+    impl StateMachineObserver for PathObserver {
+        fn on_transition(&self, previous_state: String, new_state: String) {
+            self.events
+                .write()
+                .unwrap()
+                .push(format!("transition {} -> {}", previous_state, new_state));
+        }
+
+        fn on_state_entered(&self, entering_state: String) {
+            self.events
+                .write()
+                .unwrap()
+                .push(format!("enter {}", entering_state));
+        }
+
+        fn on_state_exit(&self, leaving_state: String) {
+            self.events
+                .write()
+                .unwrap()
+                .push(format!("exit {}", leaving_state));
+        }
+    }
+
+    fn load_player(states: &str, transitions: &str) -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        let state_machine = format!(
+            r#"{{
+                "descriptor": {{ "id": "nested", "initial": 0 }},
+                "states": {},
+                "transitions": {},
+                "listeners": [],
+                "context_variables": []
+            }}"#,
+            states, transitions
+        );
+
+        assert!(player.load_state_machine_data(&state_machine));
+
+        player
+    }
+
+    fn active_state_paths(player: &DotLottiePlayer) -> Vec<String> {
+        player
+            .get_state_machine()
+            .read()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .get_active_state_paths()
+    }
+
+    #[test]
+    fn test_compound_states() {
+        let player = load_player(
+            r#"[
+                { "name": "toggle", "type": "PlaybackState", "initial": 1 },
+                { "name": "off", "type": "PlaybackState", "marker": "bird", "parent": 0 },
+                { "name": "on", "type": "PlaybackState", "parent": 0 },
+                { "name": "idle", "type": "PlaybackState", "marker": "explosion", "parent": 2 },
+                { "name": "hover", "type": "PlaybackState", "marker": "feather", "parent": 2 },
+                { "name": "done", "type": "PlaybackState", "marker": "feather" }
+            ]"#,
+            r#"[
+                { "type": "Transition", "from_state": 1, "to_state": 2, "string_event": { "value": "toggle" } },
+                { "type": "Transition", "from_state": 2, "to_state": 1, "string_event": { "value": "toggle" } },
+                { "type": "Transition", "from_state": 3, "to_state": 4, "string_event": { "value": "hover" } },
+                { "type": "Transition", "from_state": 4, "to_state": 3, "string_event": { "value": "leave" } },
+                { "type": "Transition", "from_state": 0, "to_state": 5, "string_event": { "value": "finish" } }
+            ]"#,
+        );
+        let observer = Arc::new(PathObserver::default());
+
+        assert!(player.state_machine_subscribe(observer.clone()));
+        assert!(player.start_state_machine());
+
+        // Entering a compound state enters its initial child
+        assert_eq!(current_state_path(&player), "toggle/off");
+
+        player.post_string_event("toggle");
+        assert_eq!(current_state_path(&player), "toggle/on/idle");
+
+        player.post_string_event("hover");
+        assert_eq!(current_state_path(&player), "toggle/on/hover");
+
+        observer.events.write().unwrap().clear();
+
+        // The transitions of "on" are inherited by its children
+        player.post_string_event("toggle");
+        assert_eq!(current_state_path(&player), "toggle/off");
+        assert_eq!(
+            *observer.events.read().unwrap(),
+            vec![
+                "transition toggle/on/hover -> toggle/off",
+                "exit toggle/on/hover",
+                "exit toggle/on",
+                "enter toggle/off",
+            ]
+        );
+
+        // The first child is the initial child by default
+        player.post_string_event("toggle");
+        assert_eq!(current_state_path(&player), "toggle/on/idle");
+
+        player.post_string_event("finish");
+        assert_eq!(current_state_path(&player), "done");
+        assert_eq!(active_state_paths(&player), vec!["done"]);
+    }
+
+    #[test]
+    fn test_parallel_regions() {
+        let player = load_player(
+            r#"[
+                { "name": "widget", "type": "PlaybackState", "parallel": true },
+                { "name": "icon", "type": "PlaybackState", "parent": 0 },
+                { "name": "idle", "type": "PlaybackState", "marker": "bird", "parent": 1 },
+                { "name": "active", "type": "PlaybackState", "marker": "explosion", "parent": 1 },
+                { "name": "badge", "type": "PlaybackState", "parent": 0 },
+                { "name": "hidden", "type": "PlaybackState", "parent": 4 },
+                { "name": "shown", "type": "PlaybackState", "parent": 4 }
+            ]"#,
+            r#"[
+                { "type": "Transition", "from_state": 2, "to_state": 3, "string_event": { "value": "press" } },
+                { "type": "Transition", "from_state": 5, "to_state": 6, "string_event": { "value": "notify" } },
+                { "type": "Transition", "from_state": 6, "to_state": 5, "string_event": { "value": "press" } }
+            ]"#,
+        );
+
+        assert!(player.start_state_machine());
+        assert_eq!(
+            active_state_paths(&player),
+            vec!["widget/icon/idle", "widget/badge/hidden"]
+        );
+
+        player.post_string_event("notify");
+        assert_eq!(
+            active_state_paths(&player),
+            vec!["widget/icon/idle", "widget/badge/shown"]
+        );
+
+        // Both regions take a transition, the last one sets the current state
+        player.post_string_event("press");
+        assert_eq!(
+            active_state_paths(&player),
+            vec!["widget/icon/active", "widget/badge/hidden"]
+        );
+        assert_eq!(current_state_path(&player), "widget/badge/hidden");
+    }
+
+    #[test]
+    fn test_parallel_regions_share_the_player() {
+        let player = load_player(
+            r#"[
+                { "name": "widget", "type": "PlaybackState", "parallel": true },
+                { "name": "icon", "type": "PlaybackState", "parent": 0 },
+                { "name": "idle", "type": "PlaybackState", "marker": "bird", "parent": 1 },
+                { "name": "active", "type": "PlaybackState", "marker": "explosion", "parent": 1 },
+                { "name": "badge", "type": "PlaybackState", "parent": 0 },
+                { "name": "hidden", "type": "PlaybackState", "marker": "feather", "parent": 4 }
+            ]"#,
+            r#"[
+                { "type": "Transition", "from_state": 2, "to_state": 3, "string_event": { "value": "press" } }
+            ]"#,
+        );
+
+        // The last region is entered last
+        assert!(player.start_state_machine());
+        assert_eq!(player.config().marker, "feather");
+        assert_eq!(current_state_path(&player), "widget/badge/hidden");
+
+        // The region taking a transition enters its new state last
+        player.post_string_event("press");
+        assert_eq!(
+            active_state_paths(&player),
+            vec!["widget/icon/active", "widget/badge/hidden"]
+        );
+        assert_eq!(player.config().marker, "explosion");
+        assert_eq!(current_state_path(&player), "widget/icon/active");
+    }
+
+    #[test]
+    fn test_invalid_nesting() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        let state_machine = |states: &str| {
+            format!(
+                r#"{{
+                    "descriptor": {{ "id": "nested", "initial": 0 }},
+                    "states": {},
+                    "transitions": [],
+                    "listeners": [],
+                    "context_variables": []
+                }}"#,
+                states
+            )
+        };
+
+        // Unknown parent
+        assert!(!player.load_state_machine_data(&state_machine(
+            r#"[{ "name": "a", "type": "PlaybackState", "parent": 3 }]"#
+        )));
+
+        // Nested within itself
+        assert!(!player.load_state_machine_data(&state_machine(
+            r#"[
+                { "name": "a", "type": "PlaybackState", "parent": 1 },
+                { "name": "b", "type": "PlaybackState", "parent": 0 }
+            ]"#
+        )));
+
+        // The initial state isn't a child
+        assert!(!player.load_state_machine_data(&state_machine(
+            r#"[
+                { "name": "a", "type": "PlaybackState", "initial": 0 },
+                { "name": "b", "type": "PlaybackState", "parent": 0 }
+            ]"#
+        )));
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use dotlottie_rs::{Config, DotLottiePlayer, EventInfo, StateMachineObserver};
+
+    #[derive(Default)]
+    struct EventObserver {
+        events: RwLock<Vec<String>>,
+    }
+
+    impl EventObserver {
+        fn push(&self, event: String) {
+            self.events.write().unwrap().push(event);
+        }
+
+        fn take(&self) -> Vec<String> {
+            std::mem::take(&mut *self.events.write().unwrap())
+        }
+    }
+
+    // Only implements the callbacks that have no default
+    impl StateMachineObserver for EventObserver {
+        fn on_transition(&self, _previous_state: String, _new_state: String) {}
+
+        fn on_state_entered(&self, _entering_state: String) {}
+
+        fn on_state_exit(&self, _leaving_state: String) {}
+
+        fn on_context_changed(&self, key: String, old_value: Option<String>, new_value: String) {
+            self.push(format!("context {} {:?} {}", key, old_value, new_value));
+        }
+
+        fn on_event_rejected(&self, event: EventInfo) {
+            self.push(format!("rejected {} {}", event.event_type, event.value));
+        }
+
+        fn on_listener_triggered(&self, listener_type: String, target: String) {
+            self.push(format!("listener {} {}", listener_type, target));
+        }
+
+        fn on_custom_event(&self, event: String) {
+            self.push(format!("custom {}", event));
+        }
+
+        fn on_error(&self, message: String) {
+            self.push(format!("error {}", message));
+        }
+    }
+
+    const STATE_MACHINE: &str = r#"{
+        "descriptor": { "id": "observer", "initial": "pigeon" },
+        "states": [
+            { "name": "pigeon", "type": "PlaybackState", "marker": "bird" },
+            {
+                "name": "explosion",
+                "type": "PlaybackState",
+                "marker": "explosion",
+                "entry_actions": [{ "type": "FireCustomEvent", "event": "boom" }]
+            },
+            { "name": "nest", "type": "PlaybackState", "animation_id": "nest" }
+        ],
+        "transitions": [
+            {
+                "type": "Transition",
+                "from_state": "pigeon",
+                "to_state": "explosion",
+                "string_event": { "value": "explode" },
+                "guards": [{ "type": "Boolean", "context_key": "armed", "condition_type": "Equal", "compare_to": true }]
+            },
+            { "type": "Transition", "from_state": "explosion", "to_state": "nest", "string_event": { "value": "nest" } }
+        ],
+        "listeners": [
+            { "type": "PointerDown", "actions": [{ "type": "Toggle", "context_key": "armed" }] }
+        ],
+        "context_variables": [{ "type": "Boolean", "key": "armed", "value": false }]
+    }"#;
+
+    fn load_player() -> (DotLottiePlayer, Arc<EventObserver>) {
+        let player = DotLottiePlayer::new(Config::default());
+        let observer = Arc::new(EventObserver::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        assert!(player.load_state_machine_data(STATE_MACHINE));
+        assert!(player.state_machine_subscribe(observer.clone()));
+        assert!(player.start_state_machine());
+
+        (player, observer)
+    }
+
+    #[test]
+    fn test_rejected_events_and_custom_events() {
+        let (player, observer) = load_player();
+
+        player.post_string_event("explode");
+        assert_eq!(observer.take(), vec!["rejected String explode"]);
+
+        // Events no transition waits for aren't rejected
+        player.post_string_event("unknown");
+        assert!(observer.take().is_empty());
+
+        player.post_pointer_down_event(0.0, 0.0);
+        assert_eq!(
+            observer.take(),
+            vec![
+                "listener PointerDown ",
+                "context armed Some(\"false\") true"
+            ]
+        );
+
+        player.post_string_event("explode");
+        assert_eq!(observer.take(), vec!["custom boom"]);
+    }
+
+    #[test]
+    fn test_context_changes() {
+        let (player, observer) = load_player();
+
+        assert!(player.set_state_machine_boolean_context("armed", true));
+        assert!(player.set_state_machine_boolean_context("armed", true));
+        assert!(player.set_state_machine_string_context("name", "pigeon"));
+
+        // Setting the same value isn't a change
+        assert_eq!(
+            observer.take(),
+            vec![
+                "context armed Some(\"false\") true",
+                "context name None pigeon"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let (player, observer) = load_player();
+
+        assert!(player.set_state_machine_boolean_context("armed", true));
+        player.post_string_event("explode");
+        observer.take();
+
+        player.post_string_event("nest");
+        assert_eq!(
+            observer.take(),
+            vec!["error Failed to load animation 'nest' of state 'nest'"]
+        );
+    }
+}
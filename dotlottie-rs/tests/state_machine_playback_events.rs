@@ -0,0 +1,173 @@
+mod test_utils;
+
+use std::sync::Arc;
+
+use crate::test_utils::{advance, current_state_name, HEIGHT, WIDTH};
+use dotlottie_rs::{Config, DotLottiePlayer, ManualClock};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // test.json runs at 30 fps and has the markers Marker_1 to Marker_4 at frames 0, 10, 20 and 30
+    fn load_player(clock: &ManualClock, playing_state: &str, transition: &str) -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+        player.set_clock(Arc::new(clock.clone()));
+
+        assert!(player.load_animation_path("tests/fixtures/test.json", WIDTH, HEIGHT));
+
+        let state_machine = format!(
+            r#"{{
+                "descriptor": {{ "id": "playback_events", "initial": 0 }},
+                "states": [
+                    {},
+                    {{ "name": "reached", "type": "PlaybackState", "autoplay": false }}
+                ],
+                "transitions": [{}],
+                "listeners": [],
+                "context_variables": [{{ "type": "Numeric", "key": "reached", "value": 0 }}]
+            }}"#,
+            playing_state, transition
+        );
+
+        assert!(player.load_state_machine_data(&state_machine));
+        assert!(player.start_state_machine());
+
+        player
+    }
+
+    #[test]
+    fn test_marker_reached_transition() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"{ "name": "playing", "type": "PlaybackState", "autoplay": true }"#,
+            r#"{
+                "type": "Transition",
+                "from_state": 0,
+                "to_state": 1,
+                "on_marker_reached_event": { "name": "Marker_3" },
+                "actions": [{ "type": "Increment", "context_key": "reached" }]
+            }"#,
+        );
+
+        player.render();
+
+        // Frame 15, past Marker_2 only
+        advance(&player, &clock, 0.5);
+        assert_eq!(current_state_name(&player), "playing");
+
+        // Frame 22.5, Marker_3 was crossed between the two renders
+        advance(&player, &clock, 0.25);
+        assert_eq!(current_state_name(&player), "reached");
+        assert_eq!(
+            player
+                .get_state_machine()
+                .read()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .get_numeric_context("reached"),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn test_frame_reached_transition() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"{ "name": "playing", "type": "PlaybackState", "autoplay": true }"#,
+            r#"{ "type": "Transition", "from_state": 0, "to_state": 1, "on_frame_reached_event": { "frame": 12 } }"#,
+        );
+
+        player.render();
+
+        // Frame 11.25
+        advance(&player, &clock, 0.375);
+        assert_eq!(current_state_name(&player), "playing");
+
+        // Frame 15
+        advance(&player, &clock, 0.125);
+        assert_eq!(current_state_name(&player), "reached");
+    }
+
+    #[test]
+    fn test_frame_reached_in_reverse() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"{ "name": "playing", "type": "PlaybackState", "autoplay": true, "mode": "Reverse" }"#,
+            r#"{ "type": "Transition", "from_state": 0, "to_state": 1, "on_frame_reached_event": { "frame": 30 } }"#,
+        );
+
+        player.render();
+
+        advance(&player, &clock, 0.25);
+        assert_eq!(current_state_name(&player), "playing");
+
+        advance(&player, &clock, 0.25);
+        assert_eq!(current_state_name(&player), "reached");
+    }
+
+    #[test]
+    fn test_loop_complete_transition() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"{ "name": "playing", "type": "PlaybackState", "autoplay": true, "loop": true }"#,
+            r#"{ "type": "Transition", "from_state": 0, "to_state": 1, "on_loop_complete_event": {} }"#,
+        );
+
+        player.render();
+
+        // Frame 30 of 42
+        advance(&player, &clock, 1.0);
+        assert_eq!(current_state_name(&player), "playing");
+
+        advance(&player, &clock, 0.5);
+        assert_eq!(current_state_name(&player), "reached");
+    }
+
+    #[test]
+    fn test_marker_reached_across_the_loop_point() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"{ "name": "playing", "type": "PlaybackState", "autoplay": true, "loop": true }"#,
+            r#"{ "type": "Transition", "from_state": 0, "to_state": 1, "on_marker_reached_event": { "name": "Marker_4" } }"#,
+        );
+
+        player.render();
+
+        assert!(player.set_frame(25.0));
+        player.render();
+        assert_eq!(current_state_name(&player), "playing");
+
+        // Marker_4 at frame 30 is at the end of the pass left by looping to frame 5
+        assert!(player.set_frame(5.0));
+        player.render();
+        assert_eq!(current_state_name(&player), "reached");
+    }
+
+    #[test]
+    fn test_frame_reached_across_the_loop_point_in_reverse() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"{ "name": "playing", "type": "PlaybackState", "autoplay": true, "loop": true, "mode": "Reverse" }"#,
+            r#"{ "type": "Transition", "from_state": 0, "to_state": 1, "on_frame_reached_event": { "frame": 2 } }"#,
+        );
+
+        player.render();
+
+        assert!(player.set_frame(10.0));
+        player.render();
+        assert_eq!(current_state_name(&player), "playing");
+
+        // Frame 2 is at the end of the reverse pass left by looping to frame 38
+        assert!(player.set_frame(38.0));
+        player.render();
+        assert_eq!(current_state_name(&player), "reached");
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use dotlottie_rs::{states::StateTrait, Config, DotLottiePlayer, Event};
+    use dotlottie_rs::{states::StateTrait, Config, DotLottiePlayer, Event, EventResult};
 
     #[test]
     pub fn pointer_down_up_test() {
@@ -27,7 +27,7 @@ mod tests {
         // Send pointer coordinates to activate first state (first star)
         let event = Event::OnPointerDown { x: 15.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -45,7 +45,7 @@ mod tests {
         // Send pointer coordinates to de-activate first state (first star)
         let event = Event::OnPointerUp { x: 15.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -63,7 +63,7 @@ mod tests {
         // Send pointer coordinates to activate second state (second star)
         let event = Event::OnPointerDown { x: 30.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -81,7 +81,7 @@ mod tests {
         // Send pointer coordinates to de-activate second state (second star)
         let event = Event::OnPointerUp { x: 30.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -99,7 +99,7 @@ mod tests {
         // Send pointer coordinates to activate third state (third star)
         let event = Event::OnPointerDown { x: 45.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -117,7 +117,7 @@ mod tests {
         // Send pointer coordinates to de-activate third state (third star)
         let event = Event::OnPointerUp { x: 45.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -135,7 +135,7 @@ mod tests {
         // Send pointer coordinates to activate fourth state (fourth star)
         let event = Event::OnPointerDown { x: 60.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -153,7 +153,7 @@ mod tests {
         // Send pointer coordinates to de-activate fourth state (fourth star)
         let event = Event::OnPointerUp { x: 60.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -171,7 +171,7 @@ mod tests {
         // Send pointer coordinates to activate fifth state (fifth star)
         let event = Event::OnPointerDown { x: 74.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -189,7 +189,7 @@ mod tests {
         // Send pointer coordinates to de-activate fifth state (fifth star)
         let event = Event::OnPointerUp { x: 74.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -231,7 +231,7 @@ mod tests {
         // Send pointer coordinates to activate first state (first star)
         let event = Event::OnPointerMove { x: 15.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -249,7 +249,7 @@ mod tests {
         // Send pointer coordinates to de-activate first state (first star)
         let event = Event::OnPointerMove { x: 0.0, y: 0.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -267,7 +267,7 @@ mod tests {
         // Send pointer coordinates to activate second state (second star)
         let event = Event::OnPointerMove { x: 30.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -285,7 +285,7 @@ mod tests {
         // Send pointer coordinates to de-activate second state (second star)
         let event = Event::OnPointerMove { x: 0.0, y: 0.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -303,7 +303,7 @@ mod tests {
         // Send pointer coordinates to activate third state (third star)
         let event = Event::OnPointerMove { x: 45.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -321,7 +321,7 @@ mod tests {
         // Send pointer coordinates to de-activate third state (third star)
         let event = Event::OnPointerMove { x: 0.0, y: 0.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -339,7 +339,7 @@ mod tests {
         // Send pointer coordinates to activate fourth state (fourth star)
         let event = Event::OnPointerMove { x: 60.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -357,7 +357,7 @@ mod tests {
         // Send pointer coordinates to de-activate fourth state (fourth star)
         let event = Event::OnPointerMove { x: 0.0, y: 0.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -375,7 +375,7 @@ mod tests {
         // Send pointer coordinates to activate fifth state (fifth star)
         let event = Event::OnPointerMove { x: 74.0, y: 45.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 2);
+        assert_eq!(pe, EventResult::Play);
         assert_eq!(
             sm.read()
                 .unwrap()
@@ -393,7 +393,7 @@ mod tests {
         // Send pointer coordinates to de-activate fifth state (fifth star)
         let event = Event::OnPointerMove { x: 0.0, y: 0.0 };
         let pe = player.post_event(&event);
-        assert_eq!(pe, 0);
+        assert_eq!(pe, EventResult::Success);
         assert_eq!(
             sm.read()
                 .unwrap()
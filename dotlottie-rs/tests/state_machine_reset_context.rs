@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use dotlottie_rs::{Config, DotLottiePlayer, StateMachineObserver};
+
+    #[derive(Default)]
+    struct ResetObserver {
+        reset_keys: RwLock<Vec<String>>,
+    }
+
+    impl StateMachineObserver for ResetObserver {
+        fn on_transition(&self, _previous_state: String, _new_state: String) {}
+
+        fn on_state_entered(&self, _entering_state: String) {}
+
+        fn on_state_exit(&self, _leaving_state: String) {}
+
+        fn on_context_reset(&self, key: String) {
+            self.reset_keys.write().unwrap().push(key);
+        }
+    }
+
+    const STATE_MACHINE: &str = r#"{
+        "descriptor": { "id": "rating", "initial": 0 },
+        "states": [
+            { "name": "rating", "type": "PlaybackState" },
+            { "name": "submitted", "type": "PlaybackState", "reset_context": "stars" },
+            { "name": "restart", "type": "PlaybackState", "reset_context": "*" }
+        ],
+        "transitions": [
+            { "type": "Transition", "from_state": 0, "to_state": 1, "string_event": { "value": "next" } },
+            { "type": "Transition", "from_state": 1, "to_state": 2, "string_event": { "value": "next" } },
+            { "type": "Transition", "from_state": 2, "to_state": 0, "string_event": { "value": "next" } }
+        ],
+        "listeners": [],
+        "context_variables": [
+            { "type": "Numeric", "key": "stars", "value": 0 },
+            { "type": "String", "key": "comment", "value": "" },
+            { "type": "Boolean", "key": "rated", "value": false }
+        ]
+    }"#;
+
+    fn assert_context(player: &DotLottiePlayer, stars: f32, comment: &str, rated: bool) {
+        let state_machine = player.get_state_machine();
+        let state_machine = state_machine.read().unwrap();
+        let state_machine = state_machine.as_ref().unwrap();
+
+        assert_eq!(state_machine.get_numeric_context("stars"), Some(stars));
+        assert_eq!(
+            state_machine.get_string_context("comment"),
+            Some(comment.to_string())
+        );
+        assert_eq!(state_machine.get_bool_context("rated"), Some(rated));
+    }
+
+    #[test]
+    fn test_reset_context() {
+        let player = DotLottiePlayer::new(Config::default());
+        let observer = Arc::new(ResetObserver::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/test.lottie"), 100, 100);
+
+        assert!(player.load_state_machine_data(STATE_MACHINE));
+        assert!(player.state_machine_subscribe(observer.clone()));
+        assert!(player.start_state_machine());
+
+        assert!(player.set_state_machine_numeric_context("stars", 4.0));
+        assert!(player.set_state_machine_string_context("comment", "great"));
+        assert!(player.set_state_machine_boolean_context("rated", true));
+        assert_context(&player, 4.0, "great", true);
+
+        // Only the named key is restored
+        player.post_string_event("next");
+        assert_context(&player, 0.0, "great", true);
+        assert_eq!(*observer.reset_keys.read().unwrap(), vec!["stars"]);
+
+        // The wildcard restores every key, observers are only told about the changed ones
+        player.post_string_event("next");
+        assert_context(&player, 0.0, "", false);
+        assert_eq!(
+            *observer.reset_keys.read().unwrap(),
+            vec!["stars", "comment", "rated"]
+        );
+
+        // States without reset_context leave the context untouched
+        assert!(player.set_state_machine_numeric_context("stars", 2.0));
+        player.post_string_event("next");
+        assert_context(&player, 2.0, "", false);
+        assert_eq!(observer.reset_keys.read().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_reset_context_on_start() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/test.lottie"), 100, 100);
+
+        let state_machine = STATE_MACHINE.replace(r#""initial": 0"#, r#""initial": 1"#);
+
+        assert!(player.load_state_machine_data(&state_machine));
+        assert!(player.set_state_machine_numeric_context("stars", 5.0));
+
+        // The initial state is entered on start, resetting its context
+        assert!(player.start_state_machine());
+        assert_context(&player, 0.0, "", false);
+    }
+}
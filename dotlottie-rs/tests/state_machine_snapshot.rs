@@ -0,0 +1,145 @@
+mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::current_state_path;
+    use dotlottie_rs::{Config, DotLottiePlayer, StateMachineSnapshot, StateMachineStatus};
+
+    const STATE_MACHINE: &str = r#"{
+        "descriptor": { "id": "snapshot", "initial": "pigeon" },
+        "states": [
+            { "name": "pigeon", "type": "PlaybackState", "marker": "bird", "autoplay": true },
+            { "name": "explosion", "type": "PlaybackState", "marker": "explosion", "autoplay": true },
+            { "name": "feather", "type": "PlaybackState", "marker": "feather", "loop": true }
+        ],
+        "transitions": [
+            {
+                "type": "Transition",
+                "from_state": "pigeon",
+                "to_state": "explosion",
+                "string_event": { "value": "explode" },
+                "actions": [{ "type": "Increment", "context_key": "explosions" }]
+            },
+            { "type": "Transition", "from_state": "explosion", "to_state": "feather", "on_complete_event": {} }
+        ],
+        "listeners": [{ "type": "PointerDown", "target": "pigeon" }],
+        "context_variables": [
+            { "type": "Numeric", "key": "explosions", "value": 0 },
+            { "type": "String", "key": "name", "value": "pigeon" },
+            { "type": "Boolean", "key": "exploded", "value": false }
+        ]
+    }"#;
+
+    fn load_player(state_machine: &str) -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+        assert!(player.load_state_machine_data(state_machine));
+
+        player
+    }
+
+    #[test]
+    fn test_to_json_round_trip() {
+        let player = load_player(STATE_MACHINE);
+        let definition = player.state_machine_to_json();
+
+        // Unset values are left out
+        assert!(!definition.is_empty());
+        assert!(!definition.contains("null"));
+
+        let reloaded = load_player(&definition);
+        assert_eq!(reloaded.state_machine_to_json(), definition);
+
+        let player = DotLottiePlayer::new(Config::default());
+        assert_eq!(player.state_machine_to_json(), "");
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let player = load_player(STATE_MACHINE);
+
+        assert!(player.start_state_machine());
+        player.post_string_event("explode");
+        assert!(player.set_state_machine_boolean_context("exploded", true));
+
+        let snapshot = player.state_machine_snapshot();
+        let parsed: StateMachineSnapshot = serde_json::from_str(&snapshot).unwrap();
+
+        assert_eq!(parsed.id, "snapshot");
+        assert_eq!(parsed.status, StateMachineStatus::Running);
+        assert_eq!(parsed.current_state.as_deref(), Some("explosion"));
+        assert_eq!(parsed.numeric_context.get("explosions"), Some(&1.0));
+
+        // After a restart, the same state machine is loaded again and put back into place
+        let restored = load_player(STATE_MACHINE);
+
+        assert!(restored.restore_state_machine_snapshot(&snapshot));
+        assert_eq!(current_state_path(&restored), "explosion");
+        assert_eq!(restored.state_machine_snapshot(), snapshot);
+        assert!(restored.is_playing());
+
+        let state_machine = restored.get_state_machine();
+        let state_machine = state_machine.read().unwrap();
+        let state_machine = state_machine.as_ref().unwrap();
+
+        assert_eq!(state_machine.status, StateMachineStatus::Running);
+        assert_eq!(state_machine.get_numeric_context("explosions"), Some(1.0));
+        assert_eq!(
+            state_machine.get_string_context("name"),
+            Some("pigeon".to_string())
+        );
+        assert_eq!(state_machine.get_bool_context("exploded"), Some(true));
+    }
+
+    #[test]
+    fn test_restore_invalid_snapshot() {
+        let player = load_player(STATE_MACHINE);
+        let snapshot = player.state_machine_snapshot();
+
+        // Another state machine
+        let other = load_player(&STATE_MACHINE.replace(r#""id": "snapshot""#, r#""id": "other""#));
+        assert!(!other.restore_state_machine_snapshot(&snapshot));
+        assert!(other.last_error_message().contains("'snapshot'"));
+
+        // A state that doesn't exist anymore
+        let renamed = load_player(&STATE_MACHINE.replace("\"pigeon\"", "\"bird\""));
+        assert!(!renamed.restore_state_machine_snapshot(&snapshot));
+
+        assert!(!player.restore_state_machine_snapshot("{}"));
+
+        let empty = DotLottiePlayer::new(Config::default());
+        assert!(!empty.restore_state_machine_snapshot(&snapshot));
+    }
+
+    #[test]
+    fn test_restore_ambiguous_snapshot() {
+        // States referenced by index can share a name
+        let state_machine = r#"{
+            "descriptor": { "id": "duplicates", "initial": 0 },
+            "states": [
+                { "name": "pigeon", "type": "PlaybackState", "marker": "bird" },
+                { "name": "explosion", "type": "PlaybackState", "marker": "explosion" },
+                { "name": "explosion", "type": "PlaybackState", "marker": "feather" }
+            ],
+            "transitions": [
+                { "type": "Transition", "from_state": 0, "to_state": 1, "string_event": { "value": "explode" } }
+            ],
+            "listeners": [],
+            "context_variables": []
+        }"#;
+        let player = load_player(state_machine);
+
+        assert!(player.start_state_machine());
+        player.post_string_event("explode");
+        assert_eq!(current_state_path(&player), "explosion");
+
+        let snapshot = player.state_machine_snapshot();
+        let restored = load_player(state_machine);
+
+        assert!(!restored.restore_state_machine_snapshot(&snapshot));
+        assert!(restored
+            .last_error_message()
+            .contains("Several states are at 'explosion'"));
+    }
+}
@@ -0,0 +1,112 @@
+mod test_utils;
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::current_state_path;
+    use dotlottie_rs::{Config, DotLottiePlayer};
+
+    fn load_player(initial: &str, states: &str, transitions: &str) -> (DotLottiePlayer, bool) {
+        let player = DotLottiePlayer::new(Config::default());
+
+        player.load_dotlottie_data(include_bytes!("fixtures/exploding_pigeon.lottie"), 100, 100);
+
+        let state_machine = format!(
+            r#"{{
+                "descriptor": {{ "id": "references", "initial": {} }},
+                "states": {},
+                "transitions": {},
+                "listeners": [],
+                "context_variables": []
+            }}"#,
+            initial, states, transitions
+        );
+
+        let loaded = player.load_state_machine_data(&state_machine);
+
+        (player, loaded)
+    }
+
+    const TRANSITIONS: &str = r#"[
+        { "type": "Transition", "from_state": "pigeon", "to_state": "explosion", "string_event": { "value": "explode" } },
+        { "type": "Transition", "from_state": "explosion", "to_state": "feather", "string_event": { "value": "fall" } }
+    ]"#;
+
+    #[test]
+    fn test_reordering_states_keeps_references() {
+        for states in [
+            r#"[
+                { "name": "pigeon", "type": "PlaybackState", "marker": "bird" },
+                { "name": "explosion", "type": "PlaybackState", "marker": "explosion" },
+                { "name": "feather", "type": "PlaybackState", "marker": "feather" }
+            ]"#,
+            r#"[
+                { "name": "feather", "type": "PlaybackState", "marker": "feather" },
+                { "name": "explosion", "type": "PlaybackState", "marker": "explosion" },
+                { "name": "pigeon", "type": "PlaybackState", "marker": "bird" }
+            ]"#,
+        ] {
+            let (player, loaded) = load_player(r#""pigeon""#, states, TRANSITIONS);
+
+            assert!(loaded);
+            assert!(player.start_state_machine());
+            assert_eq!(current_state_path(&player), "pigeon");
+
+            player.post_string_event("explode");
+            assert_eq!(current_state_path(&player), "explosion");
+
+            player.post_string_event("fall");
+            assert_eq!(current_state_path(&player), "feather");
+        }
+    }
+
+    #[test]
+    fn test_named_and_numeric_references() {
+        let (player, loaded) = load_player(
+            "0",
+            r#"[
+                { "name": "toggle", "type": "PlaybackState", "initial": "off" },
+                { "name": "on", "type": "PlaybackState", "marker": "explosion", "parent": "toggle" },
+                { "name": "off", "type": "PlaybackState", "marker": "bird", "parent": 0 }
+            ]"#,
+            r#"[
+                { "type": "Transition", "from_state": "off", "to_state": 1, "string_event": { "value": "toggle" } },
+                { "type": "Transition", "from_state": 1, "to_state": "off", "string_event": { "value": "toggle" } }
+            ]"#,
+        );
+
+        assert!(loaded);
+        assert!(player.start_state_machine());
+        assert_eq!(current_state_path(&player), "toggle/off");
+
+        player.post_string_event("toggle");
+        assert_eq!(current_state_path(&player), "toggle/on");
+
+        player.post_string_event("toggle");
+        assert_eq!(current_state_path(&player), "toggle/off");
+    }
+
+    #[test]
+    fn test_invalid_references() {
+        let states = r#"[
+            { "name": "pigeon", "type": "PlaybackState", "marker": "bird" },
+            { "name": "explosion", "type": "PlaybackState", "marker": "explosion" },
+            { "name": "explosion", "type": "PlaybackState", "marker": "feather" }
+        ]"#;
+
+        // Sharing a name is fine as long as the name isn't used to reference a state
+        let (_, loaded) = load_player(r#""pigeon""#, states, "[]");
+        assert!(loaded);
+
+        let (player, loaded) = load_player(r#""pigeon""#, states, TRANSITIONS);
+        assert!(!loaded);
+        assert!(player
+            .last_error_message()
+            .contains("Several states are named 'explosion'"));
+
+        let (player, loaded) = load_player(r#""bird""#, states, "[]");
+        assert!(!loaded);
+        assert!(player
+            .last_error_message()
+            .contains("There is no state named 'bird'"));
+    }
+}
@@ -0,0 +1,150 @@
+mod test_utils;
+
+use std::sync::Arc;
+
+use crate::test_utils::{advance, current_state_name, HEIGHT, WIDTH};
+use dotlottie_rs::{Config, DotLottiePlayer, ManualClock};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_player(
+        clock: &ManualClock,
+        transitions: &str,
+        context_variables: &str,
+    ) -> DotLottiePlayer {
+        let player = DotLottiePlayer::new(Config::default());
+        player.set_clock(Arc::new(clock.clone()));
+
+        assert!(player.load_dotlottie_data(
+            include_bytes!("fixtures/exploding_pigeon.lottie"),
+            WIDTH,
+            HEIGHT
+        ));
+
+        let state_machine = format!(
+            r#"{{
+                "descriptor": {{ "id": "timers", "initial": 0 }},
+                "states": [
+                    {{ "name": "pigeon", "type": "PlaybackState", "marker": "bird" }},
+                    {{ "name": "explosion", "type": "PlaybackState", "marker": "explosion" }}
+                ],
+                "transitions": {},
+                "listeners": [],
+                "context_variables": {}
+            }}"#,
+            transitions, context_variables
+        );
+
+        assert!(player.load_state_machine_data(&state_machine));
+        assert!(player.start_state_machine());
+
+        player
+    }
+
+    #[test]
+    fn test_after_transition() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"[
+                { "type": "Transition", "from_state": 0, "to_state": 1, "string_event": { "value": "explode" } },
+                { "type": "Transition", "from_state": 1, "to_state": 0, "after_event": { "duration": 3 } }
+            ]"#,
+            "[]",
+        );
+
+        player.post_string_event("explode");
+        assert_eq!(current_state_name(&player), "explosion");
+
+        advance(&player, &clock, 2.0);
+        assert_eq!(current_state_name(&player), "explosion");
+
+        // Any posted event restarts the idle time
+        player.post_string_event("poke");
+
+        advance(&player, &clock, 2.0);
+        assert_eq!(current_state_name(&player), "explosion");
+
+        advance(&player, &clock, 1.5);
+        assert_eq!(current_state_name(&player), "pigeon");
+    }
+
+    #[test]
+    fn test_delay_transition() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"[
+                {
+                    "type": "Transition",
+                    "from_state": 0,
+                    "to_state": 1,
+                    "delay_event": { "duration": 0.5 },
+                    "guards": [{ "type": "Boolean", "context_key": "armed", "condition_type": "Equal", "compare_to": true }]
+                }
+            ]"#,
+            r#"[{ "type": "Boolean", "key": "armed", "value": false }]"#,
+        );
+
+        advance(&player, &clock, 0.25);
+        assert_eq!(current_state_name(&player), "pigeon");
+
+        // The delay has passed but the guard isn't met
+        advance(&player, &clock, 0.5);
+        assert_eq!(current_state_name(&player), "pigeon");
+
+        // Events don't restart the delay, the transition is taken once the guard is met
+        assert!(player.set_state_machine_boolean_context("armed", true));
+        player.post_string_event("poke");
+
+        advance(&player, &clock, 0.01);
+        assert_eq!(current_state_name(&player), "explosion");
+    }
+
+    #[test]
+    fn test_every_transition() {
+        let clock = ManualClock::default();
+        let player = load_player(
+            &clock,
+            r#"[
+                {
+                    "type": "Transition",
+                    "from_state": 0,
+                    "to_state": 0,
+                    "every_event": { "interval": 1 },
+                    "actions": [{ "type": "Increment", "context_key": "ticks" }]
+                }
+            ]"#,
+            r#"[{ "type": "Numeric", "key": "ticks", "value": 0 }]"#,
+        );
+
+        let ticks = || {
+            player
+                .get_state_machine()
+                .read()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .get_numeric_context("ticks")
+        };
+
+        advance(&player, &clock, 0.5);
+        assert_eq!(ticks(), Some(0.0));
+
+        advance(&player, &clock, 0.75);
+        assert_eq!(ticks(), Some(1.0));
+
+        advance(&player, &clock, 0.5);
+        assert_eq!(ticks(), Some(1.0));
+
+        advance(&player, &clock, 0.5);
+        assert_eq!(ticks(), Some(2.0));
+
+        // Nothing happens while the state machine is stopped
+        assert!(player.stop_state_machine());
+        advance(&player, &clock, 5.0);
+        assert_eq!(ticks(), Some(2.0));
+    }
+}
@@ -0,0 +1,77 @@
+mod test_utils;
+
+use crate::test_utils::{HEIGHT, WIDTH};
+use dotlottie_rs::{Config, DiagnosticSeverity, DotLottiePlayer};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_machine(animation_id: &str, to_state: u32, target: &str) -> String {
+        format!(
+            r#"{{
+                "descriptor": {{ "id": "validation", "initial": 0 }},
+                "states": [
+                    {{ "name": "pigeon", "type": "PlaybackState", "animation_id": "{}" }},
+                    {{ "name": "explosion", "type": "PlaybackState", "marker": "explosion" }}
+                ],
+                "transitions": [{{
+                    "type": "Transition",
+                    "from_state": 0,
+                    "to_state": {},
+                    "on_pointer_down_event": {{ "target": "{}" }},
+                    "guards": [{{ "type": "Numeric", "context_key": "rating", "condition_type": "Equal", "compare_to": 1 }}]
+                }}],
+                "listeners": [],
+                "context_variables": [{{ "type": "Numeric", "key": "rating", "value": 0 }}]
+            }}"#,
+            animation_id, to_state, target
+        )
+    }
+
+    #[test]
+    fn test_validate_against_animation() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        assert!(player.load_dotlottie_data(
+            include_bytes!("fixtures/exploding_pigeon.lottie"),
+            WIDTH,
+            HEIGHT
+        ));
+
+        let diagnostics = player.validate_state_machine(&state_machine("bird", 3, "not_a_layer"));
+        let issues: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.path.as_str()))
+            .collect();
+
+        assert_eq!(
+            issues,
+            vec![
+                (DiagnosticSeverity::Error, "states[0].animation_id"),
+                (DiagnosticSeverity::Error, "transitions[0].to_state"),
+                (
+                    DiagnosticSeverity::Warning,
+                    "transitions[0].on_pointer_down_event.target"
+                ),
+            ]
+        );
+
+        // Validating doesn't load the state machine
+        assert!(player.get_state_machine().read().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_validate_without_animation() {
+        let player = DotLottiePlayer::new(Config::default());
+
+        // Nothing is known about the animation, only the definition itself is checked
+        let diagnostics = player.validate_state_machine(&state_machine("bird", 1, "not_a_layer"));
+        assert!(diagnostics.is_empty());
+
+        let diagnostics = player.validate_state_machine("{ \"states\": [] }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert!(!diagnostics[0].message.is_empty());
+    }
+}
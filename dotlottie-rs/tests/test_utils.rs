@@ -1,2 +1,42 @@
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use dotlottie_rs::{states::StateTrait, DotLottiePlayer, ManualClock};
+
 pub const WIDTH: u32 = 100;
 pub const HEIGHT: u32 = 100;
+
+// The name of the current state of the loaded state machine
+pub fn current_state_name(player: &DotLottiePlayer) -> String {
+    player
+        .get_state_machine()
+        .read()
+        .unwrap()
+        .as_ref()
+        .unwrap()
+        .get_current_state()
+        .unwrap()
+        .read()
+        .unwrap()
+        .get_name()
+}
+
+// The path of the current state of the loaded state machine
+pub fn current_state_path(player: &DotLottiePlayer) -> String {
+    player
+        .get_state_machine()
+        .read()
+        .unwrap()
+        .as_ref()
+        .unwrap()
+        .get_current_state_path()
+        .unwrap()
+}
+
+// Move the clock of the player forward and render the frame at the new time, as hosts do
+pub fn advance(player: &DotLottiePlayer, clock: &ManualClock, seconds: f32) {
+    clock.advance(Duration::from_secs_f32(seconds));
+    player.set_frame(player.request_frame());
+    player.render();
+}